@@ -1,7 +1,9 @@
-//! Admin instructions - Pause, unpause, and admin rotation
+//! Admin instructions - Pause, unpause, wind-down, and admin and fee recipient rotation
 
 use anchor_lang::prelude::*;
-use crate::state::VaultConfig;
+use crate::state::{CovalidatorSet, IncoConfig, LockContext, PositionTracker, VaultConfig, VaultPDA};
+use crate::math::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+use super::verify_decryption::message_versions;
 
 /// Pause the vault (emergency)
 pub fn handler_pause(ctx: Context<AdminAction>) -> Result<()> {
@@ -39,6 +41,27 @@ pub fn handler_unpause(ctx: Context<AdminAction>) -> Result<()> {
     Ok(())
 }
 
+/// Permanently disable new deposits (one-way wind-down)
+///
+/// Unlike pause, collect and withdraw keep working so users can exit, and
+/// there is no instruction that turns deposits back on.
+pub fn handler_disable_deposits_forever(ctx: Context<AdminAction>) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    ctx.accounts.vault_config.disable_deposits_forever();
+    
+    emit!(DepositsPermanentlyDisabled {
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Deposits PERMANENTLY DISABLED by admin: {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
 /// Propose new admin (step 1)
 pub fn handler_propose_admin(ctx: Context<AdminAction>, new_admin: Pubkey) -> Result<()> {
     require!(
@@ -74,12 +97,64 @@ pub fn handler_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
     Ok(())
 }
 
+/// Propose new fee recipient (step 1)
+///
+/// Proposing the default pubkey cancels a pending rotation.
+pub fn handler_propose_fee_recipient(ctx: Context<AdminAction>, new_recipient: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    ctx.accounts.vault_config.propose_fee_recipient(new_recipient);
+    
+    emit!(FeeRecipientProposed {
+        admin: ctx.accounts.admin.key(),
+        current_recipient: ctx.accounts.vault_config.fee_recipient,
+        proposed_recipient: new_recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("New fee recipient proposed: {}", new_recipient);
+    Ok(())
+}
+
+/// Accept the fee recipient role (step 2)
+pub fn handler_accept_fee_recipient(ctx: Context<AcceptFeeRecipient>) -> Result<()> {
+    let old_recipient = ctx.accounts.vault_config.fee_recipient;
+    
+    ctx.accounts.vault_config.accept_fee_recipient(ctx.accounts.new_recipient.key())?;
+    
+    emit!(FeeRecipientRotated {
+        old_recipient,
+        new_recipient: ctx.accounts.new_recipient.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Fee recipient rotated from {} to {}", old_recipient, ctx.accounts.new_recipient.key());
+    Ok(())
+}
+
 /// Update protocol parameters
+#[allow(clippy::too_many_arguments)]
 pub fn handler_update_params(
     ctx: Context<AdminAction>,
     max_slippage_bps: Option<u16>,
     min_liquidity: Option<u128>,
     max_liquidity: Option<u128>,
+    max_total_liquidity_per_vault: Option<u128>,
+    absolute_max_slippage_bps: Option<u16>,
+    min_tick_width: Option<i32>,
+    max_tick_width: Option<i32>,
+    min_collect_threshold: Option<u64>,
+    safe_tick_lower: Option<i32>,
+    safe_tick_upper: Option<i32>,
+    abandonment_period: Option<i64>,
+    max_positions_per_vault: Option<u32>,
+    accepted_message_versions: Option<u8>,
+    max_handles_per_verify: Option<u8>,
+    large_withdrawal_threshold: Option<u128>,
+    withdrawal_delay: Option<i64>,
 ) -> Result<()> {
     require!(
         ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
@@ -87,25 +162,358 @@ pub fn handler_update_params(
     );
     
     let config = &mut ctx.accounts.vault_config;
+    let before = (**config).clone();
+    
+    if let Some(ceiling) = absolute_max_slippage_bps {
+        require!(ceiling <= 10000, AdminError::InvalidSlippage); // Max 100%
+        config.absolute_max_slippage_bps = ceiling;
+    }
     
     if let Some(slippage) = max_slippage_bps {
         require!(slippage <= 10000, AdminError::InvalidSlippage); // Max 100%
         config.default_max_slippage_bps = slippage;
     }
     
+    // The default must stay usable under the ceiling
+    require!(
+        config.default_max_slippage_bps <= config.absolute_max_slippage_bps,
+        AdminError::InvalidSlippage
+    );
+    
     if let Some(min_liq) = min_liquidity {
+        require!(min_liq > 0, AdminError::InvalidLiquidityBounds);
         config.min_liquidity = min_liq;
     }
     
     if let Some(max_liq) = max_liquidity {
-        require!(max_liq > config.min_liquidity, AdminError::InvalidLiquidityBounds);
         config.max_liquidity = max_liq;
     }
     
+    // Checked once both are applied, so raising only the minimum can't pass the maximum
+    require!(
+        config.min_liquidity < config.max_liquidity,
+        AdminError::InvalidLiquidityBounds
+    );
+    
+    if let Some(vault_cap) = max_total_liquidity_per_vault {
+        require!(vault_cap >= config.min_liquidity, AdminError::InvalidLiquidityBounds);
+        config.max_total_liquidity_per_vault = vault_cap;
+    }
+    
+    if let Some(min_width) = min_tick_width {
+        config.min_tick_width = min_width;
+    }
+    
+    if let Some(max_width) = max_tick_width {
+        config.max_tick_width = max_width;
+    }
+    
+    require!(
+        config.min_tick_width > 0 && config.min_tick_width <= config.max_tick_width,
+        AdminError::InvalidTickWidthBounds
+    );
+    
+    if let Some(threshold) = min_collect_threshold {
+        config.min_collect_threshold = threshold;
+    }
+    
+    if let Some(lower) = safe_tick_lower {
+        config.safe_tick_lower = lower;
+    }
+    
+    if let Some(upper) = safe_tick_upper {
+        config.safe_tick_upper = upper;
+    }
+    
+    require!(
+        MIN_TICK_INDEX <= config.safe_tick_lower
+            && config.safe_tick_lower <= config.safe_tick_upper
+            && config.safe_tick_upper <= MAX_TICK_INDEX,
+        AdminError::InvalidSafeTickBand
+    );
+    
+    if let Some(period) = abandonment_period {
+        require!(
+            period >= VaultConfig::MIN_ABANDONMENT_PERIOD,
+            AdminError::InvalidAbandonmentPeriod
+        );
+        config.abandonment_period = period;
+    }
+    
+    if let Some(max_positions) = max_positions_per_vault {
+        config.max_positions_per_vault = max_positions;
+    }
+    
+    if let Some(versions) = accepted_message_versions {
+        require!(
+            versions != 0 && versions & !message_versions::KNOWN_MASK == 0,
+            AdminError::InvalidMessageVersions
+        );
+        config.accepted_message_versions = versions;
+    }
+    
+    if let Some(max_handles) = max_handles_per_verify {
+        require!(max_handles > 0, AdminError::InvalidMaxHandles);
+        config.max_handles_per_verify = max_handles;
+    }
+    
+    if let Some(threshold) = large_withdrawal_threshold {
+        config.large_withdrawal_threshold = threshold;
+    }
+    
+    if let Some(delay) = withdrawal_delay {
+        require!(delay >= 0, AdminError::InvalidWithdrawalDelay);
+        config.withdrawal_delay = delay;
+    }
+    
+    // Old values are reported only for the parameters that were passed
+    emit!(ParamsUpdated {
+        admin: ctx.accounts.admin.key(),
+        max_slippage_bps,
+        old_max_slippage_bps: max_slippage_bps.map(|_| before.default_max_slippage_bps),
+        min_liquidity,
+        old_min_liquidity: min_liquidity.map(|_| before.min_liquidity),
+        max_liquidity,
+        old_max_liquidity: max_liquidity.map(|_| before.max_liquidity),
+        max_total_liquidity_per_vault,
+        old_max_total_liquidity_per_vault: max_total_liquidity_per_vault
+            .map(|_| before.max_total_liquidity_per_vault),
+        absolute_max_slippage_bps,
+        old_absolute_max_slippage_bps: absolute_max_slippage_bps
+            .map(|_| before.absolute_max_slippage_bps),
+        min_tick_width,
+        old_min_tick_width: min_tick_width.map(|_| before.min_tick_width),
+        max_tick_width,
+        old_max_tick_width: max_tick_width.map(|_| before.max_tick_width),
+        min_collect_threshold,
+        old_min_collect_threshold: min_collect_threshold.map(|_| before.min_collect_threshold),
+        safe_tick_lower,
+        old_safe_tick_lower: safe_tick_lower.map(|_| before.safe_tick_lower),
+        safe_tick_upper,
+        old_safe_tick_upper: safe_tick_upper.map(|_| before.safe_tick_upper),
+        abandonment_period,
+        old_abandonment_period: abandonment_period.map(|_| before.abandonment_period),
+        max_positions_per_vault,
+        old_max_positions_per_vault: max_positions_per_vault.map(|_| before.max_positions_per_vault),
+        accepted_message_versions,
+        old_accepted_message_versions: accepted_message_versions
+            .map(|_| before.accepted_message_versions),
+        max_handles_per_verify,
+        old_max_handles_per_verify: max_handles_per_verify.map(|_| before.max_handles_per_verify),
+        large_withdrawal_threshold,
+        old_large_withdrawal_threshold: large_withdrawal_threshold
+            .map(|_| before.large_withdrawal_threshold),
+        withdrawal_delay,
+        old_withdrawal_delay: withdrawal_delay.map(|_| before.withdrawal_delay),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     msg!("Vault parameters updated");
     Ok(())
 }
 
+/// Update performance fee parameters
+pub fn handler_set_fee_params(
+    ctx: Context<AdminAction>,
+    performance_fee_bps: Option<u16>,
+    referrer_share_bps: Option<u16>,
+    high_water_mark_fees: Option<bool>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    let config = &mut ctx.accounts.vault_config;
+    
+    if let Some(fee_bps) = performance_fee_bps {
+        require!(fee_bps <= VaultConfig::MAX_FEE_BPS, AdminError::InvalidFeeBps);
+        config.performance_fee_bps = fee_bps;
+    }
+    
+    if let Some(share_bps) = referrer_share_bps {
+        require!(share_bps <= 10000, AdminError::InvalidFeeBps);
+        config.referrer_share_bps = share_bps;
+    }
+    
+    if let Some(enabled) = high_water_mark_fees {
+        config.high_water_mark_fees = enabled;
+    }
+    
+    emit!(FeeParamsUpdated {
+        admin: ctx.accounts.admin.key(),
+        performance_fee_bps: config.performance_fee_bps,
+        fee_recipient: config.fee_recipient,
+        referrer_share_bps: config.referrer_share_bps,
+        high_water_mark_fees: config.high_water_mark_fees,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Fee parameters updated");
+    Ok(())
+}
+
+/// Set or clear a position's performance fee rate override
+///
+/// `None` returns the position to `VaultConfig::performance_fee_bps`.
+pub fn handler_set_fee_override(
+    ctx: Context<SetFeeOverride>,
+    fee_bps_override: Option<u16>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    if let Some(fee_bps) = fee_bps_override {
+        require!(fee_bps <= VaultConfig::MAX_FEE_BPS, AdminError::InvalidFeeBps);
+    }
+    
+    let tracker = &mut ctx.accounts.position_tracker;
+    tracker.fee_bps_override = fee_bps_override;
+    
+    emit!(FeeOverrideSet {
+        admin: ctx.accounts.admin.key(),
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        fee_bps_override,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Fee override for {}: {:?}", tracker.lp_position_mint, fee_bps_override);
+    Ok(())
+}
+
+/// Clear a stuck reentrancy lock on a user's vault
+///
+/// Fails with `LockNotStale` unless the lock is at least
+/// `VaultPDA::STALE_LOCK_SLOTS` old; an unlocked vault is a no-op.
+pub fn handler_force_unlock_vault(ctx: Context<ForceUnlockVault>) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    // Only a lock left behind by an earlier transaction may be cleared
+    let was_locked = ctx.accounts.vault_pda.locked;
+    let locked_at_slot = ctx.accounts.vault_pda.locked_at_slot;
+    if was_locked {
+        let current_slot = Clock::get()?.slot;
+        if !ctx.accounts.vault_pda.is_lock_stale(current_slot) {
+            msg!("Lock taken at slot {} is not stale at slot {}", locked_at_slot, current_slot);
+            return err!(AdminError::LockNotStale);
+        }
+    }
+    let cx = LockContext {
+        vault: ctx.accounts.vault_pda.key(),
+        instruction: crate::instruction::ForceUnlockVault::DISCRIMINATOR,
+    };
+    ctx.accounts.vault_pda.unlock(cx);
+    
+    emit!(VaultForceUnlocked {
+        admin: ctx.accounts.admin.key(),
+        vault: ctx.accounts.vault_pda.key(),
+        owner: ctx.accounts.vault_pda.owner,
+        was_locked,
+        locked_at_slot,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Vault {} force-unlocked by admin (was locked: {})", ctx.accounts.vault_pda.key(), was_locked);
+    Ok(())
+}
+
+/// Set the Inco Lightning discriminator overrides
+pub fn handler_set_inco_discriminators(
+    ctx: Context<SetIncoDiscriminators>,
+    new_euint128: Option<[u8; 8]>,
+    e_add: Option<[u8; 8]>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    let inco_config = &mut ctx.accounts.inco_config;
+    if inco_config.new_euint128_discriminator == [0u8; 8] {
+        // Freshly created by init_if_needed
+        inco_config.initialize(ctx.bumps.inco_config);
+    }
+    
+    if let Some(disc) = new_euint128 {
+        require!(disc != [0u8; 8], AdminError::InvalidDiscriminator);
+        inco_config.new_euint128_discriminator = disc;
+    }
+    
+    if let Some(disc) = e_add {
+        require!(disc != [0u8; 8], AdminError::InvalidDiscriminator);
+        inco_config.e_add_discriminator = disc;
+    }
+    
+    require!(
+        inco_config.new_euint128_discriminator != inco_config.e_add_discriminator,
+        AdminError::InvalidDiscriminator
+    );
+    
+    emit!(IncoDiscriminatorsUpdated {
+        admin: ctx.accounts.admin.key(),
+        new_euint128: inco_config.new_euint128_discriminator,
+        e_add: inco_config.e_add_discriminator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Inco discriminators updated");
+    Ok(())
+}
+
+/// Trust an additional Inco covalidator key
+///
+/// Creates the covalidator set on first use. Attestation checks given the
+/// set accept any of its members instead of the compiled-in key.
+pub fn handler_add_covalidator(ctx: Context<ManageCovalidators>, covalidator: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    let set = &mut ctx.accounts.covalidator_set;
+    if set.count == 0 {
+        // Freshly created by init_if_needed (or emptied; the bump is the same)
+        set.bump = ctx.bumps.covalidator_set;
+    }
+    set.add(covalidator)?;
+    
+    emit!(CovalidatorAdded {
+        admin: ctx.accounts.admin.key(),
+        covalidator,
+        count: set.count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Covalidator trusted: {} ({} total)", covalidator, set.count);
+    Ok(())
+}
+
+/// Stop trusting an Inco covalidator key
+pub fn handler_remove_covalidator(ctx: Context<ManageCovalidators>, covalidator: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        AdminError::Unauthorized
+    );
+    
+    let set = &mut ctx.accounts.covalidator_set;
+    set.remove(&covalidator)?;
+    
+    emit!(CovalidatorRemoved {
+        admin: ctx.accounts.admin.key(),
+        covalidator,
+        count: set.count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Covalidator removed: {} ({} left)", covalidator, set.count);
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct AdminAction<'info> {
     #[account(mut)]
@@ -129,6 +537,84 @@ pub struct AcceptAdmin<'info> {
     pub vault_config: Account<'info, VaultConfig>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptFeeRecipient<'info> {
+    pub new_recipient: Signer<'info>,
+    
+    #[account(mut, seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ForceUnlockVault<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", vault_pda.owner.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeOverride<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+#[derive(Accounts)]
+pub struct ManageCovalidators<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = CovalidatorSet::LEN,
+        seeds = [b"covalidators"],
+        bump
+    )]
+    pub covalidator_set: Account<'info, CovalidatorSet>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetIncoDiscriminators<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = IncoConfig::LEN,
+        seeds = [b"inco_config"],
+        bump
+    )]
+    pub inco_config: Account<'info, IncoConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[error_code]
 pub enum AdminError {
     #[msg("Unauthorized - not admin")]
@@ -139,6 +625,24 @@ pub enum AdminError {
     InvalidSlippage,
     #[msg("Invalid liquidity bounds")]
     InvalidLiquidityBounds,
+    #[msg("Invalid Inco discriminator")]
+    InvalidDiscriminator,
+    #[msg("Fee basis points must be <= 10000")]
+    InvalidFeeBps,
+    #[msg("Vault lock is not stale yet")]
+    LockNotStale,
+    #[msg("Invalid tick width bounds")]
+    InvalidTickWidthBounds,
+    #[msg("Invalid safe tick band")]
+    InvalidSafeTickBand,
+    #[msg("Abandonment period must be at least 7 days")]
+    InvalidAbandonmentPeriod,
+    #[msg("Accepted message versions must be a non-empty set of known versions")]
+    InvalidMessageVersions,
+    #[msg("Max handles per verify must be positive")]
+    InvalidMaxHandles,
+    #[msg("Withdrawal delay must not be negative")]
+    InvalidWithdrawalDelay,
 }
 
 #[event]
@@ -153,6 +657,50 @@ pub struct VaultUnpaused {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ParamsUpdated {
+    pub admin: Pubkey,
+    pub max_slippage_bps: Option<u16>,
+    pub old_max_slippage_bps: Option<u16>,
+    pub min_liquidity: Option<u128>,
+    pub old_min_liquidity: Option<u128>,
+    pub max_liquidity: Option<u128>,
+    pub old_max_liquidity: Option<u128>,
+    pub max_total_liquidity_per_vault: Option<u128>,
+    pub old_max_total_liquidity_per_vault: Option<u128>,
+    pub absolute_max_slippage_bps: Option<u16>,
+    pub old_absolute_max_slippage_bps: Option<u16>,
+    pub min_tick_width: Option<i32>,
+    pub old_min_tick_width: Option<i32>,
+    pub max_tick_width: Option<i32>,
+    pub old_max_tick_width: Option<i32>,
+    pub min_collect_threshold: Option<u64>,
+    pub old_min_collect_threshold: Option<u64>,
+    pub safe_tick_lower: Option<i32>,
+    pub old_safe_tick_lower: Option<i32>,
+    pub safe_tick_upper: Option<i32>,
+    pub old_safe_tick_upper: Option<i32>,
+    pub abandonment_period: Option<i64>,
+    pub old_abandonment_period: Option<i64>,
+    pub max_positions_per_vault: Option<u32>,
+    pub old_max_positions_per_vault: Option<u32>,
+    pub accepted_message_versions: Option<u8>,
+    pub old_accepted_message_versions: Option<u8>,
+    pub max_handles_per_verify: Option<u8>,
+    pub old_max_handles_per_verify: Option<u8>,
+    pub large_withdrawal_threshold: Option<u128>,
+    pub old_large_withdrawal_threshold: Option<u128>,
+    pub withdrawal_delay: Option<i64>,
+    pub old_withdrawal_delay: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositsPermanentlyDisabled {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AdminProposed {
     pub current_admin: Pubkey,
@@ -166,3 +714,71 @@ pub struct AdminRotated {
     pub new_admin: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct FeeRecipientProposed {
+    pub admin: Pubkey,
+    pub current_recipient: Pubkey,
+    pub proposed_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeRecipientRotated {
+    pub old_recipient: Pubkey,
+    pub new_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultForceUnlocked {
+    pub admin: Pubkey,
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub was_locked: bool,
+    pub locked_at_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IncoDiscriminatorsUpdated {
+    pub admin: Pubkey,
+    pub new_euint128: [u8; 8],
+    pub e_add: [u8; 8],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeParamsUpdated {
+    pub admin: Pubkey,
+    pub performance_fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub referrer_share_bps: u16,
+    pub high_water_mark_fees: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeOverrideSet {
+    pub admin: Pubkey,
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub fee_bps_override: Option<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CovalidatorAdded {
+    pub admin: Pubkey,
+    pub covalidator: Pubkey,
+    pub count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CovalidatorRemoved {
+    pub admin: Pubkey,
+    pub covalidator: Pubkey,
+    pub count: u8,
+    pub timestamp: i64,
+}
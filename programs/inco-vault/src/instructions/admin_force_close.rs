@@ -0,0 +1,308 @@
+//! Admin Force Close - Protocol hygiene for abandoned positions
+//!
+//! This instruction:
+//! 1. Requires the admin and a tracker idle for `VaultConfig::abandonment_period`
+//!    (never less than `VaultConfig::MIN_ABANDONMENT_PERIOD`)
+//! 2. Decreases ALL remaining liquidity into the owner's token accounts, with
+//!    minimums no lower than the position's value at the current price less
+//!    the default slippage
+//! 3. Collects owed fees into the same owner accounts
+//! 4. Closes the Whirlpool position and the tracker, refunding rent to the owner
+//!
+//! Nothing is routed to the admin: every token and lamport goes back to the
+//! position owner, so the instruction can clean up state but never seize
+//! funds. Rewards are not collected; a position with rewards still owed cannot
+//! be closed and the call fails with `OwedTokensRemain`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+
+use crate::math;
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::WHIRLPOOL_PROGRAM_ID;
+use super::whirlpool_cpi;
+
+/// Force-close an abandoned position on behalf of its owner
+pub fn handler(
+    ctx: Context<AdminForceClose>,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    // Step 0: Admin + inactivity gate + lock
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.vault_config.admin,
+        ForceCloseError::Unauthorized
+    );
+    ctx.accounts.position_tracker.require_not_closed()?;
+
+    // Configs written before the minimum existed may hold a shorter period
+    let now = Clock::get()?.unix_timestamp;
+    let inactive_secs = now.saturating_sub(ctx.accounts.position_tracker.last_update);
+    let abandonment_period = ctx
+        .accounts
+        .vault_config
+        .abandonment_period
+        .max(VaultConfig::MIN_ABANDONMENT_PERIOD);
+    require!(
+        inactive_secs >= abandonment_period,
+        ForceCloseError::PositionNotAbandoned
+    );
+
+    let instruction = crate::instruction::AdminForceClose::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        whirlpool_cpi::validate_tick_arrays(
+            &accounts.whirlpool.to_account_info(),
+            accounts.tick_array_lower.key,
+            accounts.tick_array_upper.key,
+            accounts.position_tracker.tick_lower,
+            accounts.position_tracker.tick_upper,
+        )?;
+
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.position_tracker.user.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let pre_balance_a = accounts.recovery_account_a.amount;
+        let pre_balance_b = accounts.recovery_account_b.amount;
+
+        // Step 1: Decrease ALL liquidity to the owner
+        let position_info = accounts.whirlpool_position.to_account_info();
+        let liquidity = whirlpool_cpi::read_position_liquidity(&position_info)?;
+
+        // The owner isn't here to set minimums, so never accept less than the
+        // position is worth at the current price, less the default slippage
+        let (floor_a, floor_b) = slippage_floor(accounts, liquidity)?;
+        let token_min_a = token_min_a.max(floor_a);
+        let token_min_b = token_min_b.max(floor_b);
+
+        if liquidity > 0 {
+            whirlpool_cpi::cpi_decrease_liquidity(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.whirlpool.to_account_info(),
+                accounts.token_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                position_info.clone(),
+                accounts.position_token_account.to_account_info(),
+                accounts.recovery_account_a.to_account_info(),
+                accounts.recovery_account_b.to_account_info(),
+                accounts.token_vault_a.to_account_info(),
+                accounts.token_vault_b.to_account_info(),
+                accounts.tick_array_lower.to_account_info(),
+                accounts.tick_array_upper.to_account_info(),
+                signer_seeds,
+                liquidity,
+                token_min_a,
+                token_min_b,
+            )?;
+        }
+        accounts.vault_pda.remove_liquidity(liquidity);
+
+        // Step 2: Collect owed fees to the owner so the position can close
+        whirlpool_cpi::cpi_collect_fees(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            position_info.clone(),
+            accounts.position_token_account.to_account_info(),
+            accounts.recovery_account_a.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.recovery_account_b.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        accounts.recovery_account_a.reload()?;
+        accounts.recovery_account_b.reload()?;
+        let recovered_a = accounts.recovery_account_a.amount.saturating_sub(pre_balance_a);
+        let recovered_b = accounts.recovery_account_b.amount.saturating_sub(pre_balance_b);
+
+        // Step 3: Close the position (rent to the owner)
+        require!(
+            !whirlpool_cpi::position_has_owed_tokens(&position_info)?,
+            ForceCloseError::OwedTokensRemain
+        );
+        whirlpool_cpi::cpi_close_position(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.owner.to_account_info(),
+            position_info,
+            accounts.position_mint.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        accounts.vault_pda.decrement_position_count();
+        // The tracker closes with this call, so its fee can no longer be claimed
+        if accounts.position_tracker.performance_fee_owed {
+            accounts.vault_pda.close_fee_claim();
+        }
+
+        msg!(
+            "FORCE CLOSE: admin {} closed position {} of {} after {}s idle",
+            accounts.admin.key(),
+            accounts.position_tracker.lp_position_mint,
+            accounts.position_tracker.user,
+            inactive_secs
+        );
+
+        emit!(PositionForceClosed {
+            admin: accounts.admin.key(),
+            user: accounts.position_tracker.user,
+            position_tracker: accounts.position_tracker.key(),
+            position_mint: accounts.position_mint.key(),
+            liquidity_withdrawn: liquidity,
+            token_a_recovered: recovered_a,
+            token_b_recovered: recovered_b,
+            inactive_secs,
+            timestamp: now,
+        });
+
+        Ok(())
+    })
+}
+
+/// Least each side of `liquidity` may return: its value at the current pool
+/// price, reduced by the vault's default slippage
+fn slippage_floor(accounts: &AdminForceClose, liquidity: u128) -> Result<(u64, u64)> {
+    let sqrt_price =
+        whirlpool_cpi::read_whirlpool_sqrt_price(&accounts.whirlpool.to_account_info())?;
+    let (expected_a, expected_b) = math::get_amounts_from_liquidity(
+        liquidity,
+        sqrt_price,
+        math::sqrt_price_from_tick_index(accounts.position_tracker.tick_lower)?,
+        math::sqrt_price_from_tick_index(accounts.position_tracker.tick_upper)?,
+        false,
+    )?;
+    let slippage = accounts.vault_config.default_max_slippage_bps;
+    Ok((
+        math::amount_with_slippage_min(expected_a, slippage),
+        math::amount_with_slippage_min(expected_b, slippage),
+    ))
+}
+
+#[derive(Accounts)]
+pub struct AdminForceClose<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Position owner; receives all rent refunds
+    #[account(
+        mut,
+        address = position_tracker.user @ ForceCloseError::InvalidOwner
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // Whirlpool accounts
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        mut,
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ ForceCloseError::WhirlpoolMismatch
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Position (validated by CPI)
+    #[account(mut)]
+    pub whirlpool_position: UncheckedAccount<'info>,
+
+    // LP NFT (decimals 0, supply 1, mint authority revoked by Whirlpool)
+    #[account(
+        mut,
+        constraint = position_mint.key() == position_tracker.lp_position_mint @ ForceCloseError::InvalidPositionMint,
+        constraint = position_mint.decimals == 0 @ ForceCloseError::InvalidPositionMint,
+        constraint = position_mint.supply == 1 @ ForceCloseError::InvalidPositionMint,
+        constraint = position_mint.mint_authority.is_none() @ ForceCloseError::InvalidPositionMint
+    )]
+    pub position_mint: Account<'info, Mint>,
+
+    /// CHECK: Position token account (owned by vault PDA)
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    // Owner token accounts that receive the recovered liquidity and fees
+    #[account(
+        mut,
+        constraint = recovery_account_a.owner == position_tracker.user @ ForceCloseError::InvalidOwner
+    )]
+    pub recovery_account_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recovery_account_b.owner == position_tracker.user @ ForceCloseError::InvalidOwner
+    )]
+    pub recovery_account_b: Account<'info, TokenAccount>,
+
+    // Pool vaults
+    /// CHECK: Token vault A
+    #[account(mut)]
+    pub token_vault_a: UncheckedAccount<'info>,
+
+    /// CHECK: Token vault B
+    #[account(mut)]
+    pub token_vault_b: UncheckedAccount<'info>,
+
+    // Tick arrays
+    /// CHECK: Tick array lower
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array upper
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum ForceCloseError {
+    #[msg("Unauthorized - not admin")]
+    Unauthorized,
+    #[msg("Position has been active within the abandonment period")]
+    PositionNotAbandoned,
+    #[msg("Account is not owned by the position owner")]
+    InvalidOwner,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Position mint is not the tracked Whirlpool LP NFT")]
+    InvalidPositionMint,
+    #[msg("Position still has fees or rewards owed")]
+    OwedTokensRemain,
+}
+
+#[event]
+pub struct PositionForceClosed {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub position_tracker: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity_withdrawn: u128,
+    pub token_a_recovered: u64,
+    pub token_b_recovered: u64,
+    pub inactive_secs: i64,
+    pub timestamp: i64,
+}
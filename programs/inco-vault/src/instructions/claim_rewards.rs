@@ -0,0 +1,258 @@
+//! Claim Rewards - Pays collected reward tokens out to the position owner
+//!
+//! This instruction:
+//! 1. Transfers each reward balance from the vault-owned reward account
+//!    to the owner's token account for the same mint
+//! 2. Subtracts the paid amount from the tracker's encrypted reward total
+//! 3. Emits the cleartext amounts paid
+//!
+//! `collect_all_profits` only measures and encrypts rewards; the tokens stay
+//! in the vault's accounts until claimed here.
+//!
+//! Rewards still pending encryption must be flushed first (via
+//! `encrypt_collected_profits`), otherwise the encrypted totals would be
+//! debited for amounts they do not include yet. Dust below
+//! `min_collect_threshold` does not block a claim: it is paid out first and
+//! cleared from the tracker without touching the encrypted total.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::INCO_LIGHTNING_ID;
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+
+/// Claim collected rewards
+///
+/// `amounts` caps what is paid per reward index; `None` pays each vault
+/// reward account's full balance.
+pub fn handler(ctx: Context<ClaimRewards>, amounts: Option<[u64; 3]>) -> Result<()> {
+    // Step 0: Check not paused + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    let instruction = crate::instruction::ClaimRewards::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        // Dust below the collect threshold never reaches the encrypted totals
+        require!(
+            !accounts
+                .position_tracker
+                .has_encryptable_rewards(accounts.vault_config.min_collect_threshold),
+            ClaimError::ProfitsPendingEncryption
+        );
+
+        let user = accounts.position_tracker.user;
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            user.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let inco_discriminators = IncoDiscriminators::load(&accounts.inco_config)?;
+        let inco_program = accounts.inco_lightning_program.to_account_info();
+        let authority = accounts.user.to_account_info();
+        let vault_info = accounts.vault_pda.to_account_info();
+        let token_program = accounts.token_program.to_account_info();
+
+        let tracker = &accounts.position_tracker;
+        let reward_count = tracker.reward_count as usize;
+        let reward_mints = tracker.reward_mints;
+        let mut pending_rewards = tracker.pending_rewards;
+        let mut handles = [
+            tracker.encrypted_reward_0,
+            tracker.encrypted_reward_1,
+            tracker.encrypted_reward_2,
+        ];
+        let slots = [
+            (&accounts.vault_reward_account_0, &accounts.user_reward_account_0),
+            (&accounts.vault_reward_account_1, &accounts.user_reward_account_1),
+            (&accounts.vault_reward_account_2, &accounts.user_reward_account_2),
+        ];
+
+        let mut claimed = [0u64; 3];
+        for (i, ((vault_account, user_account), handle)) in
+            slots.into_iter().zip(handles.iter_mut()).enumerate().take(reward_count)
+        {
+            let Some(vault_account) = vault_account.as_ref() else {
+                continue;
+            };
+            require!(vault_account.mint == reward_mints[i], ClaimError::RewardMintMismatch);
+
+            let amount = match amounts {
+                Some(amounts) => amounts[i],
+                None => vault_account.amount,
+            };
+            if amount == 0 {
+                continue;
+            }
+            require!(amount <= vault_account.amount, ClaimError::InsufficientRewardBalance);
+            // Pending dust is paid out first, without debiting the encrypted total
+            let dust = pending_rewards[i].min(amount);
+            let tracked = amount - dust;
+            // A zero handle means nothing was ever tracked for this slot
+            require!(tracked == 0 || *handle != 0, ClaimError::RewardNotTracked);
+
+            let user_account = user_account.as_ref().ok_or(ClaimError::MissingRewardAccount)?;
+            require!(user_account.mint == reward_mints[i], ClaimError::RewardMintMismatch);
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    Transfer {
+                        from: vault_account.to_account_info(),
+                        to: user_account.to_account_info(),
+                        authority: vault_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+
+            // Debit the encrypted total by the tracked part of the amount paid
+            pending_rewards[i] -= dust;
+            if tracked > 0 {
+                let amount_handle = inco_lightning_cpi::cpi_new_euint128(
+                    inco_program.clone(),
+                    authority.clone(),
+                    &inco_discriminators,
+                    (tracked as u128).to_le_bytes().to_vec(),
+                    inco_lightning_cpi::amount_types::PLAINTEXT,
+                )?;
+                *handle = inco_lightning_cpi::cpi_e_sub(
+                    inco_program.clone(),
+                    authority.clone(),
+                    &inco_discriminators,
+                    *handle,
+                    amount_handle,
+                )?;
+            }
+
+            claimed[i] = amount;
+            msg!("Reward {} claimed: {}", i, amount);
+        }
+        require!(claimed.iter().any(|amount| *amount > 0), ClaimError::NothingToClaim);
+
+        let tracker = &mut accounts.position_tracker;
+        [tracker.encrypted_reward_0, tracker.encrypted_reward_1, tracker.encrypted_reward_2] = handles;
+        tracker.pending_rewards = pending_rewards;
+        tracker.last_update = Clock::get()?.unix_timestamp;
+
+        emit!(RewardsClaimed {
+            position: tracker.lp_position_mint,
+            user: tracker.user,
+            reward_0: claimed[0],
+            reward_1: claimed[1],
+            reward_2: claimed[2],
+            encrypted_reward_0: tracker.encrypted_reward_0,
+            encrypted_reward_1: tracker.encrypted_reward_1,
+            encrypted_reward_2: tracker.encrypted_reward_2,
+            timestamp: tracker.last_update,
+        });
+
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", user.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == user.key() @ ClaimError::Unauthorized
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", user.key().as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == user.key() @ ClaimError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    // Vault-owned reward accounts (sources; omitted slots are skipped)
+    #[account(
+        mut,
+        constraint = vault_reward_account_0.owner == vault_pda.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub vault_reward_account_0: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_reward_account_1.owner == vault_pda.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub vault_reward_account_1: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_reward_account_2.owner == vault_pda.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub vault_reward_account_2: Option<Account<'info, TokenAccount>>,
+
+    // User reward accounts (destinations)
+    #[account(
+        mut,
+        constraint = user_reward_account_0.owner == user.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub user_reward_account_0: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account_1.owner == user.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub user_reward_account_1: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account_2.owner == user.key() @ ClaimError::InvalidRewardAccount
+    )]
+    pub user_reward_account_2: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum ClaimError {
+    #[msg("Unauthorized - not position owner")]
+    Unauthorized,
+    #[msg("Reward account must be owned by the vault (source) or user (destination)")]
+    InvalidRewardAccount,
+    #[msg("Reward account mint does not match pool reward mint")]
+    RewardMintMismatch,
+    #[msg("User reward account missing for a non-zero claim")]
+    MissingRewardAccount,
+    #[msg("Claim exceeds the vault's reward balance")]
+    InsufficientRewardBalance,
+    #[msg("Reward slot has no encrypted total to debit")]
+    RewardNotTracked,
+    #[msg("Collected profits are pending encryption; run encrypt_collected_profits first")]
+    ProfitsPendingEncryption,
+    #[msg("No rewards to claim")]
+    NothingToClaim,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub reward_0: u64,
+    pub reward_1: u64,
+    pub reward_2: u64,
+    pub encrypted_reward_0: u128,
+    pub encrypted_reward_1: u128,
+    pub encrypted_reward_2: u128,
+    pub timestamp: i64,
+}
@@ -0,0 +1,100 @@
+//! Close Vault - Reclaims VaultPDA rent once the vault is empty
+//!
+//! This instruction:
+//! 1. Requires no open positions and no operation in progress
+//! 2. Closes every vault-owned token account passed in `remaining_accounts`
+//!    (each must already be empty - run `sweep_dust` first)
+//! 3. Closes the VaultPDA, returning its rent to the owner
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+
+use crate::state::VaultPDA;
+
+/// Close the owner's vault and its empty token accounts
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+    let vault = &ctx.accounts.vault_pda;
+    require!(vault.position_count == 0, CloseVaultError::VaultNotEmpty);
+    require!(!vault.locked, CloseVaultError::VaultLocked);
+    let token_accounts_closed = u8::try_from(ctx.remaining_accounts.len())
+        .map_err(|_| error!(CloseVaultError::TooManyTokenAccounts))?;
+
+    let owner_key = ctx.accounts.owner.key();
+    let vault_seeds = &[
+        b"vault".as_ref(),
+        owner_key.as_ref(),
+        &[vault.bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    // Close vault-owned token accounts so no tokens are stranded
+    for token_account_info in ctx.remaining_accounts.iter() {
+        let token_account = Account::<TokenAccount>::try_from(token_account_info)?;
+        require!(
+            token_account.owner == vault.key(),
+            CloseVaultError::InvalidVaultTokenAccount
+        );
+        require!(token_account.amount == 0, CloseVaultError::TokenAccountNotEmpty);
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: token_account_info.clone(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(VaultClosed {
+        owner: owner_key,
+        vault: vault.key(),
+        token_accounts_closed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Vault closed for owner: {}", owner_key);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == owner.key() @ CloseVaultError::Unauthorized
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum CloseVaultError {
+    #[msg("Unauthorized - not vault owner")]
+    Unauthorized,
+    #[msg("Vault still has open positions")]
+    VaultNotEmpty,
+    #[msg("Vault is locked - operation in progress")]
+    VaultLocked,
+    #[msg("Token account is not owned by the vault")]
+    InvalidVaultTokenAccount,
+    #[msg("Vault token account still holds tokens - sweep dust first")]
+    TokenAccountNotEmpty,
+    #[msg("Too many token accounts to close in one call")]
+    TooManyTokenAccounts,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub token_accounts_closed: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,261 @@
+//! Collect All Positions - Harvests fees for several positions in one call
+//!
+//! This instruction:
+//! 1. Walks `remaining_accounts` in groups of `POSITION_GROUP_LEN`, one per position
+//! 2. Checks the group's position is the tracked one, its LP NFT is in the
+//!    vault, and the fee accounts are the vault's ATAs
+//! 3. Collects token A/B fees and takes the performance fee (+ referrer
+//!    share) through the same `Collection` steps as `collect_all_profits`
+//! 4. Encrypts the net fees into each tracker's profit handles
+//!
+//! Group layout (in order): position_tracker, whirlpool, whirlpool_position,
+//! position_token_account, token_vault_a, token_vault_b, fee_account_a,
+//! fee_account_b, fee_recipient_account_a, fee_recipient_account_b,
+//! referrer_account_a, referrer_account_b. Unused fee/referrer destinations
+//! are passed as this program's ID, mirroring Anchor's optional accounts.
+//!
+//! Rewards are not harvested here; use `collect_all_profits` for those.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::{INCO_LIGHTNING_ID, WHIRLPOOL_PROGRAM_ID};
+use super::collect_profits::{Collection, ProfitCollected};
+use super::set_delegate::{DelegateActed, DelegatedAction};
+use super::whirlpool_cpi;
+
+/// Accounts per position in `remaining_accounts`
+pub const POSITION_GROUP_LEN: usize = 12;
+
+/// Upper bound on positions per call, to stay within the compute budget
+pub const MAX_POSITIONS_PER_CALL: usize = 4;
+
+/// Collect and encrypt fees for every position group passed
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CollectAllPositions<'info>>) -> Result<()> {
+    // Step 0: Check not paused + validate groups + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len().is_multiple_of(POSITION_GROUP_LEN),
+        BatchCollectError::InvalidAccountGroup
+    );
+    let position_count = remaining.len() / POSITION_GROUP_LEN;
+    require!(
+        position_count <= MAX_POSITIONS_PER_CALL,
+        BatchCollectError::TooManyPositions
+    );
+
+    let instruction = crate::instruction::CollectAllPositions::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        let owner = accounts.vault_pda.owner;
+        let vault_key = accounts.vault_pda.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        for group in remaining.chunks_exact(POSITION_GROUP_LEN) {
+            let mut tracker = Account::<PositionTracker>::try_from(&group[0])?;
+            let whirlpool = &group[1];
+
+            // Tracker must be this vault's canonical PDA and callable by the signer
+            let tracker_pda = Pubkey::create_program_address(
+                &[b"tracker", tracker.user.as_ref(), tracker.whirlpool.as_ref(), &[tracker.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(BatchCollectError::InvalidAccountGroup))?;
+            require!(
+                tracker_pda == tracker.key() && tracker.user == owner,
+                BatchCollectError::InvalidAccountGroup
+            );
+            require!(
+                tracker.is_owner_or_delegate(&accounts.authority.key()),
+                BatchCollectError::Unauthorized
+            );
+            require!(whirlpool.key() == tracker.whirlpool, BatchCollectError::WhirlpoolMismatch);
+            tracker.require_not_closed()?;
+            tracker.require_not_frozen_for(&accounts.authority.key())?;
+
+            if tracker.is_delegate_action(&accounts.authority.key()) {
+                emit!(DelegateActed {
+                    position_tracker: tracker.key(),
+                    owner: tracker.user,
+                    delegate: accounts.authority.key(),
+                    action: DelegatedAction::CollectProfits,
+                    timestamp: now,
+                });
+            }
+
+            // The tracked position, and its LP NFT held by the vault
+            require!(
+                whirlpool_cpi::read_position_mint(&group[2])? == tracker.lp_position_mint,
+                BatchCollectError::PositionMismatch
+            );
+            let position_token_account = Account::<TokenAccount>::try_from(&group[3])?;
+            require!(
+                position_token_account.owner == vault_key
+                    && position_token_account.mint == tracker.lp_position_mint
+                    && position_token_account.amount == 1,
+                BatchCollectError::PositionMismatch
+            );
+
+            // Fee accounts: the vault PDA's ATAs, as in `collect_all_profits`
+            let mut fee_account_a = Account::<TokenAccount>::try_from(&group[6])?;
+            let mut fee_account_b = Account::<TokenAccount>::try_from(&group[7])?;
+            for (fee_account, mint) in [
+                (&fee_account_a, tracker.token_mint_a),
+                (&fee_account_b, tracker.token_mint_b),
+            ] {
+                require!(
+                    fee_account.key() == get_associated_token_address(&vault_key, &mint)
+                        && fee_account.owner == vault_key
+                        && fee_account.mint == mint,
+                    BatchCollectError::InvalidFeeAccount
+                );
+            }
+
+            let fee_recipient_a = optional_token_account(&group[8])?;
+            let fee_recipient_b = optional_token_account(&group[9])?;
+            let referrer_a = optional_token_account(&group[10])?;
+            let referrer_b = optional_token_account(&group[11])?;
+            for (recipient, mint) in [
+                (&fee_recipient_a, tracker.token_mint_a),
+                (&fee_recipient_b, tracker.token_mint_b),
+            ] {
+                if let Some(recipient) = recipient {
+                    require!(
+                        recipient.owner == accounts.vault_config.fee_recipient && recipient.mint == mint,
+                        BatchCollectError::FeeAccountMismatch
+                    );
+                }
+            }
+            for (referrer, mint) in [
+                (&referrer_a, tracker.token_mint_a),
+                (&referrer_b, tracker.token_mint_b),
+            ] {
+                if let Some(referrer) = referrer {
+                    require!(
+                        Some(referrer.owner) == accounts.vault_pda.referrer && referrer.mint == mint,
+                        BatchCollectError::FeeAccountMismatch
+                    );
+                }
+            }
+
+            // ========== COLLECT FEES, TAKE THE PERFORMANCE FEE, ENCRYPT ==========
+            // Same steps as `collect_all_profits`, minus the reward slots
+            let mut collection = Collection {
+                payer: accounts.authority.to_account_info(),
+                vault_config: &accounts.vault_config,
+                vault_pda: &mut accounts.vault_pda,
+                position_tracker: &mut tracker,
+                whirlpool: whirlpool.clone(),
+                whirlpool_position: group[2].clone(),
+                position_token_account: group[3].clone(),
+                token_vault_a: group[4].clone(),
+                token_vault_b: group[5].clone(),
+                fee_account_a: &mut fee_account_a,
+                fee_account_b: &mut fee_account_b,
+                fee_recipient_account_a: fee_recipient_a.as_ref(),
+                fee_recipient_account_b: fee_recipient_b.as_ref(),
+                referrer_account_a: referrer_a.as_ref(),
+                referrer_account_b: referrer_b.as_ref(),
+                reward_accounts: [None, None, None],
+                reward_vaults: [None, None, None],
+                inco_config: accounts.inco_config.to_account_info(),
+                inco_lightning_program: accounts.inco_lightning_program.to_account_info(),
+                whirlpool_program: accounts.whirlpool_program.to_account_info(),
+                token_program: &accounts.token_program,
+            };
+            let fees = collection.collect_fees()?;
+            collection.track(&fees, [0; 3], true)?;
+
+            let below_threshold = tracker.has_pending_profits();
+            tracker.exit(&crate::ID)?;
+
+            emit!(ProfitCollected {
+                position: tracker.lp_position_mint,
+                fee_a: fees.fee_a,
+                fee_b: fees.fee_b,
+                reward_0: 0,
+                reward_1: 0,
+                reward_2: 0,
+                performance_fee_bps: fees.fee_bps,
+                protocol_fee_a: fees.protocol_fee_a,
+                protocol_fee_b: fees.protocol_fee_b,
+                referrer: accounts.vault_pda.referrer,
+                referrer_fee_a: fees.referrer_fee_a,
+                referrer_fee_b: fees.referrer_fee_b,
+                encrypted: true,
+                below_threshold,
+                encrypted_realized_profit_a: tracker.encrypted_realized_profit_a,
+                encrypted_realized_profit_b: tracker.encrypted_realized_profit_b,
+                encrypted_reward_0: tracker.encrypted_reward_0,
+                encrypted_reward_1: tracker.encrypted_reward_1,
+                encrypted_reward_2: tracker.encrypted_reward_2,
+                timestamp: tracker.last_update,
+            });
+        }
+
+        msg!("Collected fees for {} positions", position_count);
+        Ok(())
+    })
+}
+
+/// Deserialize a token account, treating this program's ID as "not provided"
+fn optional_token_account<'info>(
+    info: &'info AccountInfo<'info>,
+) -> Result<Option<Account<'info, TokenAccount>>> {
+    if info.key() == crate::ID {
+        return Ok(None);
+    }
+    Account::try_from(info).map(Some)
+}
+
+#[derive(Accounts)]
+pub struct CollectAllPositions<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault_pda.owner.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum BatchCollectError {
+    #[msg("Unauthorized - not position owner or delegate")]
+    Unauthorized,
+    #[msg("Remaining accounts do not form valid position groups")]
+    InvalidAccountGroup,
+    #[msg("Too many positions for a single call")]
+    TooManyPositions,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Fee account must be the vault's associated token account for the pool's mint")]
+    InvalidFeeAccount,
+    #[msg("Fee destination account owner or mint mismatch")]
+    FeeAccountMismatch,
+    #[msg("Position or its token account does not hold the tracked LP NFT in the vault")]
+    PositionMismatch,
+}
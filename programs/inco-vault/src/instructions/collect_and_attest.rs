@@ -0,0 +1,110 @@
+//! Collect And Attest - Collection bound to an attested plaintext in one tx
+//!
+//! This instruction:
+//! 1. Runs `collect_all_profits` with encryption on
+//! 2. Reads the realized-profit handles it just produced
+//! 3. Requires an Inco Ed25519 attestation of exactly those handles
+//!    elsewhere in the same transaction
+//! 4. Numbers the attestation with the tracker's `attest_nonce`
+//! 5. Emits the handles with their attested plaintexts and nonce
+//!
+//! The attestation may come before or after this instruction; the runtime
+//! checks every Ed25519 precompile signature before any program runs. A
+//! transaction whose attestation names any other handle fails, so the
+//! emitted record proves the collection and the reported value match.
+//!
+//! Only handles this collection replaced are attested. Inco never reissues
+//! a handle, so an attestation from an earlier transaction cannot cover
+//! them, and a replayed one fails instead of emitting a second record.
+
+use anchor_lang::prelude::*;
+
+// Glob import: the nested `CollectAllProfits` needs its generated client modules
+use super::collect_profits::{self, *};
+use crate::state::CovalidatorSet;
+use super::verify_decryption;
+
+/// Collect profits and require an attestation of the resulting handles
+pub fn handler(ctx: Context<CollectAndAttest>, reward_min: Option<[u64; 3]>) -> Result<()> {
+    let tracker = &ctx.accounts.collect.position_tracker;
+    let handles_before = [
+        tracker.encrypted_realized_profit_a,
+        tracker.encrypted_realized_profit_b,
+    ];
+
+    collect_profits::collect(
+        &mut ctx.accounts.collect,
+        crate::instruction::CollectAndAttest::DISCRIMINATOR,
+        true,
+        reward_min,
+    )?;
+
+    // Amounts below the collect threshold stay pending as dust; they are not
+    // part of the encrypted totals, so the attestation covers those totals
+    let tracker = &mut ctx.accounts.collect.position_tracker;
+
+    let handles: Vec<[u8; 16]> = [
+        tracker.encrypted_realized_profit_a,
+        tracker.encrypted_realized_profit_b,
+    ]
+    .into_iter()
+    .zip(handles_before)
+    .filter(|(handle, before)| *handle != 0 && handle != before)
+    .map(|(handle, _)| handle.to_le_bytes())
+    .collect();
+    require!(!handles.is_empty(), AttestError::NothingToAttest);
+
+    let plaintexts = verify_decryption::find_transaction_attestation(
+        &ctx.accounts.instructions,
+        CovalidatorSet::load(&ctx.accounts.covalidator_set)?.as_ref(),
+        &handles,
+        ctx.accounts.collect.vault_config.accepted_message_versions,
+    )?
+    .ok_or(AttestError::AttestationMismatch)?;
+    msg!("✓ Attestation covers the {} freshly collected handles", handles.len());
+    let nonce = tracker.record_attestation()?;
+
+    emit!(ProfitAttested {
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        nonce,
+        sequence: tracker.collect_count,
+        handles,
+        plaintexts,
+        timestamp: tracker.last_update,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CollectAndAttest<'info> {
+    pub collect: CollectAllProfits<'info>,
+
+    /// CHECK: Instructions sysvar for reading the Ed25519 attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum AttestError {
+    #[msg("No attestation in this transaction covers the collected handles")]
+    AttestationMismatch,
+    #[msg("Collection produced no new realized-profit handles to attest")]
+    NothingToAttest,
+}
+
+#[event]
+pub struct ProfitAttested {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub nonce: u64,
+    pub sequence: u32,
+    pub handles: Vec<[u8; 16]>,
+    pub plaintexts: Vec<[u8; 16]>,
+    pub timestamp: i64,
+}
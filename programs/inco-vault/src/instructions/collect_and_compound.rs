@@ -0,0 +1,583 @@
+//! Collect and Compound - Reinvests fees and rewards into the same position
+//!
+//! This instruction:
+//! 1. Collects token A and B fees and takes the performance fee
+//! 2. Collects every active reward routed to the vault (`Track` or `Compound`)
+//! 3. Swaps rewards into token A/B (one Whirlpool `swap` per `swaps` entry),
+//!    through the position's pool or the owner-set `reward_swap_pools`, and
+//!    never more than step 1 or 2 harvested into the swap's input account
+//! 4. Adds the proceeds back to the position via `increase_liquidity`
+//!
+//! Unlike `collect_all_profits`, compounded tokens stay in the position, so
+//! only what is left over (unswapped rewards, unused A/B) is staged as
+//! pending profit. A `liquidity_amount` below `VaultConfig::min_liquidity`
+//! is treated as dust: the add is skipped and everything is left pending.
+//!
+//! Rewards routed to the user or the treasury are left owed on the position
+//! for `collect_all_profits` to deliver. Leftover `Compound` rewards stay in
+//! their vault account and are not staged; a later compound only swaps what
+//! it harvests itself.
+//!
+//! The vault is locked once at entry and released once at exit; every CPI in
+//! between runs under that single lock. Shared steps must come from the
+//! `_locked` helpers (`collect_profits::collect_locked`,
+//! `withdraw_position::withdraw_locked`), never from handlers that lock again.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::WHIRLPOOL_PROGRAM_ID;
+use super::collect_profits::{emit_profit_realized, transfer_fee};
+use super::whirlpool_cpi::{self, SwapAccounts, SwapParams};
+use super::set_delegate::{DelegateActed, DelegatedAction};
+
+/// Collect fees and rewards and compound them into the position
+///
+/// `swaps` are executed in order; each consumes `SwapAccounts::LEN` accounts
+/// from `remaining_accounts`, and both of its owner accounts must be one of
+/// this instruction's vault-owned token accounts. Each swap is exact-input,
+/// runs through the position's whirlpool or the pool the owner set for the
+/// reward it spends, and its amount comes out of what this call harvested
+/// into the input account. Slippage is bounded by each
+/// swap's `other_amount_threshold`/`sqrt_price_limit`, and the liquidity add
+/// can never spend more than was harvested.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CollectAndCompound<'info>>,
+    liquidity_amount: u128,
+    swaps: Vec<SwapParams>,
+) -> Result<()> {
+    // Step 0: Check not paused + position still open + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+    let instruction = crate::instruction::CollectAndCompound::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+        if accounts.position_tracker.is_delegate_action(&accounts.authority.key()) {
+            emit!(DelegateActed {
+                position_tracker: accounts.position_tracker.key(),
+                owner: accounts.position_tracker.user,
+                delegate: accounts.authority.key(),
+                action: DelegatedAction::Compound,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        whirlpool_cpi::validate_tick_arrays(
+            &accounts.whirlpool.to_account_info(),
+            accounts.tick_array_lower.key,
+            accounts.tick_array_upper.key,
+            accounts.position_tracker.tick_lower,
+            accounts.position_tracker.tick_upper,
+        )?;
+
+        // Each active vault-held reward slot needs its account + vault, with the pool's mint
+        let reward_count = accounts.position_tracker.reward_count as usize;
+        let reward_routes = accounts.position_tracker.reward_routes;
+        let reward_inputs = [
+            (&accounts.reward_account_0, accounts.reward_vault_0.is_some()),
+            (&accounts.reward_account_1, accounts.reward_vault_1.is_some()),
+            (&accounts.reward_account_2, accounts.reward_vault_2.is_some()),
+        ];
+        for (i, (reward_account, has_vault)) in reward_inputs.iter().enumerate().take(reward_count) {
+            if !reward_routes[i].is_vault_held() {
+                continue;
+            }
+            let reward_account = reward_account
+                .as_ref()
+                .ok_or(CompoundError::MissingRewardAccount)?;
+            require!(*has_vault, CompoundError::MissingRewardAccount);
+            require!(
+                reward_account.mint == accounts.position_tracker.reward_mints[i],
+                CompoundError::RewardMintMismatch
+            );
+        }
+
+        // Swaps may only move tokens between this vault's own accounts
+        require!(
+            ctx.remaining_accounts.len() == swaps.len() * SwapAccounts::LEN,
+            CompoundError::SwapAccountsMismatch
+        );
+        let vault_accounts: Vec<Pubkey> = [
+            Some(accounts.token_account_a.key()),
+            Some(accounts.token_account_b.key()),
+            accounts.reward_account_0.as_ref().map(|a| a.key()),
+            accounts.reward_account_1.as_ref().map(|a| a.key()),
+            accounts.reward_account_2.as_ref().map(|a| a.key()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.position_tracker.user.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        // ========== STEP 1: COLLECT TOKEN A + B FEES ==========
+        let pre_balance_a = accounts.token_account_a.amount;
+        let pre_balance_b = accounts.token_account_b.amount;
+
+        whirlpool_cpi::cpi_collect_fees(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_account_a.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.token_account_b.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        accounts.token_account_a.reload()?;
+        accounts.token_account_b.reload()?;
+        let fee_a = accounts.token_account_a.amount.saturating_sub(pre_balance_a);
+        let fee_b = accounts.token_account_b.amount.saturating_sub(pre_balance_b);
+        msg!("Fees collected: {} token_a, {} token_b", fee_a, fee_b);
+
+        accounts.vault_pda.record_fees(fee_a, fee_b)?;
+
+        // Performance fee applies to compounded fees as well
+        let has_referrer = accounts.vault_pda.referrer.is_some();
+        let fee_bps = accounts.vault_config.performance_fee_bps_for(accounts.position_tracker.fee_bps_override);
+        let (protocol_fee_a, referrer_fee_a) =
+            accounts.vault_config.performance_fee_split(fee_a, fee_bps, has_referrer);
+        let (protocol_fee_b, referrer_fee_b) =
+            accounts.vault_config.performance_fee_split(fee_b, fee_bps, has_referrer);
+
+        let vault_info = accounts.vault_pda.to_account_info();
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.token_account_a,
+            accounts.fee_recipient_account_a.as_deref(),
+            vault_info.clone(),
+            signer_seeds,
+            protocol_fee_a,
+        )?;
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.token_account_b,
+            accounts.fee_recipient_account_b.as_deref(),
+            vault_info.clone(),
+            signer_seeds,
+            protocol_fee_b,
+        )?;
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.token_account_a,
+            accounts.referrer_account_a.as_deref(),
+            vault_info.clone(),
+            signer_seeds,
+            referrer_fee_a,
+        )?;
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.token_account_b,
+            accounts.referrer_account_b.as_deref(),
+            vault_info,
+            signer_seeds,
+            referrer_fee_b,
+        )?;
+
+        // ========== STEP 2: COLLECT REWARDS ==========
+        let mut rewards = [0u64; 3];
+        let mut pre_rewards = [0u64; 3];
+
+        let whirlpool_program = accounts.whirlpool_program.to_account_info();
+        let whirlpool = accounts.whirlpool.to_account_info();
+        let vault_info = accounts.vault_pda.to_account_info();
+        let position = accounts.whirlpool_position.to_account_info();
+        let position_token_account = accounts.position_token_account.to_account_info();
+        let token_program = accounts.token_program.to_account_info();
+
+        let reward_slots = [
+            (&mut accounts.reward_account_0, &accounts.reward_vault_0),
+            (&mut accounts.reward_account_1, &accounts.reward_vault_1),
+            (&mut accounts.reward_account_2, &accounts.reward_vault_2),
+        ];
+        debug_assert!(reward_count <= whirlpool_cpi::NUM_REWARDS);
+        let mut collected = [false; whirlpool_cpi::NUM_REWARDS];
+        for (i, (reward_account, reward_vault)) in reward_slots.into_iter().enumerate().take(reward_count) {
+            // Presence checked in step 0; other routes stay owed for collect_all_profits
+            if !reward_routes[i].is_vault_held() {
+                continue;
+            }
+            let (Some(reward_account), Some(reward_vault)) = (reward_account.as_mut(), reward_vault) else {
+                continue;
+            };
+            whirlpool_cpi::mark_reward_index(&mut collected, i as u8)?;
+
+            pre_rewards[i] = reward_account.amount;
+            whirlpool_cpi::cpi_collect_reward(
+                whirlpool_program.clone(),
+                whirlpool.clone(),
+                vault_info.clone(),
+                position.clone(),
+                position_token_account.clone(),
+                reward_account.to_account_info(),
+                reward_vault.to_account_info(),
+                token_program.clone(),
+                signer_seeds,
+                i as u8,
+            )?;
+            reward_account.reload()?;
+
+            rewards[i] = reward_account.amount.saturating_sub(pre_rewards[i]);
+            msg!("Reward {} collected: {}", i, rewards[i]);
+        }
+
+        // ========== STEP 3: SWAP REWARDS INTO TOKEN A/B ==========
+        // Per input account: what this call harvested into it, and the extra
+        // pool the owner allowed for it
+        let reward_swap_pools = accounts.position_tracker.reward_swap_pools;
+        let mut budgets = vec![
+            (
+                accounts.token_account_a.key(),
+                fee_a.saturating_sub(protocol_fee_a).saturating_sub(referrer_fee_a),
+                Pubkey::default(),
+            ),
+            (
+                accounts.token_account_b.key(),
+                fee_b.saturating_sub(protocol_fee_b).saturating_sub(referrer_fee_b),
+                Pubkey::default(),
+            ),
+        ];
+        let reward_keys = [
+            accounts.reward_account_0.as_ref().map(|a| a.key()),
+            accounts.reward_account_1.as_ref().map(|a| a.key()),
+            accounts.reward_account_2.as_ref().map(|a| a.key()),
+        ];
+        for (i, key) in reward_keys.into_iter().enumerate() {
+            if let Some(key) = key {
+                budgets.push((key, rewards[i], reward_swap_pools[i]));
+            }
+        }
+
+        for (params, chunk) in swaps.iter().zip(ctx.remaining_accounts.chunks(SwapAccounts::LEN)) {
+            let swap_accounts = SwapAccounts::from_accounts(chunk)?;
+            require!(
+                vault_accounts.contains(swap_accounts.token_owner_account_a.key)
+                    && vault_accounts.contains(swap_accounts.token_owner_account_b.key),
+                CompoundError::InvalidSwapAccount
+            );
+            require!(params.amount_specified_is_input, CompoundError::SwapExceedsHarvest);
+            let input = if params.a_to_b {
+                swap_accounts.token_owner_account_a.key
+            } else {
+                swap_accounts.token_owner_account_b.key
+            };
+            let (_, budget, allowed_pool) = budgets
+                .iter_mut()
+                .find(|(key, _, _)| key == input)
+                .ok_or(CompoundError::InvalidSwapAccount)?;
+            let pool = swap_accounts.whirlpool.key;
+            require!(
+                *pool == whirlpool.key()
+                    || (*allowed_pool != Pubkey::default() && pool == allowed_pool),
+                CompoundError::SwapPoolNotAllowed
+            );
+            *budget = budget
+                .checked_sub(params.amount)
+                .ok_or(CompoundError::SwapExceedsHarvest)?;
+
+            whirlpool_cpi::cpi_swap(
+                whirlpool_program.clone(),
+                token_program.clone(),
+                vault_info.clone(),
+                swap_accounts,
+                signer_seeds,
+                params,
+            )?;
+            msg!("Swapped {} (a_to_b: {})", params.amount, params.a_to_b);
+        }
+
+        // What is left of each reward after swapping stays pending
+        let mut remaining_rewards = [0u64; 3];
+        let reward_accounts = [
+            &mut accounts.reward_account_0,
+            &mut accounts.reward_account_1,
+            &mut accounts.reward_account_2,
+        ];
+        for (i, reward_account) in reward_accounts.into_iter().enumerate().take(reward_count) {
+            if let Some(reward_account) = reward_account.as_mut() {
+                reward_account.reload()?;
+                remaining_rewards[i] = reward_account.amount.saturating_sub(pre_rewards[i]);
+            }
+        }
+
+        // Net fees plus swap proceeds, after the performance fee
+        accounts.token_account_a.reload()?;
+        accounts.token_account_b.reload()?;
+        let available_a = accounts.token_account_a.amount.saturating_sub(pre_balance_a);
+        let available_b = accounts.token_account_b.amount.saturating_sub(pre_balance_b);
+
+        // ========== STEP 4: ADD PROCEEDS BACK AS LIQUIDITY ==========
+        let skipped = liquidity_amount < accounts.vault_config.min_liquidity;
+        let (used_a, used_b) = if skipped {
+            msg!("Compound skipped: liquidity {} below dust threshold", liquidity_amount);
+            (0, 0)
+        } else {
+            let vault_cap = accounts.vault_config.max_total_liquidity_per_vault;
+            accounts.vault_pda.add_liquidity(liquidity_amount, vault_cap)?;
+
+            let before_a = accounts.token_account_a.amount;
+            let before_b = accounts.token_account_b.amount;
+
+            // Token maxima are the harvested amounts, so the add can't dip
+            // into principal however far the price has moved
+            whirlpool_cpi::cpi_increase_liquidity(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.whirlpool.to_account_info(),
+                accounts.token_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                accounts.whirlpool_position.to_account_info(),
+                accounts.position_token_account.to_account_info(),
+                accounts.token_account_a.to_account_info(),
+                accounts.token_account_b.to_account_info(),
+                accounts.token_vault_a.to_account_info(),
+                accounts.token_vault_b.to_account_info(),
+                accounts.tick_array_lower.to_account_info(),
+                accounts.tick_array_upper.to_account_info(),
+                signer_seeds,
+                liquidity_amount,
+                available_a,
+                available_b,
+            )?;
+
+            accounts.token_account_a.reload()?;
+            accounts.token_account_b.reload()?;
+            let used_a = before_a.saturating_sub(accounts.token_account_a.amount);
+            let used_b = before_b.saturating_sub(accounts.token_account_b.amount);
+            msg!("Compounded liquidity {} (A: {}, B: {})", liquidity_amount, used_a, used_b);
+            (used_a, used_b)
+        };
+        let liquidity_added = if skipped { 0 } else { liquidity_amount };
+
+        // ========== STEP 5: TRACK COMPOUNDED + LEFTOVER PROFIT ==========
+        let tracker = &mut accounts.position_tracker;
+        tracker.record_compounded(liquidity_added)?;
+        let tracked_rewards = tracker.tracked_rewards(remaining_rewards);
+        tracker.add_pending_profits(
+            available_a.saturating_sub(used_a),
+            available_b.saturating_sub(used_b),
+            tracked_rewards,
+        )?;
+        let now = Clock::get()?.unix_timestamp;
+        tracker.last_update = now;
+        emit_profit_realized(
+            tracker,
+            (fee_a - protocol_fee_a - referrer_fee_a, fee_b - protocol_fee_b - referrer_fee_b),
+            rewards,
+            now,
+        )?;
+
+        emit!(ProfitCompounded {
+            position: tracker.lp_position_mint,
+            fee_a,
+            fee_b,
+            reward_0: rewards[0],
+            reward_1: rewards[1],
+            reward_2: rewards[2],
+            protocol_fee_a,
+            protocol_fee_b,
+            referrer_fee_a,
+            referrer_fee_b,
+            liquidity_added,
+            token_a_used: used_a,
+            token_b_used: used_b,
+            lifetime_compounded: tracker.lifetime_compounded,
+            timestamp: tracker.last_update,
+        });
+
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct CollectAndCompound<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Box<Account<'info, VaultConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Box<Account<'info, VaultPDA>>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.is_owner_or_delegate(&authority.key()) @ CompoundError::Unauthorized
+    )]
+    pub position_tracker: Box<Account<'info, PositionTracker>>,
+
+    // Whirlpool accounts
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        mut,
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ CompoundError::WhirlpoolMismatch
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Position (validated by CPI)
+    #[account(mut)]
+    pub whirlpool_position: UncheckedAccount<'info>,
+
+    /// CHECK: Position token account
+    pub position_token_account: UncheckedAccount<'info>,
+
+    // Token vaults
+    /// CHECK: Token vault A
+    #[account(mut)]
+    pub token_vault_a: UncheckedAccount<'info>,
+
+    /// CHECK: Token vault B
+    #[account(mut)]
+    pub token_vault_b: UncheckedAccount<'info>,
+
+    // Vault-owned token accounts that receive fees and fund the liquidity add
+    #[account(
+        mut,
+        constraint = token_account_a.owner == vault_pda.key() @ CompoundError::InvalidTokenAccount,
+        constraint = token_account_a.mint == position_tracker.token_mint_a @ CompoundError::InvalidTokenAccount
+    )]
+    pub token_account_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_account_b.owner == vault_pda.key() @ CompoundError::InvalidTokenAccount,
+        constraint = token_account_b.mint == position_tracker.token_mint_b @ CompoundError::InvalidTokenAccount
+    )]
+    pub token_account_b: Box<Account<'info, TokenAccount>>,
+
+    // Performance fee destinations (required when the fee is non-zero)
+    #[account(
+        mut,
+        constraint = fee_recipient_account_a.owner == vault_config.fee_recipient @ CompoundError::FeeAccountMismatch,
+        constraint = fee_recipient_account_a.mint == position_tracker.token_mint_a @ CompoundError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_a: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_account_b.owner == vault_config.fee_recipient @ CompoundError::FeeAccountMismatch,
+        constraint = fee_recipient_account_b.mint == position_tracker.token_mint_b @ CompoundError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_b: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Referrer fee destinations (required when the vault has a referrer)
+    #[account(
+        mut,
+        constraint = Some(referrer_account_a.owner) == vault_pda.referrer @ CompoundError::FeeAccountMismatch,
+        constraint = referrer_account_a.mint == position_tracker.token_mint_a @ CompoundError::FeeAccountMismatch
+    )]
+    pub referrer_account_a: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = Some(referrer_account_b.owner) == vault_pda.referrer @ CompoundError::FeeAccountMismatch,
+        constraint = referrer_account_b.mint == position_tracker.token_mint_b @ CompoundError::FeeAccountMismatch
+    )]
+    pub referrer_account_b: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Reward accounts (vault-owned so they can be swapped)
+    #[account(
+        mut,
+        constraint = reward_account_0.owner == vault_pda.key() @ CompoundError::InvalidTokenAccount
+    )]
+    pub reward_account_0: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = reward_account_1.owner == vault_pda.key() @ CompoundError::InvalidTokenAccount
+    )]
+    pub reward_account_1: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = reward_account_2.owner == vault_pda.key() @ CompoundError::InvalidTokenAccount
+    )]
+    pub reward_account_2: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Pool reward vaults (paired with the reward accounts above)
+    /// CHECK: Reward vault 0 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_0: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Reward vault 1 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_1: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Reward vault 2 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_2: Option<UncheckedAccount<'info>>,
+
+    // Tick arrays
+    /// CHECK: Tick array lower (validated against the tracker's range)
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array upper (validated against the tracker's range)
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum CompoundError {
+    #[msg("Unauthorized - not position owner or delegate")]
+    Unauthorized,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Token account must be owned by the vault and hold the expected mint")]
+    InvalidTokenAccount,
+    #[msg("Reward account mint does not match pool reward mint")]
+    RewardMintMismatch,
+    #[msg("Reward account or vault missing for an active reward slot")]
+    MissingRewardAccount,
+    #[msg("Fee destination account owner or mint mismatch")]
+    FeeAccountMismatch,
+    #[msg("Remaining accounts do not match the number of swaps")]
+    SwapAccountsMismatch,
+    #[msg("Swap token account is not one of the vault's accounts")]
+    InvalidSwapAccount,
+    #[msg("Swap pool is neither the position's pool nor the owner's pool for that reward")]
+    SwapPoolNotAllowed,
+    #[msg("Swap must be exact-input and spend no more than this call harvested")]
+    SwapExceedsHarvest,
+}
+
+#[event]
+pub struct ProfitCompounded {
+    pub position: Pubkey,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub reward_0: u64,
+    pub reward_1: u64,
+    pub reward_2: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub referrer_fee_a: u64,
+    pub referrer_fee_b: u64,
+    pub liquidity_added: u128,
+    pub token_a_used: u64,
+    pub token_b_used: u64,
+    pub lifetime_compounded: u128,
+    pub timestamp: i64,
+}
@@ -2,176 +2,547 @@
 //!
 //! This instruction:
 //! 1. Collects token A and B fees via Whirlpool CPI
-//! 2. Collects up to 3 reward tokens
-//! 3. Encrypts and tracks all profits via Inco
+//! 2. Takes the protocol performance fee (shared with the vault's referrer)
+//! 3. Collects up to 3 reward tokens, each to the destination its
+//!    `RewardRoute` names
+//! 4. Encrypts and tracks all profits via Inco (or defers this to
+//!    `encrypt_collected_profits` when `encrypt` is false)
+//!
+//! `collect_fees_only` runs steps 1, 2 and 4 for token A/B fees alone, with
+//! no reward CPIs; reward accounts may be omitted.
+//!
+//! Every collection path also emits a numbered `ProfitRealized` entry with
+//! the cleartext amounts and the position's age, as a realization ledger.
+//!
+//! Fees land in the vault PDA's token A/B ATAs, which are created on the
+//! first collect (paid by the caller) so no separate setup step is needed.
+//!
+//! Only rewards routed to `Track` are encrypted. `Track` and `Compound`
+//! rewards must be collected into a vault-owned account, `ClaimToUser` into
+//! the owner's and `ToTreasury` into the fee recipient's.
+//!
+//! Amounts below `VaultConfig::min_collect_threshold` are left pending (the
+//! tokens stay collected) and get encrypted once they accumulate past it.
+//!
+//! Crash recovery: collected amounts are staged in the tracker's
+//! `pending_fee_*`/`pending_rewards` before any Inco CPI runs. A failing Inco
+//! call reverts the whole transaction (tokens included), so nothing drifts;
+//! callers that want to isolate Inco failures collect with `encrypt = false`
+//! and retry `encrypt_collected_profits` until it succeeds.
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
-use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use crate::state::{PositionTracker, RewardRoute, VaultPDA, VaultConfig};
 use super::create_position::{INCO_LIGHTNING_ID, WHIRLPOOL_PROGRAM_ID};
 use super::whirlpool_cpi;
+use super::inco_lightning_cpi::IncoDiscriminators;
+use super::encrypt_collected_profits::encrypt_pending_profits;
+use super::set_delegate::{DelegateActed, DelegatedAction};
+use super::compute_hint::{self, ComputeMeter};
 
 /// Collect all fees and rewards, update encrypted profit tracking
-pub fn handler(ctx: Context<CollectAllProfits>) -> Result<()> {
-    // Step 0: Check not paused + lock vault
-    ctx.accounts.vault_config.require_not_paused()?;
-    ctx.accounts.vault_pda.lock()?;
-
-    let vault_seeds = &[
-        b"vault".as_ref(),
-        ctx.accounts.position_tracker.user.as_ref(),
-        &[ctx.accounts.vault_pda.bump],
-    ];
-    let signer_seeds = &[&vault_seeds[..]];
-
-    // ========== STEP 1: COLLECT TOKEN A + B FEES ==========
-    let pre_balance_a = ctx.accounts.fee_account_a.amount;
-    let pre_balance_b = ctx.accounts.fee_account_b.amount;
-
-    // CPI to collect_fees
-    whirlpool_cpi::cpi_collect_fees(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        ctx.accounts.whirlpool.to_account_info(),
-        ctx.accounts.vault_pda.to_account_info(),
-        ctx.accounts.whirlpool_position.to_account_info(),
-        ctx.accounts.position_token_account.to_account_info(),
-        ctx.accounts.fee_account_a.to_account_info(),
-        ctx.accounts.token_vault_a.to_account_info(),
-        ctx.accounts.fee_account_b.to_account_info(),
-        ctx.accounts.token_vault_b.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        signer_seeds,
-    )?;
-
-    // Reload to get post-collection balances
-    ctx.accounts.fee_account_a.reload()?;
-    ctx.accounts.fee_account_b.reload()?;
-    
-    let fee_a = ctx.accounts.fee_account_a.amount.saturating_sub(pre_balance_a);
-    let fee_b = ctx.accounts.fee_account_b.amount.saturating_sub(pre_balance_b);
-    
-    msg!("Fees collected: {} token_a, {} token_b", fee_a, fee_b);
+///
+/// With `encrypt = false` only the token collection runs; the amounts stay
+/// pending on the tracker until `encrypt_collected_profits` is called.
+///
+/// `reward_min` sets a per-index floor on each measured reward amount.
+pub fn handler(
+    ctx: Context<CollectAllProfits>,
+    encrypt: bool,
+    reward_min: Option<[u64; 3]>,
+) -> Result<()> {
+    let meter = ComputeMeter::start();
+    collect(ctx.accounts, crate::instruction::CollectAllProfits::DISCRIMINATOR, encrypt, reward_min)?;
+    meter.finish("collect_all_profits");
+    Ok(())
+}
+
+/// Body of `collect_all_profits`, shared with `collect_and_attest`
+///
+/// `instruction` is the caller's discriminator, for lock traces.
+pub(crate) fn collect(
+    accounts: &mut CollectAllProfits,
+    instruction: &'static [u8],
+    encrypt: bool,
+    reward_min: Option<[u64; 3]>,
+) -> Result<()> {
+    // Step 0: Check not paused + position still open + lock vault
+    accounts.vault_config.require_not_paused()?;
+    accounts.position_tracker.require_not_closed()?;
+    VaultPDA::with_lock(accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        collect_locked(accounts, encrypt, reward_min)
+    })
+}
+
+/// Body of `collect` for callers that already hold the vault lock
+///
+/// Composite instructions that lock once at entry call this directly rather
+/// than `collect`, which would fail with `VaultLocked` on the second lock.
+pub(crate) fn collect_locked(
+    accounts: &mut CollectAllProfits,
+    encrypt: bool,
+    reward_min: Option<[u64; 3]>,
+) -> Result<()> {
+    accounts.vault_pda.require_locked()?;
+
+    accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+    emit_delegate_acted(accounts)?;
+
+    let mut collection = accounts.collection();
+    collection.validate_rewards()?;
+
+    compute_hint::checkpoint("collect: validated");
+
+    // ========== STEP 1: COLLECT TOKEN A + B FEES (+ PERFORMANCE FEE) ==========
+    let fees = collection.collect_fees()?;
+
+    compute_hint::checkpoint("collect: fees collected");
 
     // ========== STEP 2: COLLECT ALL 3 REWARDS ==========
-    let mut rewards = [0u64; 3];
-    
-    // Reward 0
-    // Reward 0 - Skip reload due to borrow constraints, reward amount is from CPI
-    if let Some(_reward_account) = &ctx.accounts.reward_account_0 {
-        // Reward collection will be handled by CPI
-        rewards[0] = 0;
-        msg!("Reward 0 placeholder");
-    }
-    
-    // Reward 1
-    if let Some(reward_account) = &ctx.accounts.reward_account_1 {
-        let _pre_reward = reward_account.amount;
-        // CPI similar to above...
-        rewards[1] = 0; // Would be from CPI
-        msg!("Reward 1 collected: {}", rewards[1]);
+    let rewards = collection.collect_rewards()?;
+
+    // Enforce caller floors (all zeros = no floor)
+    let reward_min = reward_min.unwrap_or_default();
+    for (collected, min) in rewards.iter().zip(reward_min) {
+        require!(*collected >= min, CollectError::RewardBelowMinimum);
     }
-    
-    // Reward 2
-    if let Some(reward_account) = &ctx.accounts.reward_account_2 {
-        let _pre_reward = reward_account.amount;
-        // CPI similar to above...
-        rewards[2] = 0; // Would be from CPI
-        msg!("Reward 2 collected: {}", rewards[2]);
+
+    compute_hint::checkpoint("collect: rewards collected");
+
+    track_profits(accounts, &fees, rewards, encrypt)
+}
+
+/// Collect token A/B fees only and fold them into the encrypted profit
+///
+/// Skips every reward CPI and needs no reward accounts, so it stays cheap on
+/// pools without rewards. Rewards keep accruing on the position for the
+/// next `collect_all_profits`.
+pub fn handler_fees_only(ctx: Context<CollectAllProfits>) -> Result<()> {
+    let meter = ComputeMeter::start();
+    // Step 0: Check not paused + position still open + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+    let instruction = crate::instruction::CollectFeesOnly::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, collect_fees_only_locked)?;
+    meter.finish("collect_fees_only");
+    Ok(())
+}
+
+/// Body of `collect_fees_only` for callers that already hold the vault lock
+pub(crate) fn collect_fees_only_locked(accounts: &mut CollectAllProfits) -> Result<()> {
+    accounts.vault_pda.require_locked()?;
+
+    accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+    emit_delegate_acted(accounts)?;
+
+    let fees = accounts.collection().collect_fees()?;
+    track_profits(accounts, &fees, [0; 3], true)
+}
+
+/// Log a `DelegateActed` event when the caller is the position's delegate
+fn emit_delegate_acted(accounts: &CollectAllProfits) -> Result<()> {
+    if accounts.position_tracker.is_delegate_action(&accounts.authority.key()) {
+        emit!(DelegateActed {
+            position_tracker: accounts.position_tracker.key(),
+            owner: accounts.position_tracker.user,
+            delegate: accounts.authority.key(),
+            action: DelegatedAction::CollectProfits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
     }
+    Ok(())
+}
 
+/// Step 3 of collection: stage, optionally encrypt, and emit `ProfitCollected`
+fn track_profits(
+    accounts: &mut CollectAllProfits,
+    fees: &CollectedFees,
+    rewards: [u64; 3],
+    encrypt: bool,
+) -> Result<()> {
     // ========== STEP 3: ENCRYPT AND TRACK PROFITS VIA INCO ==========
-    let tracker = &mut ctx.accounts.position_tracker;
-    
-    // Token A profit
-    if fee_a > 0 {
-        // 1. Create encrypted handle from cleartext fee
-        let fee_handle = super::inco_lightning_cpi::cpi_new_euint128(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            fee_a.to_le_bytes().to_vec(),
-            0, // amount_type (public/cleartext)
-        )?;
-        
-        // 2. Add to accumulated profit
-        let new_total = super::inco_lightning_cpi::cpi_e_add(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            tracker.encrypted_realized_profit_a,
-            fee_handle,
-        )?;
-        
-        tracker.encrypted_realized_profit_a = new_total;
-        msg!("Encrypted profit A updated. New handle: {}", new_total);
+    // Profits are staged on the tracker first; with `encrypt = false` the
+    // Inco step is deferred to `encrypt_collected_profits`.
+    accounts.collection().track(fees, rewards, encrypt)?;
+    compute_hint::checkpoint("collect: profits encrypted");
+
+    // Anything still pending after encryption was dust under the threshold
+    let tracker = &accounts.position_tracker;
+    let below_threshold = encrypt && tracker.has_pending_profits();
+
+    emit!(ProfitCollected {
+        position: tracker.lp_position_mint,
+        fee_a: fees.fee_a,
+        fee_b: fees.fee_b,
+        reward_0: rewards[0],
+        reward_1: rewards[1],
+        reward_2: rewards[2],
+        performance_fee_bps: fees.fee_bps,
+        protocol_fee_a: fees.protocol_fee_a,
+        protocol_fee_b: fees.protocol_fee_b,
+        referrer: accounts.vault_pda.referrer,
+        referrer_fee_a: fees.referrer_fee_a,
+        referrer_fee_b: fees.referrer_fee_b,
+        encrypted: encrypt,
+        below_threshold,
+        encrypted_realized_profit_a: tracker.encrypted_realized_profit_a,
+        encrypted_realized_profit_b: tracker.encrypted_realized_profit_b,
+        encrypted_reward_0: tracker.encrypted_reward_0,
+        encrypted_reward_1: tracker.encrypted_reward_1,
+        encrypted_reward_2: tracker.encrypted_reward_2,
+        timestamp: tracker.last_update,
+    });
+
+    if encrypt {
+        msg!("All profits collected and encrypted!");
+    } else {
+        msg!("All profits collected; encryption pending");
     }
+    Ok(())
+}
 
-    // Token B profit
-    if fee_b > 0 {
-        let fee_handle = super::inco_lightning_cpi::cpi_new_euint128(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            fee_b.to_le_bytes().to_vec(),
-            0,
-        )?;
-        
-        let new_total = super::inco_lightning_cpi::cpi_e_add(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            tracker.encrypted_realized_profit_b,
-            fee_handle,
-        )?;
-        
-        tracker.encrypted_realized_profit_b = new_total;
-        msg!("Encrypted profit B updated. New handle: {}", new_total);
+/// The accounts one collection touches, borrowed from the instruction running it
+///
+/// `collect_all_profits` and `crank_collect` validate their accounts
+/// differently but run the same fee, reward-route and tracking steps through
+/// this view. The caller holds the vault lock.
+pub(crate) struct Collection<'a, 'info> {
+    /// Signs and pays for the Inco handle CPIs
+    pub payer: AccountInfo<'info>,
+    pub vault_config: &'a VaultConfig,
+    pub vault_pda: &'a mut Account<'info, VaultPDA>,
+    pub position_tracker: &'a mut Account<'info, PositionTracker>,
+    pub whirlpool: AccountInfo<'info>,
+    pub whirlpool_position: AccountInfo<'info>,
+    pub position_token_account: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub fee_account_a: &'a mut Account<'info, TokenAccount>,
+    pub fee_account_b: &'a mut Account<'info, TokenAccount>,
+    pub fee_recipient_account_a: Option<&'a Account<'info, TokenAccount>>,
+    pub fee_recipient_account_b: Option<&'a Account<'info, TokenAccount>>,
+    pub referrer_account_a: Option<&'a Account<'info, TokenAccount>>,
+    pub referrer_account_b: Option<&'a Account<'info, TokenAccount>>,
+    pub reward_accounts: [Option<&'a mut Account<'info, TokenAccount>>; 3],
+    pub reward_vaults: [Option<AccountInfo<'info>>; 3],
+    pub inco_config: AccountInfo<'info>,
+    pub inco_lightning_program: AccountInfo<'info>,
+    pub whirlpool_program: AccountInfo<'info>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl Collection<'_, '_> {
+    /// Check each active reward slot against the pool's mint and its route
+    ///
+    /// Every slot below `reward_count` needs its account and pool vault, and
+    /// the account must belong to whoever the slot's `RewardRoute` names.
+    pub fn validate_rewards(&self) -> Result<()> {
+        let tracker = &self.position_tracker;
+        let reward_count = tracker.reward_count as usize;
+        for i in 0..reward_count.min(whirlpool_cpi::NUM_REWARDS) {
+            let reward_account = self.reward_accounts[i]
+                .as_ref()
+                .ok_or(CollectError::MissingRewardAccount)?;
+            require!(self.reward_vaults[i].is_some(), CollectError::MissingRewardAccount);
+            require!(
+                reward_account.mint == tracker.reward_mints[i],
+                CollectError::RewardMintMismatch
+            );
+
+            // Tracked rewards stay in the vault until withdrawn, like fees
+            let required_owner = match tracker.reward_routes[i] {
+                RewardRoute::Track | RewardRoute::Compound => self.vault_pda.key(),
+                RewardRoute::ClaimToUser => tracker.user,
+                RewardRoute::ToTreasury => self.vault_config.fee_recipient,
+            };
+            require!(reward_account.owner == required_owner, CollectError::RewardRouteMismatch);
+        }
+        Ok(())
     }
 
-    // Rewards
-    if rewards[0] > 0 {
-        let reward_handle = super::inco_lightning_cpi::cpi_new_euint128(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            rewards[0].to_le_bytes().to_vec(),
-            0,
+    /// Steps 1-1.5 of collection: fees into the vault, then the performance fee
+    pub fn collect_fees(&mut self) -> Result<CollectedFees> {
+        let user = self.position_tracker.user;
+        let vault_seeds = &[b"vault".as_ref(), user.as_ref(), &[self.vault_pda.bump]];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        // ========== STEP 1: COLLECT TOKEN A + B FEES ==========
+        let pre_balance_a = self.fee_account_a.amount;
+        let pre_balance_b = self.fee_account_b.amount;
+
+        // CPI to collect_fees
+        whirlpool_cpi::cpi_collect_fees(
+            self.whirlpool_program.clone(),
+            self.whirlpool.clone(),
+            self.vault_pda.to_account_info(),
+            self.whirlpool_position.clone(),
+            self.position_token_account.clone(),
+            self.fee_account_a.to_account_info(),
+            self.token_vault_a.clone(),
+            self.fee_account_b.to_account_info(),
+            self.token_vault_b.clone(),
+            self.token_program.to_account_info(),
+            signer_seeds,
         )?;
-        
-        let new_total = super::inco_lightning_cpi::cpi_e_add(
-            ctx.accounts.inco_lightning_program.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            tracker.encrypted_reward_0,
-            reward_handle,
+
+        // Reload to get post-collection balances
+        self.fee_account_a.reload()?;
+        self.fee_account_b.reload()?;
+
+        let fee_a = self.fee_account_a.amount.saturating_sub(pre_balance_a);
+        let fee_b = self.fee_account_b.amount.saturating_sub(pre_balance_b);
+
+        msg!("Fees collected: {} token_a, {} token_b", fee_a, fee_b);
+
+        // Cleartext at this point, before encryption
+        self.vault_pda.record_fees(fee_a, fee_b)?;
+
+        // ========== STEP 1.5: PERFORMANCE FEE (+ REFERRER SHARE) ==========
+        let has_referrer = self.vault_pda.referrer.is_some();
+        let fee_bps = self
+            .vault_config
+            .performance_fee_bps_for(self.position_tracker.fee_bps_override);
+        let (protocol_fee_a, referrer_fee_a) =
+            self.vault_config.performance_fee_split(fee_a, fee_bps, has_referrer);
+        let (protocol_fee_b, referrer_fee_b) =
+            self.vault_config.performance_fee_split(fee_b, fee_bps, has_referrer);
+
+        let vault_info = self.vault_pda.to_account_info();
+        transfer_fee(
+            self.token_program,
+            self.fee_account_a,
+            self.fee_recipient_account_a,
+            vault_info.clone(),
+            signer_seeds,
+            protocol_fee_a,
         )?;
-        
-        tracker.encrypted_reward_0 = new_total;
-        msg!("Encrypted reward 0 updated. New handle: {}", new_total);
-    }
-    if rewards[1] > 0 {
-        tracker.encrypted_reward_1 = tracker.encrypted_reward_1
-            .saturating_add(rewards[1] as u128);
+        transfer_fee(
+            self.token_program,
+            self.fee_account_b,
+            self.fee_recipient_account_b,
+            vault_info.clone(),
+            signer_seeds,
+            protocol_fee_b,
+        )?;
+        transfer_fee(
+            self.token_program,
+            self.fee_account_a,
+            self.referrer_account_a,
+            vault_info.clone(),
+            signer_seeds,
+            referrer_fee_a,
+        )?;
+        transfer_fee(
+            self.token_program,
+            self.fee_account_b,
+            self.referrer_account_b,
+            vault_info,
+            signer_seeds,
+            referrer_fee_b,
+        )?;
+
+        msg!(
+            "Performance fee: {}/{} protocol, {}/{} referrer",
+            protocol_fee_a, protocol_fee_b, referrer_fee_a, referrer_fee_b
+        );
+
+        Ok(CollectedFees {
+            fee_a,
+            fee_b,
+            fee_bps,
+            protocol_fee_a,
+            protocol_fee_b,
+            referrer_fee_a,
+            referrer_fee_b,
+        })
     }
-    if rewards[2] > 0 {
-        tracker.encrypted_reward_2 = tracker.encrypted_reward_2
-            .saturating_add(rewards[2] as u128);
+
+    /// Step 2 of collection: each active reward into its routed account
+    ///
+    /// Slots must have passed `validate_rewards`. Returns the amount each
+    /// reward account received.
+    pub fn collect_rewards(&mut self) -> Result<[u64; 3]> {
+        let user = self.position_tracker.user;
+        let vault_seeds = &[b"vault".as_ref(), user.as_ref(), &[self.vault_pda.bump]];
+        let signer_seeds = &[&vault_seeds[..]];
+        let vault_info = self.vault_pda.to_account_info();
+
+        let mut rewards = [0u64; 3];
+        let reward_count = self.position_tracker.reward_count as usize;
+        debug_assert!(reward_count <= whirlpool_cpi::NUM_REWARDS);
+        let mut collected = [false; whirlpool_cpi::NUM_REWARDS];
+        let reward_slots = self.reward_accounts.iter_mut().zip(&self.reward_vaults);
+        for (i, (reward_account, reward_vault)) in reward_slots.enumerate().take(reward_count) {
+            // Presence checked by `validate_rewards`
+            let (Some(reward_account), Some(reward_vault)) = (reward_account.as_mut(), reward_vault) else {
+                continue;
+            };
+            whirlpool_cpi::mark_reward_index(&mut collected, i as u8)?;
+
+            let pre_reward = reward_account.amount;
+            whirlpool_cpi::cpi_collect_reward(
+                self.whirlpool_program.clone(),
+                self.whirlpool.clone(),
+                vault_info.clone(),
+                self.whirlpool_position.clone(),
+                self.position_token_account.clone(),
+                reward_account.to_account_info(),
+                reward_vault.clone(),
+                self.token_program.to_account_info(),
+                signer_seeds,
+                i as u8,
+            )?;
+            reward_account.reload()?;
+
+            rewards[i] = reward_account.amount.saturating_sub(pre_reward);
+            msg!("Reward {} collected: {}", i, rewards[i]);
+        }
+        Ok(rewards)
     }
 
-    tracker.last_update = Clock::get()?.unix_timestamp;
+    /// Stage the user's share on the tracker, optionally encrypt it, and
+    /// number the collection in the realization ledger
+    ///
+    /// Only rewards routed to `Track` are staged.
+    pub fn track(&mut self, fees: &CollectedFees, rewards: [u64; 3], encrypt: bool) -> Result<()> {
+        let tracker = &mut **self.position_tracker;
+        let tracked_rewards = tracker.tracked_rewards(rewards);
+        let (net_fee_a, net_fee_b) = fees.net();
+        tracker.add_pending_profits(net_fee_a, net_fee_b, tracked_rewards)?;
 
-    // Unlock vault
-    ctx.accounts.vault_pda.unlock();
+        if encrypt && tracker.has_pending_profits() {
+            let inco_discriminators = IncoDiscriminators::load(&self.inco_config)?;
+            encrypt_pending_profits(
+                self.inco_lightning_program.clone(),
+                self.payer.clone(),
+                &inco_discriminators,
+                tracker,
+                self.vault_config.min_collect_threshold,
+            )?;
+        }
 
-    emit!(ProfitCollected {
+        let now = Clock::get()?.unix_timestamp;
+        tracker.last_update = now;
+        emit_profit_realized(tracker, fees.net(), rewards, now)
+    }
+}
+
+impl<'info> CollectAllProfits<'info> {
+    /// Borrow the accounts a collection runs over
+    pub(crate) fn collection(&mut self) -> Collection<'_, 'info> {
+        Collection {
+            payer: self.authority.to_account_info(),
+            vault_config: &self.vault_config,
+            vault_pda: &mut self.vault_pda,
+            position_tracker: &mut self.position_tracker,
+            whirlpool: self.whirlpool.to_account_info(),
+            whirlpool_position: self.whirlpool_position.to_account_info(),
+            position_token_account: self.position_token_account.to_account_info(),
+            token_vault_a: self.token_vault_a.to_account_info(),
+            token_vault_b: self.token_vault_b.to_account_info(),
+            fee_account_a: &mut self.fee_account_a,
+            fee_account_b: &mut self.fee_account_b,
+            fee_recipient_account_a: self.fee_recipient_account_a.as_deref(),
+            fee_recipient_account_b: self.fee_recipient_account_b.as_deref(),
+            referrer_account_a: self.referrer_account_a.as_deref(),
+            referrer_account_b: self.referrer_account_b.as_deref(),
+            reward_accounts: [
+                self.reward_account_0.as_mut(),
+                self.reward_account_1.as_mut(),
+                self.reward_account_2.as_mut(),
+            ],
+            reward_vaults: [
+                self.reward_vault_0.as_ref().map(|v| v.to_account_info()),
+                self.reward_vault_1.as_ref().map(|v| v.to_account_info()),
+                self.reward_vault_2.as_ref().map(|v| v.to_account_info()),
+            ],
+            inco_config: self.inco_config.to_account_info(),
+            inco_lightning_program: self.inco_lightning_program.to_account_info(),
+            whirlpool_program: self.whirlpool_program.to_account_info(),
+            token_program: &self.token_program,
+        }
+    }
+}
+
+/// Number a collection and emit its `ProfitRealized` ledger entry
+///
+/// `net_fees` is the user's share after the performance fee; `rewards` are
+/// the amounts collected, whatever their route.
+pub(crate) fn emit_profit_realized(
+    tracker: &mut PositionTracker,
+    net_fees: (u64, u64),
+    rewards: [u64; 3],
+    now: i64,
+) -> Result<()> {
+    let (sequence, position_age) = tracker.record_collection(now)?;
+    emit!(ProfitRealized {
         position: tracker.lp_position_mint,
-        fee_a,
-        fee_b,
+        user: tracker.user,
+        sequence,
+        position_age,
+        fee_a: net_fees.0,
+        fee_b: net_fees.1,
         reward_0: rewards[0],
         reward_1: rewards[1],
         reward_2: rewards[2],
-        timestamp: tracker.last_update,
+        timestamp: now,
     });
-
-    msg!("All profits collected and encrypted!");
     Ok(())
 }
 
+/// Fees measured by one collection, split by who they belong to
+pub(crate) struct CollectedFees {
+    pub fee_a: u64,
+    pub fee_b: u64,
+    /// Performance fee rate applied
+    pub fee_bps: u16,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub referrer_fee_a: u64,
+    pub referrer_fee_b: u64,
+}
+
+impl CollectedFees {
+    /// The user's share: what remains after the performance fee
+    pub fn net(&self) -> (u64, u64) {
+        (
+            self.fee_a - self.protocol_fee_a - self.referrer_fee_a,
+            self.fee_b - self.protocol_fee_b - self.referrer_fee_b,
+        )
+    }
+}
+
+/// Transfer a fee share out of a vault-owned fee account
+pub(crate) fn transfer_fee<'info>(
+    token_program: &Program<'info, Token>,
+    from: &Account<'info, TokenAccount>,
+    to: Option<&Account<'info, TokenAccount>>,
+    vault_pda: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let to = to.ok_or(CollectError::MissingFeeAccount)?;
+    require!(to.mint == from.mint, CollectError::FeeAccountMismatch);
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: vault_pda,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
 #[derive(Accounts)]
 pub struct CollectAllProfits<'info> {
     #[account(mut)]
@@ -191,12 +562,15 @@ pub struct CollectAllProfits<'info> {
         mut,
         seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
         bump = position_tracker.bump,
-        constraint = position_tracker.user == authority.key() @ CollectError::Unauthorized
+        constraint = position_tracker.is_owner_or_delegate(&authority.key()) @ CollectError::Unauthorized
     )]
     pub position_tracker: Account<'info, PositionTracker>,
     
     // Whirlpool accounts
-    /// CHECK: Whirlpool (validated by CPI)
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ CollectError::WhirlpoolMismatch
+    )]
     pub whirlpool: UncheckedAccount<'info>,
     
     /// CHECK: Position (validated by CPI)
@@ -215,14 +589,62 @@ pub struct CollectAllProfits<'info> {
     #[account(mut)]
     pub token_vault_b: UncheckedAccount<'info>,
     
-    // Fee collection accounts (owned by vault PDA)
-    #[account(mut)]
+    // Pool token mints (must match the tracker)
+    #[account(address = position_tracker.token_mint_a @ CollectError::InvalidFeeAccount)]
+    pub token_mint_a: Box<Account<'info, Mint>>,
+    
+    #[account(address = position_tracker.token_mint_b @ CollectError::InvalidFeeAccount)]
+    pub token_mint_b: Box<Account<'info, Mint>>,
+    
+    // Fee collection accounts: the vault PDA's ATAs, created on first collect.
+    // The ATA constraint pins the address, so no other account can be initialized.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = vault_pda
+    )]
     pub fee_account_a: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = vault_pda
+    )]
     pub fee_account_b: Account<'info, TokenAccount>,
     
-    // Optional reward accounts
+    // Performance fee destinations (required when the fee is non-zero)
+    #[account(
+        mut,
+        constraint = fee_recipient_account_a.owner == vault_config.fee_recipient @ CollectError::FeeAccountMismatch,
+        constraint = fee_recipient_account_a.mint == position_tracker.token_mint_a @ CollectError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_a: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        constraint = fee_recipient_account_b.owner == vault_config.fee_recipient @ CollectError::FeeAccountMismatch,
+        constraint = fee_recipient_account_b.mint == position_tracker.token_mint_b @ CollectError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_b: Option<Box<Account<'info, TokenAccount>>>,
+    
+    // Referrer fee destinations (required when the vault has a referrer)
+    #[account(
+        mut,
+        constraint = Some(referrer_account_a.owner) == vault_pda.referrer @ CollectError::FeeAccountMismatch,
+        constraint = referrer_account_a.mint == position_tracker.token_mint_a @ CollectError::FeeAccountMismatch
+    )]
+    pub referrer_account_a: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        constraint = Some(referrer_account_b.owner) == vault_pda.referrer @ CollectError::FeeAccountMismatch,
+        constraint = referrer_account_b.mint == position_tracker.token_mint_b @ CollectError::FeeAccountMismatch
+    )]
+    pub referrer_account_b: Option<Box<Account<'info, TokenAccount>>>,
+    
+    // Reward accounts (required for indexes below position_tracker.reward_count)
     #[account(mut)]
     pub reward_account_0: Option<Account<'info, TokenAccount>>,
     
@@ -232,6 +654,23 @@ pub struct CollectAllProfits<'info> {
     #[account(mut)]
     pub reward_account_2: Option<Account<'info, TokenAccount>>,
     
+    // Pool reward vaults (paired with the reward accounts above)
+    /// CHECK: Reward vault 0 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_0: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Reward vault 1 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_1: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Reward vault 2 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_2: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+    
     // Programs
     /// CHECK: Inco Lightning
     #[account(address = INCO_LIGHTNING_ID)]
@@ -242,12 +681,30 @@ pub struct CollectAllProfits<'info> {
     pub whirlpool_program: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[error_code]
 pub enum CollectError {
-    #[msg("Unauthorized - not position owner")]
+    #[msg("Unauthorized - not position owner or delegate")]
     Unauthorized,
+    #[msg("Reward account mint does not match pool reward mint")]
+    RewardMintMismatch,
+    #[msg("Fee destination account required for non-zero fee")]
+    MissingFeeAccount,
+    #[msg("Fee destination account owner or mint mismatch")]
+    FeeAccountMismatch,
+    #[msg("Collected reward is below the requested minimum")]
+    RewardBelowMinimum,
+    #[msg("Reward account or vault missing for an active reward slot")]
+    MissingRewardAccount,
+    #[msg("Fee account must be owned by the vault or position owner and hold the pool's mint")]
+    InvalidFeeAccount,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Reward account owner does not match the reward's route")]
+    RewardRouteMismatch,
 }
 
 #[event]
@@ -258,5 +715,39 @@ pub struct ProfitCollected {
     pub reward_0: u64,
     pub reward_1: u64,
     pub reward_2: u64,
+    /// Performance fee rate applied: the position override or the vault rate
+    pub performance_fee_bps: u16,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub referrer: Option<Pubkey>,
+    pub referrer_fee_a: u64,
+    pub referrer_fee_b: u64,
+    pub encrypted: bool,
+    pub below_threshold: bool,
+    pub encrypted_realized_profit_a: u128,
+    pub encrypted_realized_profit_b: u128,
+    pub encrypted_reward_0: u128,
+    pub encrypted_reward_1: u128,
+    pub encrypted_reward_2: u128,
+    pub timestamp: i64,
+}
+
+/// One entry of the per-position realization ledger (for tax/accounting tools)
+///
+/// Emitted by every collection path with the cleartext amounts realized at
+/// that moment, alongside the encrypted running totals.
+#[event]
+pub struct ProfitRealized {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    /// 1-based collection number on this tracker
+    pub sequence: u32,
+    /// Seconds since the position was opened
+    pub position_age: i64,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub reward_0: u64,
+    pub reward_1: u64,
+    pub reward_2: u64,
     pub timestamp: i64,
 }
@@ -0,0 +1,88 @@
+//! Compute unit diagnostics for sizing `ComputeBudget` limits
+//!
+//! Everything here is a no-op unless the program is built with the
+//! `cu-debug` feature, so release builds pay no compute for it. With the
+//! feature on, `checkpoint` logs the remaining units and `ComputeMeter`
+//! emits one `ComputeHint` per instruction.
+//!
+//! Units spent by Anchor before the handler runs (account deserialization
+//! and constraint checks) are not measured; the recommended limit adds
+//! headroom for them.
+
+use anchor_lang::prelude::*;
+
+/// Highest limit `setComputeUnitLimit` accepts
+pub const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// Headroom added on top of the measured cost, in basis points
+pub const HEADROOM_BPS: u64 = 2_000;
+
+/// Log the remaining compute units at a named checkpoint
+#[inline(always)]
+pub fn checkpoint(_label: &str) {
+    #[cfg(feature = "cu-debug")]
+    {
+        msg!("CU checkpoint: {}", _label);
+        anchor_lang::solana_program::log::sol_log_compute_units();
+    }
+}
+
+/// Measures the units a handler consumes and reports them once
+pub struct ComputeMeter {
+    #[cfg(feature = "cu-debug")]
+    start: u64,
+}
+
+impl ComputeMeter {
+    /// Start measuring at handler entry
+    #[inline(always)]
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "cu-debug")]
+            start: anchor_lang::solana_program::compute_units::sol_remaining_compute_units(),
+        }
+    }
+
+    /// Emit the `ComputeHint` for `instruction`
+    #[inline(always)]
+    pub fn finish(self, _instruction: &str) {
+        #[cfg(feature = "cu-debug")]
+        {
+            let remaining = anchor_lang::solana_program::compute_units::sol_remaining_compute_units();
+            let consumed = self.start.saturating_sub(remaining);
+            emit!(ComputeHint {
+                instruction: _instruction.to_string(),
+                consumed,
+                remaining,
+                recommended_limit: recommended_limit(consumed),
+            });
+        }
+    }
+}
+
+/// Measured cost plus headroom, capped at the runtime maximum
+pub fn recommended_limit(consumed: u64) -> u64 {
+    let headroom = consumed.saturating_mul(HEADROOM_BPS) / 10_000;
+    consumed.saturating_add(headroom).min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+#[event]
+pub struct ComputeHint {
+    pub instruction: String,
+    pub consumed: u64,
+    pub remaining: u64,
+    pub recommended_limit: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommended_limit() {
+        assert_eq!(recommended_limit(0), 0);
+        assert_eq!(recommended_limit(100_000), 120_000);
+        assert_eq!(recommended_limit(1_300_000), MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(recommended_limit(u64::MAX), MAX_COMPUTE_UNIT_LIMIT);
+    }
+}
@@ -0,0 +1,298 @@
+//! Crank Collect - Permissionless harvest that keeps profit tracking current
+//!
+//! This instruction:
+//! 1. Collects fees into the vault's ATAs and active rewards to the account
+//!    each reward's `RewardRoute` names
+//! 2. Takes the performance fee
+//! 3. Folds the net amounts into the tracker's encrypted handles
+//! 4. Pays the caller a small fixed tip out of the VaultConfig account
+//!
+//! Steps 1-3 are the shared `collect_profits::Collection` steps, so a crank
+//! collects exactly like `collect_all_profits`.
+//!
+//! Anyone may call it, but every destination is pinned to the vault, the
+//! owner or the fee recipient, so the caller never receives tokens. Whirlpool
+//! CPIs are signed by the vault PDA. The tip comes from lamports deposited on
+//! the config account above its rent-exempt minimum, and is only paid when an
+//! amount collected by this call reached `min_collect_threshold`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::{INCO_LIGHTNING_ID, WHIRLPOOL_PROGRAM_ID};
+use super::collect_profits::Collection;
+
+/// Fixed tip paid to the cranker per successful call (one signature fee)
+pub const CRANK_TIP_LAMPORTS: u64 = 5_000;
+
+/// Collect and encrypt a position's profits on the owner's behalf
+pub fn handler(ctx: Context<CrankCollect>) -> Result<()> {
+    // Step 0: Check not paused + position still open + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+    ctx.accounts.position_tracker.require_not_frozen_for(&ctx.accounts.cranker.key())?;
+    let instruction = crate::instruction::CrankCollect::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        // Same steps as `collect_all_profits`, reward routes included
+        let mut collection = accounts.collection();
+        collection.validate_rewards()?;
+
+        // ========== STEP 1: COLLECT TOKEN A + B FEES (+ PERFORMANCE FEE) ==========
+        let fees = collection.collect_fees()?;
+
+        // ========== STEP 2: COLLECT REWARDS ==========
+        let rewards = collection.collect_rewards()?;
+
+        // ========== STEP 3: ENCRYPT INTO THE TRACKER ==========
+        collection.track(&fees, rewards, true)?;
+
+        // ========== STEP 4: TIP THE CRANKER ==========
+        // Only when something collected here reached the collect threshold,
+        // and never below the config's rent-exempt minimum
+        let threshold = accounts.vault_config.min_collect_threshold.max(1);
+        let harvested = [fees.fee_a, fees.fee_b]
+            .into_iter()
+            .chain(rewards)
+            .any(|amount| amount >= threshold);
+        let tip = if harvested {
+            let config_info = accounts.vault_config.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(config_info.data_len());
+            let tip = config_info.lamports().saturating_sub(rent_floor).min(CRANK_TIP_LAMPORTS);
+            if tip > 0 {
+                **config_info.try_borrow_mut_lamports()? -= tip;
+                **accounts.cranker.to_account_info().try_borrow_mut_lamports()? += tip;
+            }
+            tip
+        } else {
+            0
+        };
+
+        let tracker = &accounts.position_tracker;
+        emit!(PositionCranked {
+            position: tracker.lp_position_mint,
+            cranker: accounts.cranker.key(),
+            fee_a: fees.fee_a,
+            fee_b: fees.fee_b,
+            reward_0: rewards[0],
+            reward_1: rewards[1],
+            reward_2: rewards[2],
+            protocol_fee_a: fees.protocol_fee_a,
+            protocol_fee_b: fees.protocol_fee_b,
+            referrer_fee_a: fees.referrer_fee_a,
+            referrer_fee_b: fees.referrer_fee_b,
+            tip_lamports: tip,
+            timestamp: tracker.last_update,
+        });
+
+        Ok(())
+    })
+}
+
+impl<'info> CrankCollect<'info> {
+    /// Borrow the accounts a collection runs over; the cranker pays for Inco handles
+    fn collection(&mut self) -> Collection<'_, 'info> {
+        Collection {
+            payer: self.cranker.to_account_info(),
+            vault_config: &self.vault_config,
+            vault_pda: &mut self.vault_pda,
+            position_tracker: &mut self.position_tracker,
+            whirlpool: self.whirlpool.to_account_info(),
+            whirlpool_position: self.whirlpool_position.to_account_info(),
+            position_token_account: self.position_token_account.to_account_info(),
+            token_vault_a: self.token_vault_a.to_account_info(),
+            token_vault_b: self.token_vault_b.to_account_info(),
+            fee_account_a: &mut self.fee_account_a,
+            fee_account_b: &mut self.fee_account_b,
+            fee_recipient_account_a: self.fee_recipient_account_a.as_deref(),
+            fee_recipient_account_b: self.fee_recipient_account_b.as_deref(),
+            referrer_account_a: self.referrer_account_a.as_deref(),
+            referrer_account_b: self.referrer_account_b.as_deref(),
+            reward_accounts: [
+                self.reward_account_0.as_deref_mut(),
+                self.reward_account_1.as_deref_mut(),
+                self.reward_account_2.as_deref_mut(),
+            ],
+            reward_vaults: [
+                self.reward_vault_0.as_ref().map(|v| v.to_account_info()),
+                self.reward_vault_1.as_ref().map(|v| v.to_account_info()),
+                self.reward_vault_2.as_ref().map(|v| v.to_account_info()),
+            ],
+            inco_config: self.inco_config.to_account_info(),
+            inco_lightning_program: self.inco_lightning_program.to_account_info(),
+            whirlpool_program: self.whirlpool_program.to_account_info(),
+            token_program: &self.token_program,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CrankCollect<'info> {
+    /// Anyone; receives only the lamport tip
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// Also the tip pool: lamports above rent fund crank tips
+    #[account(mut, seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Box<Account<'info, VaultConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Box<Account<'info, VaultPDA>>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump
+    )]
+    pub position_tracker: Box<Account<'info, PositionTracker>>,
+
+    // Whirlpool accounts
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ CrankError::WhirlpoolMismatch
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Position (validated by CPI)
+    #[account(mut)]
+    pub whirlpool_position: UncheckedAccount<'info>,
+
+    /// CHECK: Position token account
+    pub position_token_account: UncheckedAccount<'info>,
+
+    // Token vaults
+    /// CHECK: Token vault A
+    #[account(mut)]
+    pub token_vault_a: UncheckedAccount<'info>,
+
+    /// CHECK: Token vault B
+    #[account(mut)]
+    pub token_vault_b: UncheckedAccount<'info>,
+
+    // Pool token mints (must match the tracker)
+    #[account(address = position_tracker.token_mint_a @ CrankError::InvalidVaultAccount)]
+    pub token_mint_a: Box<Account<'info, Mint>>,
+
+    #[account(address = position_tracker.token_mint_b @ CrankError::InvalidVaultAccount)]
+    pub token_mint_b: Box<Account<'info, Mint>>,
+
+    // Fee collection accounts: the vault PDA's ATAs, as in `collect_all_profits`
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = vault_pda
+    )]
+    pub fee_account_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = vault_pda
+    )]
+    pub fee_account_b: Box<Account<'info, TokenAccount>>,
+
+    // Performance fee destinations (required when the fee is non-zero)
+    #[account(
+        mut,
+        constraint = fee_recipient_account_a.owner == vault_config.fee_recipient @ CrankError::FeeAccountMismatch,
+        constraint = fee_recipient_account_a.mint == position_tracker.token_mint_a @ CrankError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_a: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_account_b.owner == vault_config.fee_recipient @ CrankError::FeeAccountMismatch,
+        constraint = fee_recipient_account_b.mint == position_tracker.token_mint_b @ CrankError::FeeAccountMismatch
+    )]
+    pub fee_recipient_account_b: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Referrer fee destinations (required when the vault has a referrer)
+    #[account(
+        mut,
+        constraint = Some(referrer_account_a.owner) == vault_pda.referrer @ CrankError::FeeAccountMismatch,
+        constraint = referrer_account_a.mint == position_tracker.token_mint_a @ CrankError::FeeAccountMismatch
+    )]
+    pub referrer_account_a: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = Some(referrer_account_b.owner) == vault_pda.referrer @ CrankError::FeeAccountMismatch,
+        constraint = referrer_account_b.mint == position_tracker.token_mint_b @ CrankError::FeeAccountMismatch
+    )]
+    pub referrer_account_b: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Reward accounts (owner checked against each reward's route)
+    #[account(mut)]
+    pub reward_account_0: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub reward_account_1: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub reward_account_2: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Pool reward vaults (paired with the reward accounts above)
+    /// CHECK: Reward vault 0 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_0: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Reward vault 1 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_1: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Reward vault 2 (validated by CPI)
+    #[account(mut)]
+    pub reward_vault_2: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[error_code]
+pub enum CrankError {
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Token account must be owned by the vault and hold the pool's mint")]
+    InvalidVaultAccount,
+    #[msg("Reward account mint does not match pool reward mint")]
+    RewardMintMismatch,
+    #[msg("Reward account or vault missing for an active reward slot")]
+    MissingRewardAccount,
+    #[msg("Fee destination account owner or mint mismatch")]
+    FeeAccountMismatch,
+}
+
+#[event]
+pub struct PositionCranked {
+    pub position: Pubkey,
+    pub cranker: Pubkey,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub reward_0: u64,
+    pub reward_1: u64,
+    pub reward_2: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub referrer_fee_a: u64,
+    pub referrer_fee_b: u64,
+    pub tip_lamports: u64,
+    pub timestamp: i64,
+}
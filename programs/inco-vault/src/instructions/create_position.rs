@@ -5,31 +5,63 @@
 //! 2. Opens real Whirlpool position (mints LP NFT)
 //! 3. Adds liquidity (transfers tokens to pool)
 //! 4. Creates PositionTracker with encrypted data
+//!
+//! `create_position_by_amounts` takes token amounts instead of liquidity: it
+//! computes the liquidity `amount_a`/`amount_b` fund at the pool's current
+//! price with the slippage margin held back, then proceeds as above with the
+//! amounts themselves as hard maxima, so it never spends more than asked.
+//!
+//! `position_mint` must be a fresh keypair that signs the transaction: an
+//! empty, system-owned account that Whirlpool initializes as the LP NFT
+//! mint. An existing mint can therefore never be bound to a second tracker.
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 
-use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use crate::math;
+use crate::state::{PoolRiskParams, PositionTracker, VaultPDA, VaultConfig};
 use super::whirlpool_cpi::{self, OpenPositionBumps};
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
 
 // Pubkey is imported from anchor_lang::prelude::*;
 
 // Inco Lightning program ID
 // 5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj
-pub const INCO_LIGHTNING_ID: Pubkey = Pubkey::new_from_array([
+const DEPLOYED_INCO_LIGHTNING_ID: Pubkey = Pubkey::new_from_array([
     72, 109, 138, 238, 163, 139, 180, 197, 134, 126,  79,  99, 196,  95,  65, 212,
     87,  50,  11, 181, 166,  87, 194, 215, 222, 102,  28, 190, 163, 126, 167,  52
 ]);
 
 // Whirlpool program ID
 // whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc
-pub const WHIRLPOOL_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+const DEPLOYED_WHIRLPOOL_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
     14,   3, 104,  95, 142, 144, 144,  83, 228,  88,  18,  28, 102, 245, 167, 106,
    237, 199, 112, 106, 161,  28, 130, 248, 170, 149,  42, 143,  43, 120, 121, 169
 ]);
 
+#[cfg(not(feature = "localnet"))]
+pub const INCO_LIGHTNING_ID: Pubkey = DEPLOYED_INCO_LIGHTNING_ID;
+
+#[cfg(not(feature = "localnet"))]
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = DEPLOYED_WHIRLPOOL_PROGRAM_ID;
+
+// With `localnet`, mock program IDs can be injected at build time, e.g.
+// `INCO_LIGHTNING_ID=<mock> anchor build -- --features localnet`
+#[cfg(feature = "localnet")]
+pub const INCO_LIGHTNING_ID: Pubkey = match option_env!("INCO_LIGHTNING_ID") {
+    Some(id) => Pubkey::from_str_const(id),
+    None => DEPLOYED_INCO_LIGHTNING_ID,
+};
+
+#[cfg(feature = "localnet")]
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = match option_env!("WHIRLPOOL_PROGRAM_ID") {
+    Some(id) => Pubkey::from_str_const(id),
+    None => DEPLOYED_WHIRLPOOL_PROGRAM_ID,
+};
+
 /// Create a new position with liquidity
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreatePositionWithLiquidity>,
     encrypted_amount_a: Vec<u8>,
@@ -41,139 +73,297 @@ pub fn handler(
     token_max_a: u64,
     token_max_b: u64,
     max_slippage_bps: Option<u16>,
+    label: Option<[u8; 32]>,
 ) -> Result<()> {
-    // Step 0: Check vault not paused + validate liquidity
-    ctx.accounts.vault_config.require_not_paused()?;
-    ctx.accounts.vault_config.validate_liquidity(liquidity_amount)?;
-    
-    // Step 0.5: Lock vault (reentrancy guard)
-    ctx.accounts.vault_pda.lock()?;
+    // Pad the client's maxima by the slippage tolerance
+    let pool_risk = PoolRiskParams::load(&ctx.accounts.pool_risk_params)?;
+    let slippage = ctx
+        .accounts
+        .vault_config
+        .resolve_slippage(pool_risk.as_ref(), max_slippage_bps)?;
+    let max_a_with_slippage = math::amount_with_slippage_max(token_max_a, slippage)
+        .ok_or(CreatePositionError::Overflow)?;
+    let max_b_with_slippage = math::amount_with_slippage_max(token_max_b, slippage)
+        .ok_or(CreatePositionError::Overflow)?;
 
-    // Step 1: Encrypt amounts via Inco CPI
-    // Create encrypted account A
-    let handle_a = super::inco_lightning_cpi::cpi_new_euint128(
-        ctx.accounts.inco_lightning_program.to_account_info(),
-        ctx.accounts.authority.to_account_info(),
+    open(
+        ctx,
+        crate::instruction::CreatePositionWithLiquidity::DISCRIMINATOR,
         encrypted_amount_a,
-        amount_type,
-    )?;
-    msg!("Encrypted account A created with handle: {}", handle_a);
-
-    // Create encrypted account B
-    let handle_b = super::inco_lightning_cpi::cpi_new_euint128(
-        ctx.accounts.inco_lightning_program.to_account_info(),
-        ctx.accounts.authority.to_account_info(),
         encrypted_amount_b,
         amount_type,
-    )?;
-    msg!("Encrypted account B created with handle: {}", handle_b);
-
-    msg!("Encrypted handles: A={}, B={}", handle_a, handle_b);
-
-    // Step 2: Build signer seeds for vault PDA
-    let vault_seeds = &[
-        b"vault".as_ref(),
-        ctx.accounts.authority.key.as_ref(),
-        &[ctx.accounts.vault_pda.bump],
-    ];
-    let signer_seeds = &[&vault_seeds[..]];
-
-    // Step 3: CPI to Whirlpool: open_position
-    // This mints the LP NFT and creates the position account
-    whirlpool_cpi::cpi_open_position(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        ctx.accounts.authority.to_account_info(),
-        ctx.accounts.vault_pda.to_account_info(),
-        ctx.accounts.whirlpool_position.to_account_info(),
-        ctx.accounts.position_mint.to_account_info(),
-        ctx.accounts.position_token_account.to_account_info(),
-        ctx.accounts.whirlpool.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.rent.to_account_info(),
-        ctx.accounts.associated_token_program.to_account_info(),
-        signer_seeds,
-        OpenPositionBumps { position_bump: 255 }, // Bump is computed by Whirlpool program
         tick_lower_index,
         tick_upper_index,
-    )?;
-
-    msg!("LP position opened at ticks [{}, {}]", tick_lower_index, tick_upper_index);
-
-    // Step 4: CPI to Whirlpool: increase_liquidity
-    // Calculate slippage-adjusted max amounts
-    let slippage = max_slippage_bps.unwrap_or(ctx.accounts.vault_config.default_max_slippage_bps);
-    let max_a_with_slippage = token_max_a
-        .checked_mul(10000 + slippage as u64)
-        .ok_or(CreatePositionError::Overflow)?
-        .checked_div(10000)
-        .ok_or(CreatePositionError::Overflow)?;
-    let max_b_with_slippage = token_max_b
-        .checked_mul(10000 + slippage as u64)
-        .ok_or(CreatePositionError::Overflow)?
-        .checked_div(10000)
-        .ok_or(CreatePositionError::Overflow)?;
-
-    whirlpool_cpi::cpi_increase_liquidity(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        ctx.accounts.whirlpool.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.vault_pda.to_account_info(),
-        ctx.accounts.whirlpool_position.to_account_info(),
-        ctx.accounts.position_token_account.to_account_info(),
-        ctx.accounts.token_account_a.to_account_info(),
-        ctx.accounts.token_account_b.to_account_info(),
-        ctx.accounts.token_vault_a.to_account_info(),
-        ctx.accounts.token_vault_b.to_account_info(),
-        ctx.accounts.tick_array_lower.to_account_info(),
-        ctx.accounts.tick_array_upper.to_account_info(),
-        signer_seeds,
         liquidity_amount,
         max_a_with_slippage,
         max_b_with_slippage,
+        label,
+    )
+}
+
+/// Body of both create instructions; `instruction` is the caller's discriminator
+///
+/// `token_max_a`/`token_max_b` are final: callers apply any slippage first.
+#[allow(clippy::too_many_arguments)]
+fn open(
+    ctx: Context<CreatePositionWithLiquidity>,
+    instruction: &'static [u8],
+    encrypted_amount_a: Vec<u8>,
+    encrypted_amount_b: Vec<u8>,
+    amount_type: u8,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+    label: Option<[u8; 32]>,
+) -> Result<()> {
+    // Step 0: Bound the ciphertexts before anything copies them
+    inco_lightning_cpi::validate_encrypted_amount(&encrypted_amount_a, amount_type)?;
+    inco_lightning_cpi::validate_encrypted_amount(&encrypted_amount_b, amount_type)?;
+
+    // Check vault not paused + validate inputs + per-vault cap
+    // Zero is rejected regardless of the configured minimum
+    require!(liquidity_amount > 0, CreatePositionError::ZeroLiquidity);
+    ctx.accounts.vault_config.require_not_paused()?;
+    ctx.accounts.vault_config.require_deposits_enabled()?;
+    let pool_risk = PoolRiskParams::load(&ctx.accounts.pool_risk_params)?;
+    ctx.accounts.vault_config.validate_liquidity(pool_risk.as_ref(), liquidity_amount)?;
+    ctx.accounts.vault_config.validate_tick_width(tick_lower_index, tick_upper_index)?;
+    require!(
+        ctx.accounts.vault_pda.position_count < ctx.accounts.vault_config.max_positions_per_vault,
+        CreatePositionError::MaxPositionsReached
+    );
+    let vault_cap = ctx.accounts.vault_config.max_total_liquidity_per_vault;
+    ctx.accounts.vault_pda.add_liquidity(liquidity_amount, vault_cap)?;
+    let position_bump = ctx.bumps.whirlpool_position;
+    
+    // Step 0.5: Lock vault (reentrancy guard)
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        // Refuse to open on a pool pinned at an anomalous price (e.g. a depeg)
+        let pool_tick =
+            whirlpool_cpi::read_whirlpool_tick_current_index(&accounts.whirlpool.to_account_info())?;
+        accounts.vault_config.validate_pool_tick(pool_tick)?;
+
+        whirlpool_cpi::validate_tick_arrays(
+            &accounts.whirlpool.to_account_info(),
+            accounts.tick_array_lower.key,
+            accounts.tick_array_upper.key,
+            tick_lower_index,
+            tick_upper_index,
+        )?;
+        whirlpool_cpi::require_tick_array_initialized(&accounts.tick_array_lower)?;
+        whirlpool_cpi::require_tick_array_initialized(&accounts.tick_array_upper)?;
+
+        // Step 1: Encrypt amounts via Inco CPI
+        let inco_discriminators = IncoDiscriminators::load(&accounts.inco_config)?;
+
+        // Create encrypted account A
+        let handle_a = super::inco_lightning_cpi::cpi_new_euint128(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            encrypted_amount_a,
+            amount_type,
+        )?;
+        msg!("Encrypted account A created with handle: {}", handle_a);
+
+        // Create encrypted account B
+        let handle_b = super::inco_lightning_cpi::cpi_new_euint128(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            encrypted_amount_b,
+            amount_type,
+        )?;
+        msg!("Encrypted account B created with handle: {}", handle_b);
+
+        msg!("Encrypted handles: A={}, B={}", handle_a, handle_b);
+
+        // Step 2: Build signer seeds for vault PDA
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.authority.key.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        // Step 3: CPI to Whirlpool: open_position
+        // This mints the LP NFT and creates the position account
+        whirlpool_cpi::cpi_open_position(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.funder.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_mint.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.associated_token_program.to_account_info(),
+            signer_seeds,
+            // Whirlpool checks this against its own derivation of the position PDA
+            OpenPositionBumps { position_bump },
+            tick_lower_index,
+            tick_upper_index,
+        )?;
+
+        msg!("LP position opened at ticks [{}, {}]", tick_lower_index, tick_upper_index);
+
+        // Step 3.5: The LP NFT must be a single non-fungible token
+        let position_mint = {
+            let info = accounts.position_mint.to_account_info();
+            require!(
+                info.owner == &anchor_spl::token::ID,
+                CreatePositionError::InvalidPositionMint
+            );
+            let data = info.try_borrow_data()?;
+            Mint::try_deserialize(&mut &data[..])?
+        };
+        require!(
+            position_mint.supply == 1 && position_mint.decimals == 0,
+            CreatePositionError::InvalidPositionMint
+        );
+
+        // Step 4: CPI to Whirlpool: increase_liquidity
+        whirlpool_cpi::cpi_increase_liquidity(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_account_a.to_account_info(),
+            accounts.token_account_b.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.tick_array_lower.to_account_info(),
+            accounts.tick_array_upper.to_account_info(),
+            signer_seeds,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        )?;
+
+        msg!("Liquidity added: {} (max A: {}, max B: {})", 
+             liquidity_amount, token_max_a, token_max_b);
+
+        // Step 4.5: The position must hold at least the minimum liquidity,
+        // measured on the position rather than taken from the request
+        let liquidity_minted = whirlpool_cpi::read_position_liquidity(
+            &accounts.whirlpool_position.to_account_info(),
+        )?;
+        let min_liquidity = accounts
+            .vault_config
+            .min_liquidity_for(pool_risk.as_ref());
+        require!(
+            liquidity_minted >= min_liquidity,
+            CreatePositionError::InsufficientLiquidityMinted
+        );
+
+        // Step 5: Initialize PositionTracker with encrypted data
+        let pool = whirlpool_cpi::read_whirlpool_meta(&accounts.whirlpool.to_account_info())?;
+        let reward_mints = pool.reward_mints;
+        let reward_count = whirlpool_cpi::reward_count_from_mints(&reward_mints);
+        // Pool price snapshot for off-chain PnL attribution
+        let entry_sqrt_price = pool.sqrt_price;
+        let entry_tick = pool.tick_current_index;
+        let token_mints = (pool.token_mint_a, pool.token_mint_b);
+        let tracker = &mut accounts.position_tracker;
+        tracker.initialize(
+            accounts.authority.key(),
+            accounts.position_mint.key(),
+            accounts.whirlpool.key(),
+            handle_a,
+            handle_b,
+            tick_lower_index,
+            tick_upper_index,
+            ctx.bumps.position_tracker,
+            reward_mints,
+            reward_count,
+            entry_sqrt_price,
+            token_mints,
+        )?;
+        tracker.label = label.unwrap_or_default();
+
+        // Step 6: Update vault stats
+        accounts.vault_pda.increment_position_count()?;
+
+        // Emit event
+        emit!(PositionCreated {
+            user: accounts.authority.key(),
+            position_mint: accounts.position_mint.key(),
+            whirlpool: accounts.whirlpool.key(),
+            tick_lower: tick_lower_index,
+            tick_upper: tick_upper_index,
+            liquidity: liquidity_amount,
+            encrypted_deposit_a: handle_a,
+            encrypted_deposit_b: handle_b,
+            entry_sqrt_price,
+            entry_tick,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Position created successfully!");
+        Ok(())
+    })
+}
+
+/// Create a new position funded by token amounts rather than raw liquidity
+#[allow(clippy::too_many_arguments)]
+pub fn handler_by_amounts(
+    ctx: Context<CreatePositionWithLiquidity>,
+    encrypted_amount_a: Vec<u8>,
+    encrypted_amount_b: Vec<u8>,
+    amount_type: u8,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_a: u64,
+    amount_b: u64,
+    max_slippage_bps: Option<u16>,
+    label: Option<[u8; 32]>,
+) -> Result<()> {
+    // Size the liquidity with the slippage margin held back, so a price
+    // move before execution is absorbed without exceeding the amounts
+    let pool_risk = PoolRiskParams::load(&ctx.accounts.pool_risk_params)?;
+    let slippage = ctx
+        .accounts
+        .vault_config
+        .resolve_slippage(pool_risk.as_ref(), max_slippage_bps)?;
+    let sqrt_price = whirlpool_cpi::read_whirlpool_sqrt_price(&ctx.accounts.whirlpool.to_account_info())?;
+    let liquidity_amount = math::get_liquidity_from_amounts(
+        sqrt_price,
+        math::sqrt_price_from_tick_index(tick_lower_index)?,
+        math::sqrt_price_from_tick_index(tick_upper_index)?,
+        math::amount_with_slippage_min(amount_a, slippage),
+        math::amount_with_slippage_min(amount_b, slippage),
     )?;
+    msg!("Liquidity {} from amounts A={}, B={}", liquidity_amount, amount_a, amount_b);
 
-    msg!("Liquidity added: {} (max A: {}, max B: {})", 
-         liquidity_amount, max_a_with_slippage, max_b_with_slippage);
-
-    // Step 5: Initialize PositionTracker with encrypted data
-    let tracker = &mut ctx.accounts.position_tracker;
-    tracker.initialize(
-        ctx.accounts.authority.key(),
-        ctx.accounts.position_mint.key(),
-        ctx.accounts.whirlpool.key(),
-        handle_a,
-        handle_b,
+    open(
+        ctx,
+        crate::instruction::CreatePositionByAmounts::DISCRIMINATOR,
+        encrypted_amount_a,
+        encrypted_amount_b,
+        amount_type,
         tick_lower_index,
         tick_upper_index,
-        ctx.bumps.position_tracker,
-    )?;
-
-    // Step 6: Update vault stats
-    ctx.accounts.vault_pda.increment_position_count();
-    
-    // Step 7: Unlock vault
-    ctx.accounts.vault_pda.unlock();
-
-    // Emit event
-    emit!(PositionCreated {
-        user: ctx.accounts.authority.key(),
-        position_mint: ctx.accounts.position_mint.key(),
-        whirlpool: ctx.accounts.whirlpool.key(),
-        tick_lower: tick_lower_index,
-        tick_upper: tick_upper_index,
-        liquidity: liquidity_amount,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
-
-    msg!("Position created successfully!");
-    Ok(())
+        liquidity_amount,
+        amount_a,
+        amount_b,
+        label,
+    )
 }
 
 #[derive(Accounts)]
 pub struct CreatePositionWithLiquidity<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    /// Pays rent for the tracker and position; may be a sponsor or the authority
+    #[account(mut)]
+    pub funder: Signer<'info>,
     
     // Global config (for pause check)
     #[account(seeds = [b"config"], bump = vault_config.bump)]
@@ -191,7 +381,7 @@ pub struct CreatePositionWithLiquidity<'info> {
     // Position tracker (new)
     #[account(
         init,
-        payer = authority,
+        payer = funder,
         space = PositionTracker::LEN,
         seeds = [b"tracker", authority.key().as_ref(), whirlpool.key().as_ref()],
         bump
@@ -202,17 +392,32 @@ pub struct CreatePositionWithLiquidity<'info> {
     /// CHECK: Whirlpool account (validated by CPI)
     pub whirlpool: UncheckedAccount<'info>,
     
-    /// CHECK: Whirlpool position (created by CPI)
-    #[account(mut)]
+    /// CHECK: Whirlpool position (created by CPI at its canonical PDA)
+    #[account(
+        mut,
+        seeds = [b"position", position_mint.key().as_ref()],
+        bump,
+        seeds::program = WHIRLPOOL_PROGRAM_ID
+    )]
     pub whirlpool_position: UncheckedAccount<'info>,
     
-    // LP NFT mint
-    #[account(mut)]
-    pub position_mint: Box<Account<'info, Mint>>,
+    /// CHECK: LP NFT mint keypair, initialized by Whirlpool's open_position
+    #[account(
+        mut,
+        signer,
+        constraint = position_mint.data_is_empty()
+            && position_mint.owner == &anchor_lang::system_program::ID
+            @ CreatePositionError::PositionMintReused
+    )]
+    pub position_mint: UncheckedAccount<'info>,
     
-    // LP NFT token account (owned by vault PDA)
-    #[account(mut)]
-    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Vault's LP NFT associated token account, created by open_position
+    #[account(
+        mut,
+        address = get_associated_token_address(&vault_pda.key(), &position_mint.key())
+            @ CreatePositionError::InvalidPositionMint
+    )]
+    pub position_token_account: UncheckedAccount<'info>,
     
     // User token accounts for deposit
     #[account(
@@ -237,14 +442,23 @@ pub struct CreatePositionWithLiquidity<'info> {
     pub token_vault_b: UncheckedAccount<'info>,
     
     // Tick arrays
-    /// CHECK: Tick array lower (validated by CPI)
+    /// CHECK: Tick array lower (must be initialized; validated by CPI)
     #[account(mut)]
     pub tick_array_lower: UncheckedAccount<'info>,
     
-    /// CHECK: Tick array upper (validated by CPI)
+    /// CHECK: Tick array upper (must be initialized; validated by CPI)
     #[account(mut)]
     pub tick_array_upper: UncheckedAccount<'info>,
     
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+    
+    // Per-pool liquidity and slippage limits (always passed, so they can't be skipped)
+    /// CHECK: Per-pool limits PDA; read whenever initialized
+    #[account(seeds = [b"pool_risk_params", whirlpool.key().as_ref()], bump)]
+    pub pool_risk_params: UncheckedAccount<'info>,
+    
     // Programs
     /// CHECK: Inco Lightning program
     #[account(address = INCO_LIGHTNING_ID)]
@@ -268,6 +482,16 @@ pub enum CreatePositionError {
     Overflow,
     #[msg("Slippage exceeded")]
     SlippageExceeded,
+    #[msg("Liquidity minted is below the configured minimum")]
+    InsufficientLiquidityMinted,
+    #[msg("Position mint is not a Whirlpool LP NFT")]
+    InvalidPositionMint,
+    #[msg("Liquidity amount must be non-zero")]
+    ZeroLiquidity,
+    #[msg("Position mint account already exists")]
+    PositionMintReused,
+    #[msg("Vault already holds the maximum number of positions")]
+    MaxPositionsReached,
 }
 
 #[event]
@@ -278,5 +502,9 @@ pub struct PositionCreated {
     pub tick_lower: i32,
     pub tick_upper: i32,
     pub liquidity: u128,
+    pub encrypted_deposit_a: u128,
+    pub encrypted_deposit_b: u128,
+    pub entry_sqrt_price: u128,
+    pub entry_tick: i32,
     pub timestamp: i64,
 }
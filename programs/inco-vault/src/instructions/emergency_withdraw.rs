@@ -0,0 +1,239 @@
+//! Emergency Withdraw - Principal exit for while the vault is paused
+//!
+//! This instruction:
+//! 1. Requires the vault to be paused (owner auth and reentrancy lock still
+//!    apply); otherwise `withdraw_position` and its withdrawal queue must be
+//!    used
+//! 2. Decreases ALL liquidity from the Whirlpool position to the owner
+//! 3. Closes the position when nothing is owed on it
+//!
+//! Fee/reward collection and profit encryption are skipped to keep the CPI
+//! surface minimal. Whirlpool will not close a position with fees or rewards
+//! owed, so in that case the close is skipped and the emptied position stays
+//! open until a regular `withdraw_position` after unpause.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+
+use crate::state::{PositionTracker, VaultConfig, VaultPDA};
+use super::create_position::WHIRLPOOL_PROGRAM_ID;
+use super::whirlpool_cpi;
+
+/// Withdraw all liquidity while the vault is paused
+pub fn handler(
+    ctx: Context<EmergencyWithdraw>,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    // Step 0: Position still open + lock (paused checked on the config account)
+    ctx.accounts.position_tracker.require_not_closed()?;
+    let instruction = crate::instruction::EmergencyWithdraw::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        whirlpool_cpi::validate_tick_arrays(
+            &accounts.whirlpool.to_account_info(),
+            accounts.tick_array_lower.key,
+            accounts.tick_array_upper.key,
+            accounts.position_tracker.tick_lower,
+            accounts.position_tracker.tick_upper,
+        )?;
+
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.authority.key.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let pre_balance_a = accounts.token_account_a.amount;
+        let pre_balance_b = accounts.token_account_b.amount;
+
+        // Step 1: Decrease ALL liquidity
+        let position_info = accounts.whirlpool_position.to_account_info();
+        let liquidity = whirlpool_cpi::read_position_liquidity(&position_info)?;
+
+        if liquidity > 0 {
+            whirlpool_cpi::cpi_decrease_liquidity(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.whirlpool.to_account_info(),
+                accounts.token_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                position_info.clone(),
+                accounts.position_token_account.to_account_info(),
+                accounts.token_account_a.to_account_info(),
+                accounts.token_account_b.to_account_info(),
+                accounts.token_vault_a.to_account_info(),
+                accounts.token_vault_b.to_account_info(),
+                accounts.tick_array_lower.to_account_info(),
+                accounts.tick_array_upper.to_account_info(),
+                signer_seeds,
+                liquidity,
+                token_min_a,
+                token_min_b,
+            )?;
+        }
+        accounts.vault_pda.remove_liquidity(liquidity);
+
+        accounts.token_account_a.reload()?;
+        accounts.token_account_b.reload()?;
+        let received_a = accounts.token_account_a.amount.saturating_sub(pre_balance_a);
+        let received_b = accounts.token_account_b.amount.saturating_sub(pre_balance_b);
+
+        msg!("Emergency withdrawal: liquidity={}, A={}, B={}", liquidity, received_a, received_b);
+
+        // Step 2: Close position if nothing is owed on it
+        let position_closed = !whirlpool_cpi::position_has_owed_tokens(&position_info)?;
+        if position_closed {
+            whirlpool_cpi::cpi_close_position(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                accounts.authority.to_account_info(),
+                position_info,
+                accounts.position_mint.to_account_info(),
+                accounts.position_token_account.to_account_info(),
+                accounts.token_program.to_account_info(),
+                signer_seeds,
+            )?;
+            accounts.vault_pda.decrement_position_count();
+            msg!("Position closed");
+        } else {
+            msg!("Close skipped: fees or rewards still owed on position");
+        }
+
+        // Step 3: Update tracker lifecycle
+        let tracker = &mut accounts.position_tracker;
+        tracker.record_withdrawal(liquidity, position_closed, Clock::get()?.unix_timestamp)?;
+
+        emit!(EmergencyWithdrawal {
+            user: accounts.authority.key(),
+            position_mint: accounts.position_mint.key(),
+            liquidity_withdrawn: liquidity,
+            token_a_received: received_a,
+            token_b_received: received_b,
+            position_closed,
+            timestamp: tracker.last_update,
+        });
+
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // Bypasses the withdrawal queue, so only usable while paused
+    #[account(
+        seeds = [b"config"],
+        bump = vault_config.bump,
+        constraint = vault_config.paused @ EmergencyWithdrawError::NotPaused
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == authority.key() @ EmergencyWithdrawError::InvalidOwner
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", authority.key().as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == authority.key() @ EmergencyWithdrawError::InvalidOwner
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    // Whirlpool accounts
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        mut,
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ EmergencyWithdrawError::WhirlpoolMismatch
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Position (validated by CPI)
+    #[account(mut)]
+    pub whirlpool_position: UncheckedAccount<'info>,
+
+    // LP NFT (decimals 0, supply 1, mint authority revoked by Whirlpool)
+    #[account(
+        mut,
+        constraint = position_mint.key() == position_tracker.lp_position_mint @ EmergencyWithdrawError::InvalidPositionMint,
+        constraint = position_mint.decimals == 0 @ EmergencyWithdrawError::InvalidPositionMint,
+        constraint = position_mint.supply == 1 @ EmergencyWithdrawError::InvalidPositionMint,
+        constraint = position_mint.mint_authority.is_none() @ EmergencyWithdrawError::InvalidPositionMint
+    )]
+    pub position_mint: Account<'info, Mint>,
+
+    /// CHECK: Position token account (owned by vault PDA)
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    // User token accounts to receive withdrawn tokens
+    #[account(
+        mut,
+        constraint = token_account_a.owner == authority.key() @ EmergencyWithdrawError::InvalidOwner,
+        constraint = token_account_a.mint == position_tracker.token_mint_a @ EmergencyWithdrawError::InvalidTokenMint
+    )]
+    pub token_account_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = token_account_b.owner == authority.key() @ EmergencyWithdrawError::InvalidOwner,
+        constraint = token_account_b.mint == position_tracker.token_mint_b @ EmergencyWithdrawError::InvalidTokenMint
+    )]
+    pub token_account_b: Account<'info, TokenAccount>,
+
+    // Pool vaults
+    /// CHECK: Token vault A
+    #[account(mut)]
+    pub token_vault_a: UncheckedAccount<'info>,
+
+    /// CHECK: Token vault B
+    #[account(mut)]
+    pub token_vault_b: UncheckedAccount<'info>,
+
+    // Tick arrays
+    /// CHECK: Tick array lower
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array upper
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum EmergencyWithdrawError {
+    #[msg("Invalid vault owner")]
+    InvalidOwner,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Position mint is not the tracked Whirlpool LP NFT")]
+    InvalidPositionMint,
+    #[msg("Token account mint does not match the pool's token mint")]
+    InvalidTokenMint,
+    #[msg("Emergency withdrawal is only available while the vault is paused")]
+    NotPaused,
+}
+
+#[event]
+pub struct EmergencyWithdrawal {
+    pub user: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity_withdrawn: u128,
+    pub token_a_received: u64,
+    pub token_b_received: u64,
+    pub position_closed: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,187 @@
+//! Encrypt Collected Profits - Second phase of a split profit collection
+//!
+//! This instruction:
+//! 1. Reads the cleartext profits left pending by `collect_all_profits(encrypt = false)`
+//! 2. Folds each non-zero amount into its encrypted total via Inco
+//! 3. Clears the pending amounts
+//!
+//! Splitting collection from encryption keeps each transaction under the
+//! compute budget on pools with three active rewards.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::INCO_LIGHTNING_ID;
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+use super::set_delegate::{DelegateActed, DelegatedAction};
+
+/// Encrypt all pending profits on the tracker
+pub fn handler(ctx: Context<EncryptCollectedProfits>) -> Result<()> {
+    // Step 0: Check not paused + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    let instruction = crate::instruction::EncryptCollectedProfits::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+        if accounts.position_tracker.is_delegate_action(&accounts.authority.key()) {
+            emit!(DelegateActed {
+                position_tracker: accounts.position_tracker.key(),
+                owner: accounts.position_tracker.user,
+                delegate: accounts.authority.key(),
+                action: DelegatedAction::EncryptProfits,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        require!(
+            accounts.position_tracker.has_pending_profits(),
+            EncryptError::NothingToEncrypt
+        );
+
+        let inco_discriminators = IncoDiscriminators::load(&accounts.inco_config)?;
+        let amounts = encrypt_pending_profits(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            &mut accounts.position_tracker,
+            0, // explicit flush: encrypt everything pending
+        )?;
+
+        let tracker = &mut accounts.position_tracker;
+        tracker.last_update = Clock::get()?.unix_timestamp;
+
+        emit!(ProfitsEncrypted {
+            position: tracker.lp_position_mint,
+            fee_a: amounts[0],
+            fee_b: amounts[1],
+            reward_0: amounts[2],
+            reward_1: amounts[3],
+            reward_2: amounts[4],
+            encrypted_realized_profit_a: tracker.encrypted_realized_profit_a,
+            encrypted_realized_profit_b: tracker.encrypted_realized_profit_b,
+            encrypted_reward_0: tracker.encrypted_reward_0,
+            encrypted_reward_1: tracker.encrypted_reward_1,
+            encrypted_reward_2: tracker.encrypted_reward_2,
+            timestamp: tracker.last_update,
+        });
+
+        msg!("Pending profits encrypted!");
+        Ok(())
+    })
+}
+
+/// Fold the tracker's pending cleartext profits into its encrypted totals
+///
+/// Amounts below `min_amount` stay pending so dust can accumulate.
+/// Returns the amounts encrypted as `[fee_a, fee_b, reward_0, reward_1, reward_2]`.
+pub(crate) fn encrypt_pending_profits<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    tracker: &mut PositionTracker,
+    min_amount: u64,
+) -> Result<[u64; 5]> {
+    let pending = [
+        tracker.pending_fee_a,
+        tracker.pending_fee_b,
+        tracker.pending_rewards[0],
+        tracker.pending_rewards[1],
+        tracker.pending_rewards[2],
+    ];
+    let amounts = pending.map(|amount| if amount >= min_amount { amount } else { 0 });
+    let handles = [
+        &mut tracker.encrypted_realized_profit_a,
+        &mut tracker.encrypted_realized_profit_b,
+        &mut tracker.encrypted_reward_0,
+        &mut tracker.encrypted_reward_1,
+        &mut tracker.encrypted_reward_2,
+    ];
+
+    for (handle, amount) in handles.into_iter().zip(amounts) {
+        if amount == 0 {
+            continue;
+        }
+
+        // 1. Create encrypted handle from cleartext amount
+        let amount_handle = inco_lightning_cpi::cpi_new_euint128(
+            inco_program.clone(),
+            authority.clone(),
+            discriminators,
+            (amount as u128).to_le_bytes().to_vec(),
+            inco_lightning_cpi::amount_types::PLAINTEXT,
+        )?;
+
+        // 2. Add to accumulated total (the first credit becomes the total)
+        *handle = inco_lightning_cpi::cpi_accumulate(
+            inco_program.clone(),
+            authority.clone(),
+            discriminators,
+            *handle,
+            amount_handle,
+        )?;
+        msg!("Encrypted total updated. New handle: {}", *handle);
+    }
+
+    // Keep only what was below the threshold
+    tracker.pending_fee_a -= amounts[0];
+    tracker.pending_fee_b -= amounts[1];
+    for (pending, encrypted) in tracker.pending_rewards.iter_mut().zip(&amounts[2..]) {
+        *pending -= encrypted;
+    }
+    Ok(amounts)
+}
+
+#[derive(Accounts)]
+pub struct EncryptCollectedProfits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.is_owner_or_delegate(&authority.key()) @ EncryptError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum EncryptError {
+    #[msg("Unauthorized - not position owner or delegate")]
+    Unauthorized,
+    #[msg("No pending profits to encrypt")]
+    NothingToEncrypt,
+}
+
+#[event]
+pub struct ProfitsEncrypted {
+    pub position: Pubkey,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub reward_0: u64,
+    pub reward_1: u64,
+    pub reward_2: u64,
+    pub encrypted_realized_profit_a: u128,
+    pub encrypted_realized_profit_b: u128,
+    pub encrypted_reward_0: u128,
+    pub encrypted_reward_1: u128,
+    pub encrypted_reward_2: u128,
+    pub timestamp: i64,
+}
@@ -0,0 +1,93 @@
+//! Estimate Position Value - Read-only unrealized value at the current pool price
+//!
+//! This instruction:
+//! 1. Reads the Whirlpool sqrt_price and the position's liquidity
+//! 2. Converts liquidity to token A/B amounts over the tracked tick range
+//! 3. Returns the estimate via return data (no state is mutated)
+
+use anchor_lang::prelude::*;
+
+use crate::math;
+use crate::state::PositionTracker;
+use super::whirlpool_cpi;
+
+/// Estimate the token amounts the position would yield if fully withdrawn now
+pub fn handler(ctx: Context<EstimatePositionValue>) -> Result<PositionValueEstimate> {
+    let tracker = &ctx.accounts.position_tracker;
+
+    let whirlpool_info = ctx.accounts.whirlpool.to_account_info();
+    let position_info = ctx.accounts.whirlpool_position.to_account_info();
+
+    require!(
+        whirlpool_cpi::read_position_mint(&position_info)? == tracker.lp_position_mint,
+        EstimateError::PositionMismatch
+    );
+
+    let sqrt_price = whirlpool_cpi::read_whirlpool_sqrt_price(&whirlpool_info)?;
+    let liquidity = whirlpool_cpi::read_position_liquidity(&position_info)?;
+
+    let sqrt_price_lower = math::sqrt_price_from_tick_index(tracker.tick_lower)?;
+    let sqrt_price_upper = math::sqrt_price_from_tick_index(tracker.tick_upper)?;
+
+    // Round down: this is what a withdrawal would actually pay out
+    let (amount_a, amount_b) = math::get_amounts_from_liquidity(
+        liquidity,
+        sqrt_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+        false,
+    )?;
+
+    let in_range = sqrt_price >= sqrt_price_lower && sqrt_price < sqrt_price_upper;
+
+    msg!(
+        "Position value estimate: A={}, B={} (in range: {})",
+        amount_a, amount_b, in_range
+    );
+
+    Ok(PositionValueEstimate {
+        amount_a,
+        amount_b,
+        liquidity,
+        sqrt_price,
+        tick_lower: tracker.tick_lower,
+        tick_upper: tracker.tick_upper,
+        in_range,
+    })
+}
+
+#[derive(Accounts)]
+pub struct EstimatePositionValue<'info> {
+    #[account(
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Whirlpool (owner checked when read)
+    #[account(constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ EstimateError::WhirlpoolMismatch)]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Whirlpool position (owner and mint checked when read)
+    pub whirlpool_position: UncheckedAccount<'info>,
+}
+
+/// Value estimate returned to the caller
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PositionValueEstimate {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity: u128,
+    pub sqrt_price: u128,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub in_range: bool,
+}
+
+#[error_code]
+pub enum EstimateError {
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Whirlpool position does not match position tracker")]
+    PositionMismatch,
+}
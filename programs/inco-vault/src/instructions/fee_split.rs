@@ -0,0 +1,204 @@
+//! Fee Split - Pays vault-held fees out to several beneficiaries pro-rata
+//!
+//! `set_fee_split`:
+//! 1. Validates the beneficiary list (distinct wallets, shares sum to 10000 bps)
+//! 2. Creates or overwrites the vault's `FeeSplitConfig` PDA
+//!
+//! `distribute_fees`:
+//! 1. Refuses while the vault owes a settled performance fee, then reads the
+//!    balance to split from a vault-owned token account
+//! 2. Transfers each beneficiary its share (rounded down) to the token
+//!    account passed for it in `remaining_accounts`, in list order
+//! 3. Emits the amounts paid; rounding dust stays in the vault
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{Beneficiary, FeeSplitConfig, VaultConfig, VaultPDA};
+
+/// Create or replace the vault's fee split
+pub fn handler_set_fee_split(ctx: Context<SetFeeSplit>, beneficiaries: Vec<Beneficiary>) -> Result<()> {
+    FeeSplitConfig::validate_beneficiaries(&beneficiaries)?;
+
+    let config = &mut ctx.accounts.fee_split_config;
+    config.vault_pda = ctx.accounts.vault_pda.key();
+    config.beneficiaries = beneficiaries;
+    config.bump = ctx.bumps.fee_split_config;
+
+    msg!("Fee split set: {} beneficiaries", config.beneficiaries.len());
+
+    emit!(FeeSplitSet {
+        vault: config.vault_pda,
+        beneficiaries: config.beneficiaries.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Split a vault-owned token balance between the configured beneficiaries
+///
+/// `remaining_accounts` holds one writable token account per beneficiary,
+/// in the same order as the config. `amount` of `None` splits the full
+/// source balance.
+pub fn handler_distribute_fees<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
+    // Step 0: Check not paused + lock vault
+    ctx.accounts.vault_config.require_not_paused()?;
+    let remaining_accounts = ctx.remaining_accounts;
+    let instruction = crate::instruction::DistributeFees::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        // A settled performance fee is paid from these balances
+        accounts.vault_pda.require_no_fee_owed()?;
+        let split = &accounts.fee_split_config;
+        require!(
+            remaining_accounts.len() == split.beneficiaries.len(),
+            DistributeError::BeneficiaryAccountCount
+        );
+
+        let source = &accounts.source_token_account;
+        let total = amount.unwrap_or(source.amount);
+        require!(total > 0, DistributeError::NothingToDistribute);
+        require!(total <= source.amount, DistributeError::InsufficientBalance);
+
+        let owner = accounts.owner.key();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            owner.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let amounts = split.split(total);
+        for ((beneficiary, info), share) in
+            split.beneficiaries.iter().zip(remaining_accounts).zip(&amounts)
+        {
+            let destination = Account::<TokenAccount>::try_from(info)?;
+            require!(
+                destination.owner == beneficiary.wallet,
+                DistributeError::BeneficiaryAccountMismatch
+            );
+            require!(destination.mint == source.mint, DistributeError::MintMismatch);
+            if *share == 0 {
+                continue;
+            }
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: source.to_account_info(),
+                        to: info.clone(),
+                        authority: accounts.vault_pda.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                *share,
+            )?;
+            msg!("Paid {} to {}", share, beneficiary.wallet);
+        }
+
+        emit!(FeesDistributed {
+            vault: accounts.vault_pda.key(),
+            mint: source.mint,
+            total,
+            beneficiaries: split.beneficiaries.iter().map(|b| b.wallet).collect(),
+            amounts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == owner.key() @ DistributeError::Unauthorized
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = FeeSplitConfig::LEN,
+        seeds = [b"fee_split", vault_pda.key().as_ref()],
+        bump
+    )]
+    pub fee_split_config: Account<'info, FeeSplitConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == owner.key() @ DistributeError::Unauthorized
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        seeds = [b"fee_split", vault_pda.key().as_ref()],
+        bump = fee_split_config.bump
+    )]
+    pub fee_split_config: Account<'info, FeeSplitConfig>,
+
+    /// Vault-owned account holding the fees to split
+    #[account(
+        mut,
+        constraint = source_token_account.owner == vault_pda.key() @ DistributeError::InvalidSourceAccount
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum DistributeError {
+    #[msg("Unauthorized - not vault owner")]
+    Unauthorized,
+    #[msg("Source token account must be owned by the vault")]
+    InvalidSourceAccount,
+    #[msg("Pass exactly one token account per beneficiary")]
+    BeneficiaryAccountCount,
+    #[msg("Token account is not owned by the beneficiary at that index")]
+    BeneficiaryAccountMismatch,
+    #[msg("Beneficiary token account mint does not match the source")]
+    MintMismatch,
+    #[msg("Distribution exceeds the source balance")]
+    InsufficientBalance,
+    #[msg("Nothing to distribute")]
+    NothingToDistribute,
+}
+
+#[event]
+pub struct FeeSplitSet {
+    pub vault: Pubkey,
+    pub beneficiaries: Vec<Beneficiary>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub beneficiaries: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub timestamp: i64,
+}
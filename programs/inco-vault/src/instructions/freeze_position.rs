@@ -0,0 +1,79 @@
+//! Freeze Position - Owner's personal circuit breaker for one position
+//!
+//! While frozen, delegates and keepers (`crank_collect`, batch collection)
+//! are rejected by every instruction that acts on the position; the owner
+//! can still collect, rebalance and withdraw. Independent of the global
+//! admin pause.
+
+use anchor_lang::prelude::*;
+
+use crate::state::PositionTracker;
+
+/// Freeze the position against delegate and keeper actions
+pub fn handler_freeze(ctx: Context<SetPositionFrozen>) -> Result<()> {
+    set_frozen(ctx, true)
+}
+
+/// Lift the freeze
+pub fn handler_unfreeze(ctx: Context<SetPositionFrozen>) -> Result<()> {
+    set_frozen(ctx, false)
+}
+
+fn set_frozen(ctx: Context<SetPositionFrozen>, frozen: bool) -> Result<()> {
+    let tracker = &mut ctx.accounts.position_tracker;
+    require!(tracker.frozen != frozen, FreezeError::AlreadyInState);
+    tracker.frozen = frozen;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    if frozen {
+        emit!(PositionFrozen {
+            position_tracker: tracker.key(),
+            owner: tracker.user,
+            timestamp,
+        });
+        msg!("Position frozen");
+    } else {
+        emit!(PositionUnfrozen {
+            position_tracker: tracker.key(),
+            owner: tracker.user,
+            timestamp,
+        });
+        msg!("Position unfrozen");
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPositionFrozen<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ FreezeError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+#[error_code]
+pub enum FreezeError {
+    #[msg("Unauthorized - not position owner")]
+    Unauthorized,
+    #[msg("Position is already in the requested freeze state")]
+    AlreadyInState,
+}
+
+#[event]
+pub struct PositionFrozen {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionUnfrozen {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
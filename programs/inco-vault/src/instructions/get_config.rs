@@ -0,0 +1,90 @@
+//! Get Config - Read-only view of the global VaultConfig
+//!
+//! This instruction:
+//! 1. Copies every VaultConfig field into a `ConfigView`
+//! 2. Returns it via return data along with the program version
+//!
+//! SDKs should prefer this over decoding the raw account, whose layout
+//! grows as fields are appended.
+
+use anchor_lang::prelude::*;
+
+use crate::state::VaultConfig;
+
+/// Crate version of the deployed program
+pub const PROGRAM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Return the current vault configuration
+pub fn handler(ctx: Context<GetConfig>) -> Result<ConfigView> {
+    let config = &ctx.accounts.vault_config;
+
+    msg!("Config: admin={}, paused={}, version={}", config.admin, config.paused, PROGRAM_VERSION);
+
+    Ok(ConfigView {
+        program_version: PROGRAM_VERSION.to_string(),
+        admin: config.admin,
+        pending_admin: config.pending_admin,
+        paused: config.paused,
+        pause_timestamp: config.pause_timestamp,
+        default_max_slippage_bps: config.default_max_slippage_bps,
+        min_liquidity: config.min_liquidity,
+        max_liquidity: config.max_liquidity,
+        max_total_liquidity_per_vault: config.max_total_liquidity_per_vault,
+        performance_fee_bps: config.performance_fee_bps,
+        fee_recipient: config.fee_recipient,
+        pending_fee_recipient: config.pending_fee_recipient,
+        referrer_share_bps: config.referrer_share_bps,
+        absolute_max_slippage_bps: config.absolute_max_slippage_bps,
+        min_tick_width: config.min_tick_width,
+        max_tick_width: config.max_tick_width,
+        min_collect_threshold: config.min_collect_threshold,
+        safe_tick_lower: config.safe_tick_lower,
+        safe_tick_upper: config.safe_tick_upper,
+        abandonment_period: config.abandonment_period,
+        max_positions_per_vault: config.max_positions_per_vault,
+        deposits_permanently_disabled: config.deposits_permanently_disabled,
+        accepted_message_versions: config.accepted_message_versions,
+        max_handles_per_verify: config.max_handles_per_verify,
+        high_water_mark_fees: config.high_water_mark_fees,
+        large_withdrawal_threshold: config.large_withdrawal_threshold,
+        withdrawal_delay: config.withdrawal_delay,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetConfig<'info> {
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+}
+
+/// Configuration snapshot returned to the caller
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigView {
+    pub program_version: String,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub paused: bool,
+    pub pause_timestamp: i64,
+    pub default_max_slippage_bps: u16,
+    pub min_liquidity: u128,
+    pub max_liquidity: u128,
+    pub max_total_liquidity_per_vault: u128,
+    pub performance_fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub pending_fee_recipient: Pubkey,
+    pub referrer_share_bps: u16,
+    pub absolute_max_slippage_bps: u16,
+    pub min_tick_width: i32,
+    pub max_tick_width: i32,
+    pub min_collect_threshold: u64,
+    pub safe_tick_lower: i32,
+    pub safe_tick_upper: i32,
+    pub abandonment_period: i64,
+    pub max_positions_per_vault: u32,
+    pub deposits_permanently_disabled: bool,
+    pub accepted_message_versions: u8,
+    pub max_handles_per_verify: u8,
+    pub high_water_mark_fees: bool,
+    pub large_withdrawal_threshold: u128,
+    pub withdrawal_delay: i64,
+}
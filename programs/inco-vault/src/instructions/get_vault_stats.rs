@@ -0,0 +1,44 @@
+//! Get Vault Stats - Read-only aggregate analytics for a vault
+//!
+//! This instruction:
+//! 1. Reads the vault's position count, lifetime fees and total liquidity
+//! 2. Returns them via return data (no state is mutated)
+
+use anchor_lang::prelude::*;
+
+use crate::state::VaultPDA;
+
+/// Return the vault's aggregate stats
+pub fn handler(ctx: Context<GetVaultStats>) -> Result<VaultStats> {
+    let vault = &ctx.accounts.vault_pda;
+
+    msg!(
+        "Vault stats: positions={}, fees A={}, fees B={}, liquidity={}",
+        vault.position_count, vault.lifetime_fees_a, vault.lifetime_fees_b, vault.total_liquidity
+    );
+
+    Ok(VaultStats {
+        position_count: vault.position_count,
+        lifetime_fees_a: vault.lifetime_fees_a,
+        lifetime_fees_b: vault.lifetime_fees_b,
+        total_liquidity: vault.total_liquidity,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetVaultStats<'info> {
+    #[account(
+        seeds = [b"vault", vault_pda.owner.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+}
+
+/// Aggregate stats returned to the caller
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultStats {
+    pub position_count: u32,
+    pub lifetime_fees_a: u128,
+    pub lifetime_fees_b: u128,
+    pub total_liquidity: u128,
+}
@@ -0,0 +1,528 @@
+//! High-Water Mark - Performance fee charged only on new encrypted profit
+//!
+//! With `VaultConfig::high_water_mark_fees` on, collection takes no cleartext
+//! performance fee. Instead each position settles it in ciphertext:
+//!
+//! `request_hwm_check`:
+//! 1. Computes `e_gt(realized_profit, high_water_mark)` per token
+//! 2. Stores the ebool handles on the tracker and requests their decryption
+//!
+//! `settle_performance_fee`:
+//! 1. Requires an Inco attestation of the pending ebools in the transaction
+//! 2. For each token above its mark, accrues `(profit - mark) * fee_bps`
+//!    into the encrypted fee owed and moves the mark up to the profit
+//! 3. Leaves tokens at or below their mark untouched
+//! 4. Marks the fee owed, which blocks sweeping, distributing and
+//!    rebalancing the vault's idle balances until it is claimed
+//!
+//! `claim_performance_fee`:
+//! 1. Requires an earlier Inco attestation of the accrued fee handles
+//! 2. Reveals each through `decrypt_to_public` and checks it matches
+//! 3. Transfers `fee / 10_000` of each token from the vault to the fee
+//!    recipient and re-encrypts the sub-unit remainder as the fee still owed
+//! 4. Releases the vault's idle balances (the remainder is under one token)
+//!
+//! A token whose mark was never set charges on its whole realized profit and
+//! needs no comparison. Accrued fees are scaled by 10_000, since Inco has no
+//! encrypted division; the claim divides once the fee is revealed.
+//!
+//! Compute: the check costs 2 Inco CPIs per token (`e_gt` + decryption
+//! request); settling costs up to 3 per token charged (`e_sub`,
+//! `e_scalar_mul`, `e_add`). Build with `cu-debug` to measure both.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::{CovalidatorSet, PositionTracker, VaultConfig, VaultPDA};
+use super::create_position::INCO_LIGHTNING_ID;
+use super::collect_profits::transfer_fee;
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+use super::verify_decryption;
+
+/// Scale applied to accrued performance fees (`excess * fee_bps`)
+pub const FEE_SCALE: u128 = 10_000;
+
+/// Split a revealed, scaled fee into the token amount to pay and the
+/// scaled remainder that stays owed
+pub fn split_scaled_fee(scaled: u128) -> Result<(u64, u128)> {
+    let amount = u64::try_from(scaled / FEE_SCALE).map_err(|_| error!(HighWaterMarkError::FeeOverflow))?;
+    Ok((amount, scaled % FEE_SCALE))
+}
+
+/// What settling one token's performance fee needs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettleStep {
+    /// No profit credited since the last settlement
+    Skip,
+    /// No mark yet: the whole realized profit is above it
+    ChargeAll,
+    /// Mark set: charge only if the attested check says profit > mark
+    ChargeAboveMark,
+}
+
+impl SettleStep {
+    /// Plan a token's settlement from its profit and mark handles
+    pub fn plan(profit: u128, high_water_mark: u128) -> Self {
+        if profit == 0 || profit == high_water_mark {
+            SettleStep::Skip
+        } else if high_water_mark == 0 {
+            SettleStep::ChargeAll
+        } else {
+            SettleStep::ChargeAboveMark
+        }
+    }
+}
+
+/// Compare realized profit against the high-water mark and request decryption
+pub fn handler_request_check(ctx: Context<HighWaterMark>) -> Result<()> {
+    require!(
+        ctx.accounts.vault_config.high_water_mark_fees,
+        HighWaterMarkError::HighWaterMarkDisabled
+    );
+
+    let inco_discriminators = IncoDiscriminators::load(&ctx.accounts.inco_config)?;
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+    let tracker = &mut ctx.accounts.position_tracker;
+
+    let pairs = [
+        (tracker.encrypted_realized_profit_a, tracker.encrypted_high_water_mark_a),
+        (tracker.encrypted_realized_profit_b, tracker.encrypted_high_water_mark_b),
+    ];
+    let mut checks = [0u128; 2];
+    for (check, (profit, mark)) in checks.iter_mut().zip(pairs) {
+        if SettleStep::plan(profit, mark) != SettleStep::ChargeAboveMark {
+            continue;
+        }
+        *check = inco_lightning_cpi::cpi_e_gt(
+            inco_program.clone(),
+            authority.clone(),
+            &inco_discriminators,
+            profit,
+            mark,
+        )?;
+        inco_lightning_cpi::cpi_request_decryption(
+            inco_program.clone(),
+            authority.clone(),
+            &inco_discriminators,
+            *check,
+        )?;
+    }
+    require!(checks.iter().any(|c| *c != 0), HighWaterMarkError::NothingToCheck);
+    [tracker.hwm_check_a, tracker.hwm_check_b] = checks;
+
+    emit!(HighWaterMarkCheckRequested {
+        position: tracker.lp_position_mint,
+        hwm_check_a: tracker.hwm_check_a,
+        hwm_check_b: tracker.hwm_check_b,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("High-water mark check requested");
+    Ok(())
+}
+
+/// Accrue the encrypted performance fee above the high-water mark
+pub fn handler_settle(ctx: Context<HighWaterMark>) -> Result<()> {
+    let config = &ctx.accounts.vault_config;
+    require!(config.high_water_mark_fees, HighWaterMarkError::HighWaterMarkDisabled);
+    let fee_bps = config.performance_fee_bps_for(ctx.accounts.position_tracker.fee_bps_override) as u64;
+    let accepted_versions = config.accepted_message_versions;
+
+    let inco_discriminators = IncoDiscriminators::load(&ctx.accounts.inco_config)?;
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+    let tracker = &mut ctx.accounts.position_tracker;
+
+    let steps = [
+        SettleStep::plan(tracker.encrypted_realized_profit_a, tracker.encrypted_high_water_mark_a),
+        SettleStep::plan(tracker.encrypted_realized_profit_b, tracker.encrypted_high_water_mark_b),
+    ];
+    let checks = [tracker.hwm_check_a, tracker.hwm_check_b];
+
+    // Every comparison being relied on must be attested in this transaction
+    let mut above_mark = steps.map(|step| step == SettleStep::ChargeAll);
+    let pending: Vec<usize> = (0..2).filter(|i| steps[*i] == SettleStep::ChargeAboveMark).collect();
+    if !pending.is_empty() {
+        let mut handles = Vec::with_capacity(pending.len());
+        for i in &pending {
+            require!(checks[*i] != 0, HighWaterMarkError::CheckNotRequested);
+            handles.push(checks[*i].to_le_bytes());
+        }
+        let plaintexts = verify_decryption::find_transaction_attestation(
+            &ctx.accounts.instructions,
+            CovalidatorSet::load(&ctx.accounts.covalidator_set)?.as_ref(),
+            &handles,
+            accepted_versions,
+        )?
+        .ok_or(HighWaterMarkError::CheckNotAttested)?;
+        for (i, plaintext) in pending.iter().zip(plaintexts) {
+            above_mark[*i] = plaintext != [0u8; 16];
+        }
+    }
+
+    let totals = [
+        (
+            tracker.encrypted_realized_profit_a,
+            tracker.encrypted_high_water_mark_a,
+            tracker.encrypted_performance_fee_a,
+        ),
+        (
+            tracker.encrypted_realized_profit_b,
+            tracker.encrypted_high_water_mark_b,
+            tracker.encrypted_performance_fee_b,
+        ),
+    ];
+    let mut marks = [tracker.encrypted_high_water_mark_a, tracker.encrypted_high_water_mark_b];
+    let mut fees = [tracker.encrypted_performance_fee_a, tracker.encrypted_performance_fee_b];
+    for (i, (profit, mark, fee)) in totals.into_iter().enumerate() {
+        if !above_mark[i] {
+            continue;
+        }
+        if fee_bps > 0 {
+            let excess = if mark == 0 {
+                profit
+            } else {
+                inco_lightning_cpi::cpi_e_sub(
+                    inco_program.clone(),
+                    authority.clone(),
+                    &inco_discriminators,
+                    profit,
+                    mark,
+                )?
+            };
+            let charged = inco_lightning_cpi::cpi_e_scalar_mul(
+                inco_program.clone(),
+                authority.clone(),
+                &inco_discriminators,
+                excess,
+                fee_bps,
+            )?;
+            fees[i] = inco_lightning_cpi::cpi_accumulate(
+                inco_program.clone(),
+                authority.clone(),
+                &inco_discriminators,
+                fee,
+                charged,
+            )?;
+        }
+        marks[i] = profit;
+    }
+
+    [tracker.encrypted_high_water_mark_a, tracker.encrypted_high_water_mark_b] = marks;
+    [tracker.encrypted_performance_fee_a, tracker.encrypted_performance_fee_b] = fees;
+    // The fee is paid from idle vault balances; hold them until it is claimed
+    if fee_bps > 0 && above_mark.contains(&true) && !tracker.performance_fee_owed {
+        tracker.performance_fee_owed = true;
+        ctx.accounts.vault_pda.open_fee_claim()?;
+    }
+    tracker.hwm_check_a = 0;
+    tracker.hwm_check_b = 0;
+    tracker.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(PerformanceFeeSettled {
+        position: tracker.lp_position_mint,
+        charged_a: above_mark[0],
+        charged_b: above_mark[1],
+        encrypted_high_water_mark_a: tracker.encrypted_high_water_mark_a,
+        encrypted_high_water_mark_b: tracker.encrypted_high_water_mark_b,
+        encrypted_performance_fee_a: tracker.encrypted_performance_fee_a,
+        encrypted_performance_fee_b: tracker.encrypted_performance_fee_b,
+        timestamp: tracker.last_update,
+    });
+
+    msg!("Performance fee settled: A {}, B {}", above_mark[0], above_mark[1]);
+    Ok(())
+}
+
+/// Pay the accrued performance fee out of the vault to the fee recipient
+pub fn handler_claim(ctx: Context<ClaimPerformanceFee>) -> Result<()> {
+    let instruction = crate::instruction::ClaimPerformanceFee::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        let fee_handles = [
+            accounts.position_tracker.encrypted_performance_fee_a,
+            accounts.position_tracker.encrypted_performance_fee_b,
+        ];
+        let handles: Vec<[u8; 16]> = fee_handles
+            .into_iter()
+            .filter(|handle| *handle != 0)
+            .map(u128::to_le_bytes)
+            .collect();
+        require!(!handles.is_empty(), HighWaterMarkError::NothingToClaim);
+
+        // No cleartext without a covalidator signature over these exact handles
+        let attested = verify_decryption::preceding_attestation(
+            &accounts.instructions,
+            CovalidatorSet::load(&accounts.covalidator_set)?.as_ref(),
+            &handles,
+            accounts.vault_config.accepted_message_versions,
+        )?
+        .ok_or(HighWaterMarkError::FeeNotAttested)?;
+
+        let inco_discriminators = IncoDiscriminators::load(&accounts.inco_config)?;
+        let inco_program = accounts.inco_lightning_program.to_account_info();
+        let authority = accounts.authority.to_account_info();
+        let mut attested = attested.into_iter();
+        let mut paid = [0u64; 2];
+        let mut owed = fee_handles;
+        for (i, handle) in fee_handles.into_iter().enumerate() {
+            if handle == 0 {
+                continue;
+            }
+            let revealed = inco_lightning_cpi::cpi_decrypt_public(
+                inco_program.clone(),
+                authority.clone(),
+                &inco_discriminators,
+                handle,
+            )?;
+            let expected = attested.next().ok_or(HighWaterMarkError::FeeNotAttested)?;
+            require!(revealed.to_le_bytes() == expected, HighWaterMarkError::RevealMismatch);
+
+            let (amount, remainder) = split_scaled_fee(revealed)?;
+            paid[i] = amount;
+            owed[i] = if remainder == 0 {
+                0
+            } else {
+                inco_lightning_cpi::cpi_new_euint128(
+                    inco_program.clone(),
+                    authority.clone(),
+                    &inco_discriminators,
+                    remainder.to_le_bytes().to_vec(),
+                    inco_lightning_cpi::amount_types::PLAINTEXT,
+                )?
+            };
+        }
+
+        let user = accounts.position_tracker.user;
+        let vault_seeds = &[b"vault".as_ref(), user.as_ref(), &[accounts.vault_pda.bump]];
+        let signer_seeds = &[&vault_seeds[..]];
+        let vault_info = accounts.vault_pda.to_account_info();
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.vault_token_a,
+            Some(&accounts.fee_recipient_account_a),
+            vault_info.clone(),
+            signer_seeds,
+            paid[0],
+        )?;
+        transfer_fee(
+            &accounts.token_program,
+            &accounts.vault_token_b,
+            Some(&accounts.fee_recipient_account_b),
+            vault_info,
+            signer_seeds,
+            paid[1],
+        )?;
+
+        let tracker = &mut accounts.position_tracker;
+        [tracker.encrypted_performance_fee_a, tracker.encrypted_performance_fee_b] = owed;
+        if tracker.performance_fee_owed {
+            tracker.performance_fee_owed = false;
+            accounts.vault_pda.close_fee_claim();
+        }
+        tracker.last_update = Clock::get()?.unix_timestamp;
+
+        emit!(PerformanceFeeClaimed {
+            position: tracker.lp_position_mint,
+            fee_recipient: accounts.vault_config.fee_recipient,
+            fee_a: paid[0],
+            fee_b: paid[1],
+            encrypted_performance_fee_a: tracker.encrypted_performance_fee_a,
+            encrypted_performance_fee_b: tracker.encrypted_performance_fee_b,
+            timestamp: tracker.last_update,
+        });
+
+        msg!("Performance fee claimed: A {}, B {}", paid[0], paid[1]);
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct HighWaterMark<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.is_owner_or_delegate(&authority.key())
+            || authority.key() == vault_config.admin @ HighWaterMarkError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar for reading the Ed25519 attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPerformanceFee<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", position_tracker.user.as_ref()],
+        bump = vault_pda.bump
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.is_owner_or_delegate(&authority.key())
+            || authority.key() == vault_config.admin @ HighWaterMarkError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    // Vault accounts holding the collected fees
+    #[account(
+        mut,
+        constraint = vault_token_a.owner == vault_pda.key() @ HighWaterMarkError::InvalidTokenAccount,
+        constraint = vault_token_a.mint == position_tracker.token_mint_a @ HighWaterMarkError::InvalidTokenAccount
+    )]
+    pub vault_token_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_token_b.owner == vault_pda.key() @ HighWaterMarkError::InvalidTokenAccount,
+        constraint = vault_token_b.mint == position_tracker.token_mint_b @ HighWaterMarkError::InvalidTokenAccount
+    )]
+    pub vault_token_b: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_account_a.owner == vault_config.fee_recipient @ HighWaterMarkError::InvalidTokenAccount,
+        constraint = fee_recipient_account_a.mint == position_tracker.token_mint_a @ HighWaterMarkError::InvalidTokenAccount
+    )]
+    pub fee_recipient_account_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_recipient_account_b.owner == vault_config.fee_recipient @ HighWaterMarkError::InvalidTokenAccount,
+        constraint = fee_recipient_account_b.mint == position_tracker.token_mint_b @ HighWaterMarkError::InvalidTokenAccount
+    )]
+    pub fee_recipient_account_b: Account<'info, TokenAccount>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar for reading the Ed25519 attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum HighWaterMarkError {
+    #[msg("Unauthorized - not position owner, delegate or admin")]
+    Unauthorized,
+    #[msg("High-water-mark performance fees are not enabled")]
+    HighWaterMarkDisabled,
+    #[msg("No token has profit to compare against its high-water mark")]
+    NothingToCheck,
+    #[msg("Run request_hwm_check before settling against an existing mark")]
+    CheckNotRequested,
+    #[msg("No attestation in this transaction covers the high-water mark checks")]
+    CheckNotAttested,
+    #[msg("Position has no accrued performance fee to claim")]
+    NothingToClaim,
+    #[msg("No earlier attestation in this transaction covers the accrued fee handles")]
+    FeeNotAttested,
+    #[msg("Inco revealed a value that differs from the attested plaintext")]
+    RevealMismatch,
+    #[msg("Accrued performance fee does not fit in a token amount")]
+    FeeOverflow,
+    #[msg("Token account must hold the position's mint and belong to the vault or fee recipient")]
+    InvalidTokenAccount,
+}
+
+#[event]
+pub struct HighWaterMarkCheckRequested {
+    pub position: Pubkey,
+    pub hwm_check_a: u128,
+    pub hwm_check_b: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceFeeSettled {
+    pub position: Pubkey,
+    pub charged_a: bool,
+    pub charged_b: bool,
+    pub encrypted_high_water_mark_a: u128,
+    pub encrypted_high_water_mark_b: u128,
+    pub encrypted_performance_fee_a: u128,
+    pub encrypted_performance_fee_b: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceFeeClaimed {
+    pub position: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub encrypted_performance_fee_a: u128,
+    pub encrypted_performance_fee_b: u128,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_plan() {
+        assert_eq!(SettleStep::plan(0, 0), SettleStep::Skip);
+        assert_eq!(SettleStep::plan(7, 7), SettleStep::Skip);
+        assert_eq!(SettleStep::plan(7, 0), SettleStep::ChargeAll);
+        assert_eq!(SettleStep::plan(7, 5), SettleStep::ChargeAboveMark);
+    }
+
+    #[test]
+    fn test_split_scaled_fee() {
+        assert_eq!(split_scaled_fee(0).unwrap(), (0, 0));
+        assert_eq!(split_scaled_fee(9_999).unwrap(), (0, 9_999));
+        assert_eq!(split_scaled_fee(1_230_042).unwrap(), (123, 42));
+        assert_eq!(
+            split_scaled_fee((u64::MAX as u128 + 1) * FEE_SCALE).unwrap_err(),
+            error!(HighWaterMarkError::FeeOverflow)
+        );
+    }
+}
@@ -7,21 +7,108 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::invoke;
 
+use crate::state::IncoConfig;
 use super::create_position::INCO_LIGHTNING_ID;
 
 /// Inco Lightning instruction discriminators
 /// Calculated as sha256("global:<instruction_name>")[0..8]
 pub mod discriminators {
-    // sha256("global:new_euint128")[0..8]
-    // 0x6e 0xe3 0x05 0x22 0x76 0xe4 0x3d 0x8a
-    pub const NEW_EUINT128: [u8; 8] = [110, 227, 5, 34, 118, 228, 61, 138];
-    
-    // sha256("global:e_add")[0..8]
-    // 0x1f 0x07 0x86 0x06 0xc8 0x33 0xf4 0x82 (Approx, verifying logic)
-    // Actually, I should verify these. For now, using placeholders.
-    // Let's rely on the assumption that standard Anchor naming applies.
-    // e_add: "global:e_add"
-    pub const E_ADD: [u8; 8] = [31, 7, 134, 6, 200, 51, 244, 130]; 
+    /// new_euint128: sha256("global:new_euint128")[0..8]
+    pub const NEW_EUINT128: [u8; 8] = [145, 32, 102, 227, 47, 231, 10, 214];
+    /// e_add: sha256("global:e_add")[0..8]
+    pub const E_ADD: [u8; 8] = [20, 83, 18, 167, 120, 33, 209, 238];
+    /// e_sub: sha256("global:e_sub")[0..8]
+    pub const E_SUB: [u8; 8] = [187, 11, 145, 30, 50, 54, 58, 228];
+    /// e_gt: sha256("global:e_gt")[0..8]
+    pub const E_GT: [u8; 8] = [183, 111, 144, 160, 162, 85, 137, 211];
+    /// e_ge: sha256("global:e_ge")[0..8]
+    pub const E_GE: [u8; 8] = [129, 196, 119, 101, 81, 108, 69, 206];
+    /// e_scalar_mul: sha256("global:e_scalar_mul")[0..8]
+    pub const E_SCALAR_MUL: [u8; 8] = [230, 230, 105, 65, 3, 243, 203, 1];
+    /// decrypt: sha256("global:decrypt")[0..8]
+    pub const DECRYPT: [u8; 8] = [176, 147, 89, 19, 114, 205, 235, 237];
+    /// decrypt_to_public: sha256("global:decrypt_to_public")[0..8]
+    pub const DECRYPT_TO_PUBLIC: [u8; 8] = [167, 112, 94, 44, 201, 163, 244, 241];
+}
+
+/// Instruction data length for binary ops (e_add, e_sub, e_gt, e_ge):
+/// discriminator + two u128 handles
+pub const BINARY_OP_DATA_LEN: usize = 8 + 16 + 16;
+
+/// Instruction data length for e_scalar_mul: discriminator + handle + u64 scalar
+pub const E_SCALAR_MUL_DATA_LEN: usize = 8 + 16 + 8;
+
+/// Supported `amount_type` values for user-supplied `new_euint128` inputs
+pub mod amount_types {
+    /// Client-side Inco ciphertext (what the dashboard sends)
+    pub const CIPHERTEXT: u8 = 0;
+    /// Little-endian u128 cleartext
+    pub const PLAINTEXT: u8 = 1;
+}
+
+/// Byte bounds for an Inco euint128 ciphertext
+pub const MIN_CIPHERTEXT_LEN: usize = 16;
+pub const MAX_CIPHERTEXT_LEN: usize = 512;
+
+/// Discriminators used when building Inco CPIs
+///
+/// Resolved from `IncoConfig` once it is initialized, otherwise the
+/// compile-time constants above.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IncoDiscriminators {
+    pub new_euint128: [u8; 8],
+    pub e_add: [u8; 8],
+    pub e_sub: [u8; 8],
+    pub e_gt: [u8; 8],
+    pub e_ge: [u8; 8],
+    pub e_scalar_mul: [u8; 8],
+    pub decrypt: [u8; 8],
+    pub decrypt_to_public: [u8; 8],
+}
+
+impl Default for IncoDiscriminators {
+    fn default() -> Self {
+        Self {
+            new_euint128: discriminators::NEW_EUINT128,
+            e_add: discriminators::E_ADD,
+            e_sub: discriminators::E_SUB,
+            e_gt: discriminators::E_GT,
+            e_ge: discriminators::E_GE,
+            e_scalar_mul: discriminators::E_SCALAR_MUL,
+            decrypt: discriminators::DECRYPT,
+            decrypt_to_public: discriminators::DECRYPT_TO_PUBLIC,
+        }
+    }
+}
+
+impl IncoDiscriminators {
+    /// Use the admin-set overrides if present, falling back to the constants
+    pub fn resolve(config: Option<&IncoConfig>) -> Self {
+        config.map(|c| c.discriminators()).unwrap_or_default()
+    }
+
+    /// Resolve from the `IncoConfig` PDA account, initialized or not
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        let config = crate::state::load_if_initialized::<IncoConfig>(info)?;
+        Ok(Self::resolve(config.as_ref()))
+    }
+}
+
+/// Check a user-supplied `new_euint128` input before it reaches Inco
+///
+/// Rejects oversized inputs first (before anything copies them into CPI
+/// data), then unknown `amount_type`s, plaintexts that are not exactly a
+/// u128, and ciphertexts below the euint128 minimum.
+pub fn validate_encrypted_amount(encrypted_amount: &[u8], amount_type: u8) -> Result<()> {
+    let len = encrypted_amount.len();
+    require!(len <= MAX_CIPHERTEXT_LEN, ErrorCode::CiphertextTooLarge);
+    let valid = match amount_type {
+        amount_types::CIPHERTEXT => (MIN_CIPHERTEXT_LEN..=MAX_CIPHERTEXT_LEN).contains(&len),
+        amount_types::PLAINTEXT => len == 16,
+        _ => return err!(ErrorCode::UnsupportedAmountType),
+    };
+    require!(valid, ErrorCode::InvalidCiphertextLength);
+    Ok(())
 }
 
 /// CPI to new_euint128 on Inco Lightning
@@ -29,13 +116,14 @@ pub mod discriminators {
 pub fn cpi_new_euint128<'info>(
     inco_program: AccountInfo<'info>,
     authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
     encrypted_amount: Vec<u8>,
     amount_type: u8,
 ) -> Result<u128> {
     
     // Argument encoding
     let mut data = Vec::with_capacity(8 + 4 + encrypted_amount.len() + 1);
-    data.extend_from_slice(&discriminators::NEW_EUINT128);
+    data.extend_from_slice(&discriminators.new_euint128);
     // Borsh serialize arguments: (encrypted_amount: Vec<u8>, amount_type: u8)
     // Vec<u8> length (u32)
     data.extend_from_slice(&(encrypted_amount.len() as u32).to_le_bytes());
@@ -57,17 +145,7 @@ pub fn cpi_new_euint128<'info>(
         &[authority, inco_program],
     )?;
 
-    // Get return data
-    let (key, return_data) = anchor_lang::solana_program::program::get_return_data()
-        .ok_or(ErrorCode::NoReturnData)?;
-
-    require!(key == INCO_LIGHTNING_ID, ErrorCode::InvalidReturnDataKey);
-    require!(return_data.len() == 16, ErrorCode::InvalidReturnDataLength);
-
-    let handle_bytes: [u8; 16] = return_data.try_into().unwrap();
-    let handle = u128::from_le_bytes(handle_bytes);
-
-    Ok(handle)
+    take_returned_handle()
 }
 
 /// CPI to e_add on Inco Lightning
@@ -76,14 +154,170 @@ pub fn cpi_new_euint128<'info>(
 pub fn cpi_e_add<'info>(
     inco_program: AccountInfo<'info>,
     authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle_dest: u128,
+    handle_src: u128,
+) -> Result<u128> {
+    cpi_binary_op(inco_program, authority, discriminators.e_add, handle_dest, handle_src)
+}
+
+/// Add `handle_src` into a running encrypted total
+///
+/// A zero `handle_total` is the tracker's "never credited" sentinel, not an
+/// Inco handle, so the first credit adopts `handle_src` as the total instead
+/// of calling `e_add` on it.
+pub fn cpi_accumulate<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle_total: u128,
+    handle_src: u128,
+) -> Result<u128> {
+    if handle_total == 0 {
+        return Ok(handle_src);
+    }
+    cpi_e_add(inco_program, authority, discriminators, handle_total, handle_src)
+}
+
+/// CPI to e_sub on Inco Lightning
+/// Subtracts the value behind `handle_src` from `handle_dest`
+/// Returns new handle with result
+pub fn cpi_e_sub<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
     handle_dest: u128,
     handle_src: u128,
 ) -> Result<u128> {
-    // data: discriminator + handle_dest (u128) + handle_src (u128)
-    let mut data = Vec::with_capacity(8 + 16 + 16);
-    data.extend_from_slice(&discriminators::E_ADD);
-    data.extend_from_slice(&handle_dest.to_le_bytes());
-    data.extend_from_slice(&handle_src.to_le_bytes());
+    cpi_binary_op(inco_program, authority, discriminators.e_sub, handle_dest, handle_src)
+}
+
+/// CPI to e_gt on Inco Lightning
+/// Returns an encrypted boolean (ebool) handle for `handle_a > handle_b`
+///
+/// The result stays encrypted; decrypt it via `cpi_request_decryption` and
+/// check the attestation with `verify_decryption` before acting on it.
+pub fn cpi_e_gt<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle_a: u128,
+    handle_b: u128,
+) -> Result<u128> {
+    cpi_binary_op(inco_program, authority, discriminators.e_gt, handle_a, handle_b)
+}
+
+/// CPI to e_ge on Inco Lightning
+/// Returns an encrypted boolean (ebool) handle for `handle_a >= handle_b`
+pub fn cpi_e_ge<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle_a: u128,
+    handle_b: u128,
+) -> Result<u128> {
+    cpi_binary_op(inco_program, authority, discriminators.e_ge, handle_a, handle_b)
+}
+
+/// CPI to e_scalar_mul on Inco Lightning
+/// Multiplies the encrypted value behind `handle` by a cleartext `scalar`
+/// Returns new handle with result
+///
+/// Lets fee shares (e.g. `profit * fee_bps`) be tracked without decrypting
+/// the underlying profit.
+pub fn cpi_e_scalar_mul<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle: u128,
+    scalar: u64,
+) -> Result<u128> {
+    require!(scalar != 0, ErrorCode::ZeroScalar);
+
+    // data: discriminator + handle (u128) + scalar (u64)
+    let mut data = Vec::with_capacity(E_SCALAR_MUL_DATA_LEN);
+    data.extend_from_slice(&discriminators.e_scalar_mul);
+    data.extend_from_slice(&handle.to_le_bytes());
+    data.extend_from_slice(&scalar.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: INCO_LIGHTNING_ID,
+        accounts: vec![AccountMeta::new_readonly(*authority.key, true)],
+        data,
+    };
+
+    invoke(&ix, &[authority, inco_program])?;
+
+    take_returned_handle()
+}
+
+/// CPI to decrypt on Inco Lightning
+/// Requests decryption of `handle` (e.g. an ebool from `cpi_e_gt`); the
+/// covalidator answers off-chain with an Ed25519 attestation
+pub fn cpi_request_decryption<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle: u128,
+) -> Result<()> {
+    // data: discriminator + handle (u128)
+    let mut data = Vec::with_capacity(8 + 16);
+    data.extend_from_slice(&discriminators.decrypt);
+    data.extend_from_slice(&handle.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: INCO_LIGHTNING_ID,
+        accounts: vec![AccountMeta::new_readonly(*authority.key, true)],
+        data,
+    };
+
+    invoke(&ix, &[authority, inco_program])?;
+    Ok(())
+}
+
+/// CPI to decrypt_to_public on Inco Lightning
+/// Reveals the value behind `handle` and returns it as a cleartext u128
+///
+/// Inco only reveals what has been attested, so callers must first require
+/// a covalidator attestation of `handle` in the transaction (see
+/// `verify_decryption::preceding_attestation`) and compare the result to it.
+pub fn cpi_decrypt_public<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminators: &IncoDiscriminators,
+    handle: u128,
+) -> Result<u128> {
+    // data: discriminator + handle (u128)
+    let mut data = Vec::with_capacity(8 + 16);
+    data.extend_from_slice(&discriminators.decrypt_to_public);
+    data.extend_from_slice(&handle.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: INCO_LIGHTNING_ID,
+        accounts: vec![AccountMeta::new_readonly(*authority.key, true)],
+        data,
+    };
+
+    invoke(&ix, &[authority, inco_program])?;
+
+    let (key, return_data) = anchor_lang::solana_program::program::get_return_data()
+        .ok_or(ErrorCode::NoReturnData)?;
+    decode_u128(&key, &return_data)
+}
+
+/// Invoke a two-handle Inco op and return the result handle
+fn cpi_binary_op<'info>(
+    inco_program: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    discriminator: [u8; 8],
+    lhs: u128,
+    rhs: u128,
+) -> Result<u128> {
+    // data: discriminator + lhs (u128) + rhs (u128)
+    let mut data = Vec::with_capacity(BINARY_OP_DATA_LEN);
+    data.extend_from_slice(&discriminator);
+    data.extend_from_slice(&lhs.to_le_bytes());
+    data.extend_from_slice(&rhs.to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(*authority.key, true),
@@ -100,19 +334,43 @@ pub fn cpi_e_add<'info>(
         &[authority, inco_program],
     )?;
 
-    // Get return data
+    take_returned_handle()
+}
+
+/// Read the handle Inco just returned
+///
+/// Must be called directly after the `invoke`, with no other CPI in between.
+/// The runtime clears return data when an instruction is invoked, so data
+/// from an earlier instruction cannot leak in; if Inco itself ends with a
+/// nested CPI that sets return data, the key check in `decode_handle`
+/// rejects it rather than accepting a foreign handle. Reading from an
+/// output account is therefore not needed.
+fn take_returned_handle() -> Result<u128> {
     let (key, return_data) = anchor_lang::solana_program::program::get_return_data()
         .ok_or(ErrorCode::NoReturnData)?;
 
-    require!(key == INCO_LIGHTNING_ID, ErrorCode::InvalidReturnDataKey);
-    require!(return_data.len() == 16, ErrorCode::InvalidReturnDataLength);
+    decode_handle(&key, &return_data)
+}
 
-    let handle_bytes: [u8; 16] = return_data.try_into().unwrap();
-    let handle = u128::from_le_bytes(handle_bytes);
+/// Decode an Inco return-data blob into a handle
+/// A zero handle is rejected: Inco never allocates it, so it signals a failed op
+fn decode_handle(key: &Pubkey, return_data: &[u8]) -> Result<u128> {
+    let handle = decode_u128(key, return_data)?;
+    require!(handle != 0, ErrorCode::ZeroHandle);
 
     Ok(handle)
 }
 
+/// Decode a little-endian u128 that Inco returned (a handle or a plaintext)
+fn decode_u128(key: &Pubkey, return_data: &[u8]) -> Result<u128> {
+    require!(*key == INCO_LIGHTNING_ID, ErrorCode::InvalidReturnDataKey);
+
+    let bytes: [u8; 16] = return_data
+        .try_into()
+        .map_err(|_| error!(ErrorCode::InvalidReturnDataLength))?;
+    Ok(u128::from_le_bytes(bytes))
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("No return data from Inco CPI")]
@@ -121,4 +379,144 @@ pub enum ErrorCode {
     InvalidReturnDataKey,
     #[msg("Invalid return data length")]
     InvalidReturnDataLength,
+    #[msg("Inco returned a zero handle")]
+    ZeroHandle,
+    #[msg("Encrypted amount has the wrong length for its amount type")]
+    InvalidCiphertextLength,
+    #[msg("Unsupported amount type")]
+    UnsupportedAmountType,
+    #[msg("Scalar multiplier must be non-zero")]
+    ZeroScalar,
+    #[msg("Encrypted amount exceeds the maximum ciphertext size")]
+    CiphertextTooLarge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use crate::instructions::create_position::WHIRLPOOL_PROGRAM_ID;
+
+    fn anchor_discriminator(name: &str) -> [u8; 8] {
+        let digest = hash(format!("global:{}", name).as_bytes());
+        digest.to_bytes()[..8].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_new_euint128_discriminator() {
+        assert_eq!(discriminators::NEW_EUINT128, anchor_discriminator("new_euint128"));
+    }
+
+    #[test]
+    fn test_e_add_discriminator() {
+        assert_eq!(discriminators::E_ADD, anchor_discriminator("e_add"));
+    }
+
+    #[test]
+    fn test_e_sub_discriminator() {
+        assert_eq!(discriminators::E_SUB, anchor_discriminator("e_sub"));
+    }
+
+    #[test]
+    fn test_comparison_discriminators() {
+        assert_eq!(discriminators::E_GT, anchor_discriminator("e_gt"));
+        assert_eq!(discriminators::E_GE, anchor_discriminator("e_ge"));
+    }
+
+    #[test]
+    fn test_e_scalar_mul_discriminator() {
+        assert_eq!(discriminators::E_SCALAR_MUL, anchor_discriminator("e_scalar_mul"));
+    }
+
+    #[test]
+    fn test_decrypt_discriminator() {
+        assert_eq!(discriminators::DECRYPT, anchor_discriminator("decrypt"));
+    }
+
+    #[test]
+    fn test_decrypt_to_public_discriminator() {
+        assert_eq!(discriminators::DECRYPT_TO_PUBLIC, anchor_discriminator("decrypt_to_public"));
+    }
+
+    #[test]
+    fn test_decode_u128_allows_zero_plaintext() {
+        assert_eq!(decode_u128(&INCO_LIGHTNING_ID, &[0u8; 16]).unwrap(), 0);
+        assert_eq!(
+            decode_u128(&Pubkey::new_unique(), &[0u8; 16]).unwrap_err(),
+            error!(ErrorCode::InvalidReturnDataKey)
+        );
+    }
+
+    #[test]
+    fn test_decode_handle_valid() {
+        let handle = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+        assert_eq!(decode_handle(&INCO_LIGHTNING_ID, &handle.to_le_bytes()).unwrap(), handle);
+    }
+
+    #[test]
+    fn test_decode_handle_wrong_length() {
+        for data in [&[][..], &[1u8; 15][..], &[1u8; 17][..]] {
+            assert_eq!(
+                decode_handle(&INCO_LIGHTNING_ID, data).unwrap_err(),
+                error!(ErrorCode::InvalidReturnDataLength)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_handle_zero() {
+        assert_eq!(
+            decode_handle(&INCO_LIGHTNING_ID, &[0u8; 16]).unwrap_err(),
+            error!(ErrorCode::ZeroHandle)
+        );
+    }
+
+    #[test]
+    fn test_decode_handle_wrong_program() {
+        assert_eq!(
+            decode_handle(&Pubkey::new_unique(), &[0xab; 16]).unwrap_err(),
+            error!(ErrorCode::InvalidReturnDataKey)
+        );
+    }
+
+    #[test]
+    fn test_decode_handle_rejects_stale_return_data() {
+        // Well-formed handle-sized data left behind by another program
+        // (e.g. a prior Whirlpool CPI or a nested call inside Inco)
+        let stale = 42u128.to_le_bytes();
+        for key in [WHIRLPOOL_PROGRAM_ID, crate::ID, Pubkey::default()] {
+            assert_eq!(
+                decode_handle(&key, &stale).unwrap_err(),
+                error!(ErrorCode::InvalidReturnDataKey)
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_encrypted_amount() {
+        assert!(validate_encrypted_amount(&[7u8; 64], amount_types::CIPHERTEXT).is_ok());
+        assert!(validate_encrypted_amount(&[7u8; 16], amount_types::PLAINTEXT).is_ok());
+        assert_eq!(
+            validate_encrypted_amount(&[], amount_types::CIPHERTEXT).unwrap_err(),
+            error!(ErrorCode::InvalidCiphertextLength)
+        );
+        assert_eq!(
+            validate_encrypted_amount(&[7u8; MAX_CIPHERTEXT_LEN + 1], amount_types::CIPHERTEXT).unwrap_err(),
+            error!(ErrorCode::CiphertextTooLarge)
+        );
+        assert!(validate_encrypted_amount(&[7u8; MAX_CIPHERTEXT_LEN], amount_types::CIPHERTEXT).is_ok());
+        // Size is checked before the type, so oversized input never gets further
+        assert_eq!(
+            validate_encrypted_amount(&[7u8; MAX_CIPHERTEXT_LEN + 1], 2).unwrap_err(),
+            error!(ErrorCode::CiphertextTooLarge)
+        );
+        assert_eq!(
+            validate_encrypted_amount(&[7u8; 8], amount_types::PLAINTEXT).unwrap_err(),
+            error!(ErrorCode::InvalidCiphertextLength)
+        );
+        assert_eq!(
+            validate_encrypted_amount(&[7u8; 16], 2).unwrap_err(),
+            error!(ErrorCode::UnsupportedAmountType)
+        );
+    }
 }
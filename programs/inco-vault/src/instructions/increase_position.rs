@@ -0,0 +1,298 @@
+//! Increase Position - Adds liquidity to an existing Whirlpool position
+//!
+//! This instruction:
+//! 1. Encrypts the added amounts via Inco CPI
+//! 2. Adds liquidity to the tracked position (transfers tokens to pool)
+//! 3. Folds the added amounts into the encrypted deposit handles (e_add)
+//!
+//! Applies the same liquidity-bounds, safe-band and slippage checks as
+//! `create_position_with_liquidity`, so a top-up keeps the encrypted
+//! principal in step with the position. The bounds apply to the position's
+//! total liquidity after the top-up, and the vault total grows by the
+//! liquidity the position actually gained.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+
+use crate::math;
+use crate::state::{PoolRiskParams, PositionTracker, VaultPDA, VaultConfig};
+use super::create_position::{INCO_LIGHTNING_ID, WHIRLPOOL_PROGRAM_ID};
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+use super::whirlpool_cpi;
+
+/// Add liquidity to an existing position and update its encrypted deposit
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<IncreasePosition>,
+    encrypted_amount_a: Vec<u8>,
+    encrypted_amount_b: Vec<u8>,
+    amount_type: u8,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+    max_slippage_bps: Option<u16>,
+) -> Result<()> {
+    // Step 0: Check vault not paused + validate the resulting position liquidity
+    require!(liquidity_amount > 0, IncreasePositionError::ZeroLiquidity);
+    ctx.accounts.vault_config.require_not_paused()?;
+    ctx.accounts.vault_config.require_deposits_enabled()?;
+    inco_lightning_cpi::validate_encrypted_amount(&encrypted_amount_a, amount_type)?;
+    inco_lightning_cpi::validate_encrypted_amount(&encrypted_amount_b, amount_type)?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+
+    // Bounds apply to the position as a whole, not just the top-up
+    let liquidity_before = whirlpool_cpi::read_position_liquidity(
+        &ctx.accounts.whirlpool_position.to_account_info(),
+    )?;
+    let liquidity_target = liquidity_before
+        .checked_add(liquidity_amount)
+        .ok_or(IncreasePositionError::Overflow)?;
+    let pool_risk = PoolRiskParams::load(&ctx.accounts.pool_risk_params)?;
+    ctx.accounts.vault_config.validate_liquidity(pool_risk.as_ref(), liquidity_target)?;
+
+    let instruction = crate::instruction::IncreasePosition::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        let pool_tick =
+            whirlpool_cpi::read_whirlpool_tick_current_index(&accounts.whirlpool.to_account_info())?;
+        accounts.vault_config.validate_pool_tick(pool_tick)?;
+
+        whirlpool_cpi::validate_tick_arrays(
+            &accounts.whirlpool.to_account_info(),
+            accounts.tick_array_lower.key,
+            accounts.tick_array_upper.key,
+            accounts.position_tracker.tick_lower,
+            accounts.position_tracker.tick_upper,
+        )?;
+
+        // Step 1: Encrypt the added amounts via Inco CPI
+        let inco_discriminators = IncoDiscriminators::load(&accounts.inco_config)?;
+
+        let added_a = inco_lightning_cpi::cpi_new_euint128(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            encrypted_amount_a,
+            amount_type,
+        )?;
+        let added_b = inco_lightning_cpi::cpi_new_euint128(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            encrypted_amount_b,
+            amount_type,
+        )?;
+
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.authority.key.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        // Step 2: CPI to Whirlpool: increase_liquidity
+        let slippage = accounts
+            .vault_config
+            .resolve_slippage(pool_risk.as_ref(), max_slippage_bps)?;
+        let max_a_with_slippage = math::amount_with_slippage_max(token_max_a, slippage)
+            .ok_or(IncreasePositionError::Overflow)?;
+        let max_b_with_slippage = math::amount_with_slippage_max(token_max_b, slippage)
+            .ok_or(IncreasePositionError::Overflow)?;
+
+        whirlpool_cpi::cpi_increase_liquidity(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_account_a.to_account_info(),
+            accounts.token_account_b.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.tick_array_lower.to_account_info(),
+            accounts.tick_array_upper.to_account_info(),
+            signer_seeds,
+            liquidity_amount,
+            max_a_with_slippage,
+            max_b_with_slippage,
+        )?;
+
+        // Step 2.5: Measure what the position actually gained and count only
+        // that toward the per-vault cap
+        let liquidity_after = whirlpool_cpi::read_position_liquidity(
+            &accounts.whirlpool_position.to_account_info(),
+        )?;
+        let liquidity_added = liquidity_after
+            .checked_sub(liquidity_before)
+            .ok_or(IncreasePositionError::InsufficientLiquidityMinted)?;
+        accounts
+            .vault_config
+            .validate_liquidity(pool_risk.as_ref(), liquidity_after)?;
+        let vault_cap = accounts.vault_config.max_total_liquidity_per_vault;
+        accounts.vault_pda.add_liquidity(liquidity_added, vault_cap)?;
+
+        msg!("Liquidity added: {} (max A: {}, max B: {})",
+             liquidity_added, max_a_with_slippage, max_b_with_slippage);
+
+        // Step 3: Fold the added amounts into the encrypted deposit
+        let deposit_a = inco_lightning_cpi::cpi_e_add(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            accounts.position_tracker.encrypted_deposit_a,
+            added_a,
+        )?;
+        let deposit_b = inco_lightning_cpi::cpi_e_add(
+            accounts.inco_lightning_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            &inco_discriminators,
+            accounts.position_tracker.encrypted_deposit_b,
+            added_b,
+        )?;
+
+        let tracker = &mut accounts.position_tracker;
+        tracker.record_deposit_increase(deposit_a, deposit_b)?;
+
+        emit!(PositionIncreased {
+            user: accounts.authority.key(),
+            position_mint: tracker.lp_position_mint,
+            liquidity_added,
+            encrypted_deposit_a: deposit_a,
+            encrypted_deposit_b: deposit_b,
+            timestamp: tracker.last_update,
+        });
+
+        msg!("Position increased successfully!");
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct IncreasePosition<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Box<Account<'info, VaultConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == authority.key() @ IncreasePositionError::InvalidOwner
+    )]
+    pub vault_pda: Box<Account<'info, VaultPDA>>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", authority.key().as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == authority.key() @ IncreasePositionError::InvalidOwner
+    )]
+    pub position_tracker: Box<Account<'info, PositionTracker>>,
+
+    // Whirlpool accounts
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        mut,
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ IncreasePositionError::WhirlpoolMismatch
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Position (validated by CPI)
+    #[account(mut)]
+    pub whirlpool_position: UncheckedAccount<'info>,
+
+    // LP NFT (decimals 0, supply 1, mint authority revoked by Whirlpool)
+    #[account(
+        constraint = position_mint.key() == position_tracker.lp_position_mint @ IncreasePositionError::InvalidPositionMint,
+        constraint = position_mint.decimals == 0 @ IncreasePositionError::InvalidPositionMint,
+        constraint = position_mint.supply == 1 @ IncreasePositionError::InvalidPositionMint,
+        constraint = position_mint.mint_authority.is_none() @ IncreasePositionError::InvalidPositionMint
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Position token account (owned by vault PDA)
+    pub position_token_account: UncheckedAccount<'info>,
+
+    // User token accounts for the deposit
+    #[account(
+        mut,
+        constraint = token_account_a.owner == authority.key() @ IncreasePositionError::InvalidOwner,
+        constraint = token_account_a.mint == position_tracker.token_mint_a @ IncreasePositionError::InvalidTokenMint
+    )]
+    pub token_account_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_account_b.owner == authority.key() @ IncreasePositionError::InvalidOwner,
+        constraint = token_account_b.mint == position_tracker.token_mint_b @ IncreasePositionError::InvalidTokenMint
+    )]
+    pub token_account_b: Box<Account<'info, TokenAccount>>,
+
+    // Pool vaults
+    /// CHECK: Pool vault A (validated by CPI)
+    #[account(mut)]
+    pub token_vault_a: UncheckedAccount<'info>,
+
+    /// CHECK: Pool vault B (validated by CPI)
+    #[account(mut)]
+    pub token_vault_b: UncheckedAccount<'info>,
+
+    // Tick arrays
+    /// CHECK: Tick array lower (validated by CPI)
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array upper (validated by CPI)
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    // Per-pool liquidity and slippage limits (always passed, so they can't be skipped)
+    /// CHECK: Per-pool limits PDA; read whenever initialized
+    #[account(seeds = [b"pool_risk_params", whirlpool.key().as_ref()], bump)]
+    pub pool_risk_params: UncheckedAccount<'info>,
+
+    // Programs
+    /// CHECK: Inco Lightning program
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum IncreasePositionError {
+    #[msg("Invalid vault or position owner")]
+    InvalidOwner,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Position liquidity fell during the increase")]
+    InsufficientLiquidityMinted,
+    #[msg("Position mint is not the tracked Whirlpool LP NFT")]
+    InvalidPositionMint,
+    #[msg("Liquidity amount must be non-zero")]
+    ZeroLiquidity,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Token account mint does not match the position's pool")]
+    InvalidTokenMint,
+}
+
+#[event]
+pub struct PositionIncreased {
+    pub user: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity_added: u128,
+    pub encrypted_deposit_a: u128,
+    pub encrypted_deposit_b: u128,
+    pub timestamp: i64,
+}
@@ -0,0 +1,84 @@
+//! Init Tick Arrays - Prepares a pool's tick arrays for a new position
+//!
+//! This instruction:
+//! 1. Checks the passed tick arrays are the ones covering the tick range
+//! 2. Initializes each one that does not exist yet via Whirlpool CPI
+//!
+//! Permissionless: tick arrays are shared pool infrastructure, so anyone can
+//! pay for them. Run it before `create_position_with_liquidity` when the
+//! range lands on arrays the pool has never used.
+
+use anchor_lang::prelude::*;
+
+use crate::math;
+use super::create_position::WHIRLPOOL_PROGRAM_ID;
+use super::whirlpool_cpi;
+
+/// Initialize the tick arrays covering `tick_lower_index`/`tick_upper_index`
+pub fn handler(
+    ctx: Context<InitTickArrays>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let whirlpool = ctx.accounts.whirlpool.to_account_info();
+    whirlpool_cpi::validate_tick_arrays(
+        &whirlpool,
+        ctx.accounts.tick_array_lower.key,
+        ctx.accounts.tick_array_upper.key,
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    let tick_spacing = whirlpool_cpi::read_whirlpool_tick_spacing(&whirlpool)?;
+    let mut initialized = 0u8;
+
+    for (tick_array, tick_index) in [
+        (&ctx.accounts.tick_array_lower, tick_lower_index),
+        (&ctx.accounts.tick_array_upper, tick_upper_index),
+    ] {
+        // Both ticks can fall in the same array; the second pass then sees it initialized
+        let tick_array = tick_array.to_account_info();
+        if whirlpool_cpi::is_tick_array_initialized(&tick_array) {
+            continue;
+        }
+
+        let start_tick_index = math::tick_array_start_index(tick_index, tick_spacing);
+        whirlpool_cpi::cpi_initialize_tick_array(
+            ctx.accounts.whirlpool_program.to_account_info(),
+            whirlpool.clone(),
+            ctx.accounts.funder.to_account_info(),
+            tick_array.clone(),
+            ctx.accounts.system_program.to_account_info(),
+            start_tick_index,
+        )?;
+        msg!("Tick array {} initialized at start index {}", tick_array.key, start_tick_index);
+        initialized += 1;
+    }
+
+    msg!("Initialized {} tick arrays", initialized);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitTickArrays<'info> {
+    /// Pays rent for any new tick arrays
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: Whirlpool (validated by CPI)
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array covering the lower tick (address checked in handler)
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Tick array covering the upper tick (address checked in handler)
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Whirlpool program
+    #[account(address = WHIRLPOOL_PROGRAM_ID)]
+    pub whirlpool_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,9 +1,13 @@
 //! Initialize instruction - Sets up VaultConfig and VaultPDA
 
 use anchor_lang::prelude::*;
+use crate::program::IncoVault;
 use crate::state::{VaultConfig, VaultPDA};
 
 /// Initialize the vault configuration
+///
+/// Only the program's upgrade authority (the deployer) may call this, so the
+/// config PDA cannot be claimed by whoever lands the first transaction.
 pub fn handler_init_config(ctx: Context<InitializeConfig>) -> Result<()> {
     let config = &mut ctx.accounts.vault_config;
     config.initialize(ctx.accounts.admin.key(), ctx.bumps.vault_config);
@@ -13,9 +17,14 @@ pub fn handler_init_config(ctx: Context<InitializeConfig>) -> Result<()> {
 }
 
 /// Initialize a user's vault PDA
-pub fn handler_init_vault(ctx: Context<InitializeVault>) -> Result<()> {
+pub fn handler_init_vault(ctx: Context<InitializeVault>, referrer: Option<Pubkey>) -> Result<()> {
+    require!(
+        referrer != Some(ctx.accounts.owner.key()),
+        InitializeError::SelfReferral
+    );
+
     let vault = &mut ctx.accounts.vault_pda;
-    vault.initialize(ctx.accounts.owner.key(), ctx.bumps.vault_pda);
+    vault.initialize(ctx.accounts.owner.key(), ctx.bumps.vault_pda, referrer);
     
     msg!("Vault PDA initialized for owner: {}", ctx.accounts.owner.key());
     Ok(())
@@ -35,6 +44,16 @@ pub struct InitializeConfig<'info> {
     )]
     pub vault_config: Account<'info, VaultConfig>,
     
+    // Deployer check: admin must be the program's upgrade authority
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, IncoVault>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ InitializeError::UnauthorizedDeployer
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -54,3 +73,11 @@ pub struct InitializeVault<'info> {
     
     pub system_program: Program<'info, System>,
 }
+
+#[error_code]
+pub enum InitializeError {
+    #[msg("Vault owner cannot be their own referrer")]
+    SelfReferral,
+    #[msg("Only the program upgrade authority can initialize the config")]
+    UnauthorizedDeployer,
+}
@@ -0,0 +1,161 @@
+//! Migrate Tracker - Upgrades a PositionTracker to the current layout
+//!
+//! This instruction:
+//! 1. Validates the raw tracker account (owner, discriminator, PDA, user)
+//! 2. Returns early if it already carries the current layout version
+//! 3. Tops up rent from the owner and reallocs to `PositionTracker::LEN`
+//! 4. Leaves appended fields at their zero defaults (status = Open,
+//!    no pending profits, no delegate, nothing compounded, no entry price, every reward routed to
+//!    `Track`, no label, no high-water mark or accrued performance fee, not
+//!    frozen, no collections numbered, nothing settled, the vault's fee rate,
+//!    no withdrawal queued, no attestations numbered, no reward swap pools,
+//!    no fee claim owed, no un-queued withdrawals) and stamps the version
+//! 5. Fills the pool token mints, and any unset reward mints, from the
+//!    tracker's whirlpool and derives `reward_count` from the reward mints
+//!
+//! The tracker is read as raw bytes because legacy accounts are too short to
+//! deserialize against the current struct.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::state::PositionTracker;
+use super::whirlpool_cpi;
+
+/// Byte offsets shared by every tracker layout (including the discriminator)
+const USER_OFFSET: usize = 8;
+const WHIRLPOOL_OFFSET: usize = 72;
+const LEGACY_BUMP_OFFSET: usize = PositionTracker::LEGACY_LEN - 1;
+
+/// Migrate a tracker in place (idempotent)
+pub fn handler(ctx: Context<MigrateTracker>) -> Result<()> {
+    let tracker_info = ctx.accounts.position_tracker.to_account_info();
+
+    let (user, whirlpool, bump, from_len, migrated) = {
+        let data = tracker_info.try_borrow_data()?;
+        require!(
+            data.len() >= PositionTracker::LEGACY_LEN,
+            MigrateError::InvalidTrackerAccount
+        );
+        require!(
+            &data[..8] == PositionTracker::DISCRIMINATOR,
+            MigrateError::InvalidTrackerAccount
+        );
+
+        let pubkey_at = |offset: usize| {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| MigrateError::InvalidTrackerAccount)
+        };
+        let user = pubkey_at(USER_OFFSET)?;
+        let whirlpool = pubkey_at(WHIRLPOOL_OFFSET)?;
+        let migrated = data.len() >= PositionTracker::LEN
+            && data[PositionTracker::VERSION_OFFSET] >= PositionTracker::CURRENT_VERSION;
+        (user, whirlpool, data[LEGACY_BUMP_OFFSET], data.len(), migrated)
+    };
+
+    require!(user == ctx.accounts.owner.key(), MigrateError::Unauthorized);
+    let expected_address = Pubkey::create_program_address(
+        &[b"tracker", user.as_ref(), whirlpool.as_ref(), &[bump]],
+        &crate::ID,
+    ).map_err(|_| MigrateError::InvalidTrackerAccount)?;
+    require!(
+        expected_address == tracker_info.key(),
+        MigrateError::InvalidTrackerAccount
+    );
+    require!(
+        whirlpool == ctx.accounts.whirlpool.key(),
+        MigrateError::WhirlpoolMismatch
+    );
+
+    if migrated {
+        msg!("Tracker already at version {}", PositionTracker::CURRENT_VERSION);
+        return Ok(());
+    }
+
+    // Owner pays the rent delta for the larger account
+    let rent_needed = Rent::get()?
+        .minimum_balance(PositionTracker::LEN)
+        .saturating_sub(tracker_info.lamports());
+    if rent_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: tracker_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    // New bytes are zeroed, which is the default for every appended field
+    tracker_info.resize(PositionTracker::LEN)?;
+    tracker_info.try_borrow_mut_data()?[PositionTracker::VERSION_OFFSET] =
+        PositionTracker::CURRENT_VERSION;
+
+    // Pool mints are immutable, so copying them once is enough
+    let pool = whirlpool_cpi::read_whirlpool_meta(&ctx.accounts.whirlpool.to_account_info())?;
+    {
+        let mut data = tracker_info.try_borrow_mut_data()?;
+        let mut tracker = PositionTracker::try_deserialize(&mut &data[..])?;
+        tracker.token_mint_a = pool.token_mint_a;
+        tracker.token_mint_b = pool.token_mint_b;
+        // Reward mints were appended after the legacy layout; keep any a
+        // previous version already recorded
+        if tracker.reward_mints == [Pubkey::default(); 3] {
+            tracker.reward_mints = pool.reward_mints;
+        }
+        // A zero count would skip every reward slot on collect
+        tracker.reward_count = whirlpool_cpi::reward_count_from_mints(&tracker.reward_mints);
+        tracker.try_serialize(&mut &mut data[..])?;
+    }
+
+    emit!(TrackerMigrated {
+        tracker: tracker_info.key(),
+        user,
+        from_len: from_len as u32,
+        to_len: PositionTracker::LEN as u32,
+        version: PositionTracker::CURRENT_VERSION,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Tracker migrated: {} -> {} bytes", from_len, PositionTracker::LEN);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateTracker<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Raw tracker (legacy layouts cannot deserialize); validated in handler
+    #[account(mut, owner = crate::ID)]
+    pub position_tracker: UncheckedAccount<'info>,
+
+    /// CHECK: Tracker's whirlpool (key checked in handler; owner checked when read)
+    pub whirlpool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum MigrateError {
+    #[msg("Unauthorized - not tracker owner")]
+    Unauthorized,
+    #[msg("Account is not a valid position tracker")]
+    InvalidTrackerAccount,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+}
+
+#[event]
+pub struct TrackerMigrated {
+    pub tracker: Pubkey,
+    pub user: Pubkey,
+    pub from_len: u32,
+    pub to_len: u32,
+    pub version: u8,
+    pub timestamp: i64,
+}
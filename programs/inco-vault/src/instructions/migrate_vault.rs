@@ -0,0 +1,188 @@
+//! Migrate Vault - Upgrades VaultConfig and VaultPDA to the current layout
+//!
+//! Both accounts only ever grew by appending fields, so an account is either
+//! one of its known earlier layouts or already current. Each handler:
+//! 1. Validates the raw account (PDA seeds, discriminator, length, signer)
+//! 2. Returns early if it already has the current size
+//! 3. Tops up rent from the signer and reallocs to `LEN`
+//! 4. Fills the appended fields: the config gets the same defaults as
+//!    `initialize_config`; every appended vault field defaults to zero
+//!    (no liquidity or fees recorded, no referrer, not locked, no fee
+//!    claims owed)
+//!
+//! The accounts are read as raw bytes because legacy accounts are too short
+//! to deserialize against the current structs.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::state::{VaultConfig, VaultPDA};
+
+/// Offset of the first field (admin / owner) in both layouts
+const AUTHORITY_OFFSET: usize = 8;
+
+/// Migrate the global config in place (idempotent; admin only)
+pub fn handler_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.vault_config.to_account_info();
+    let from_len = validate_layout(
+        &config_info,
+        VaultConfig::DISCRIMINATOR,
+        &[VaultConfig::LEGACY_LEN],
+        VaultConfig::LEN,
+        &ctx.accounts.admin.key(),
+    )?;
+    if from_len >= VaultConfig::LEN {
+        msg!("Config already at {} bytes", VaultConfig::LEN);
+        return Ok(());
+    }
+
+    grow(
+        &config_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program,
+        VaultConfig::LEN,
+    )?;
+    {
+        let mut data = config_info.try_borrow_mut_data()?;
+        let mut config = VaultConfig::try_deserialize(&mut &data[..])?;
+        config.set_appended_defaults();
+        config.try_serialize(&mut &mut data[..])?;
+    }
+
+    emit!(AccountMigrated {
+        account: config_info.key(),
+        from_len: from_len as u32,
+        to_len: VaultConfig::LEN as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Config migrated: {} -> {} bytes", from_len, VaultConfig::LEN);
+    Ok(())
+}
+
+/// Migrate the caller's vault PDA in place (idempotent)
+pub fn handler_vault(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault_pda.to_account_info();
+    let from_len = validate_layout(
+        &vault_info,
+        VaultPDA::DISCRIMINATOR,
+        &[VaultPDA::LEGACY_LEN, VaultPDA::PRE_FEE_CLAIM_LEN],
+        VaultPDA::LEN,
+        &ctx.accounts.owner.key(),
+    )?;
+    if from_len >= VaultPDA::LEN {
+        msg!("Vault already at {} bytes", VaultPDA::LEN);
+        return Ok(());
+    }
+
+    // New bytes are zeroed, which is the default for every appended field
+    grow(
+        &vault_info,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program,
+        VaultPDA::LEN,
+    )?;
+
+    emit!(AccountMigrated {
+        account: vault_info.key(),
+        from_len: from_len as u32,
+        to_len: VaultPDA::LEN as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Vault migrated: {} -> {} bytes", from_len, VaultPDA::LEN);
+    Ok(())
+}
+
+/// Check the raw account is a known earlier or current layout owned by
+/// `authority`, returning its length
+fn validate_layout(
+    info: &AccountInfo,
+    discriminator: &[u8],
+    earlier_lens: &[usize],
+    len: usize,
+    authority: &Pubkey,
+) -> Result<usize> {
+    let data = info.try_borrow_data()?;
+    require!(
+        earlier_lens.contains(&data.len()) || data.len() >= len,
+        MigrateVaultError::UnknownLayout
+    );
+    require!(
+        &data[..discriminator.len()] == discriminator,
+        MigrateVaultError::InvalidAccount
+    );
+    let stored = Pubkey::try_from(&data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32])
+        .map_err(|_| MigrateVaultError::InvalidAccount)?;
+    require!(stored == *authority, MigrateVaultError::Unauthorized);
+    Ok(data.len())
+}
+
+/// Top up rent from `payer` and zero-extend `info` to `new_len`
+fn grow<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if rent_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+    info.resize(new_len)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Raw config (legacy layout cannot deserialize); validated in handler
+    #[account(mut, owner = crate::ID, seeds = [b"config"], bump)]
+    pub vault_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Raw vault (legacy layout cannot deserialize); validated in handler
+    #[account(mut, owner = crate::ID, seeds = [b"vault", owner.key().as_ref()], bump)]
+    pub vault_pda: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum MigrateVaultError {
+    #[msg("Unauthorized - signer does not own this account")]
+    Unauthorized,
+    #[msg("Account is not a valid config or vault")]
+    InvalidAccount,
+    #[msg("Account length matches neither the legacy nor the current layout")]
+    UnknownLayout,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_len: u32,
+    pub to_len: u32,
+    pub timestamp: i64,
+}
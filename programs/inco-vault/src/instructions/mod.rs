@@ -1,5 +1,7 @@
 //! Instructions module - All instruction handlers
 
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
 pub mod create_position;
 pub mod collect_profits;
@@ -9,6 +11,35 @@ pub mod admin;
 pub mod whirlpool_cpi;
 pub mod inco_lightning_cpi;
 pub mod withdraw_position;
+pub mod estimate_position_value;
+pub mod encrypt_collected_profits;
+pub mod get_vault_stats;
+pub mod sweep_dust;
+pub mod close_vault;
+pub mod migrate_tracker;
+pub mod set_delegate;
+pub mod emergency_withdraw;
+pub mod get_config;
+pub mod collect_and_compound;
+pub mod sum_position_profits;
+pub mod crank_collect;
+pub mod collect_all_positions;
+pub mod admin_force_close;
+pub mod increase_position;
+pub mod init_tick_arrays;
+pub mod collect_and_attest;
+pub mod claim_rewards;
+pub mod set_reward_routes;
+pub mod compute_hint;
+pub mod fee_split;
+pub mod set_position_label;
+pub mod high_water_mark;
+pub mod freeze_position;
+pub mod settle_position;
+pub mod reconcile_position;
+pub mod withdrawal_queue;
+pub mod pool_risk_params;
+pub mod migrate_vault;
 
 pub use initialize::*;
 pub use create_position::*;
@@ -17,3 +48,31 @@ pub use rebalance::*;
 pub use verify_decryption::*;
 pub use admin::*;
 pub use withdraw_position::*;
+pub use estimate_position_value::*;
+pub use encrypt_collected_profits::*;
+pub use get_vault_stats::*;
+pub use sweep_dust::*;
+pub use close_vault::*;
+pub use migrate_tracker::*;
+pub use set_delegate::*;
+pub use emergency_withdraw::*;
+pub use get_config::*;
+pub use collect_and_compound::*;
+pub use sum_position_profits::*;
+pub use crank_collect::*;
+pub use collect_all_positions::*;
+pub use admin_force_close::*;
+pub use increase_position::*;
+pub use init_tick_arrays::*;
+pub use collect_and_attest::*;
+pub use claim_rewards::*;
+pub use set_reward_routes::*;
+pub use fee_split::*;
+pub use set_position_label::*;
+pub use high_water_mark::*;
+pub use freeze_position::*;
+pub use settle_position::*;
+pub use reconcile_position::*;
+pub use withdrawal_queue::*;
+pub use pool_risk_params::*;
+pub use migrate_vault::*;
@@ -0,0 +1,130 @@
+//! Pool Risk Params - Admin-managed per-pool liquidity and slippage limits
+//!
+//! `add_pool_risk_params` creates the limits for one Whirlpool and
+//! `update_pool_risk_params` changes any of them. Both require
+//! `min_liquidity < max_liquidity` and a default slippage within the global
+//! ceiling. `create_position`, `increase_position` and `rebalance_position`
+//! use a pool's limits when its account is passed.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PoolRiskParams, VaultConfig};
+use super::whirlpool_cpi;
+
+/// Create the risk limits for a Whirlpool
+pub fn handler_add(
+    ctx: Context<AddPoolRiskParams>,
+    min_liquidity: u128,
+    max_liquidity: u128,
+    default_max_slippage_bps: u16,
+) -> Result<()> {
+    let config = &ctx.accounts.vault_config;
+    require!(ctx.accounts.admin.key() == config.admin, PoolRiskParamsError::Unauthorized);
+    // Only real Whirlpools get limits
+    whirlpool_cpi::read_whirlpool_meta(&ctx.accounts.whirlpool.to_account_info())?;
+
+    let params = &mut ctx.accounts.pool_risk_params;
+    params.whirlpool = ctx.accounts.whirlpool.key();
+    params.min_liquidity = min_liquidity;
+    params.max_liquidity = max_liquidity;
+    params.default_max_slippage_bps = default_max_slippage_bps;
+    params.bump = ctx.bumps.pool_risk_params;
+    params.validate(config.absolute_max_slippage_bps)?;
+
+    emit_updated(ctx.accounts.admin.key(), params)?;
+    msg!("Pool risk params added for {}", params.whirlpool);
+    Ok(())
+}
+
+/// Change any of a Whirlpool's risk limits
+pub fn handler_update(
+    ctx: Context<UpdatePoolRiskParams>,
+    min_liquidity: Option<u128>,
+    max_liquidity: Option<u128>,
+    default_max_slippage_bps: Option<u16>,
+) -> Result<()> {
+    let config = &ctx.accounts.vault_config;
+    require!(ctx.accounts.admin.key() == config.admin, PoolRiskParamsError::Unauthorized);
+
+    let params = &mut ctx.accounts.pool_risk_params;
+    if let Some(min) = min_liquidity {
+        params.min_liquidity = min;
+    }
+    if let Some(max) = max_liquidity {
+        params.max_liquidity = max;
+    }
+    if let Some(slippage) = default_max_slippage_bps {
+        params.default_max_slippage_bps = slippage;
+    }
+    params.validate(config.absolute_max_slippage_bps)?;
+
+    emit_updated(ctx.accounts.admin.key(), params)?;
+    msg!("Pool risk params updated for {}", params.whirlpool);
+    Ok(())
+}
+
+fn emit_updated(admin: Pubkey, params: &PoolRiskParams) -> Result<()> {
+    emit!(PoolRiskParamsUpdated {
+        admin,
+        whirlpool: params.whirlpool,
+        min_liquidity: params.min_liquidity,
+        max_liquidity: params.max_liquidity,
+        default_max_slippage_bps: params.default_max_slippage_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddPoolRiskParams<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    /// CHECK: Whirlpool (owner and discriminator checked when read)
+    pub whirlpool: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolRiskParams::LEN,
+        seeds = [b"pool_risk_params", whirlpool.key().as_ref()],
+        bump
+    )]
+    pub pool_risk_params: Account<'info, PoolRiskParams>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolRiskParams<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_risk_params", pool_risk_params.whirlpool.as_ref()],
+        bump = pool_risk_params.bump
+    )]
+    pub pool_risk_params: Account<'info, PoolRiskParams>,
+}
+
+#[error_code]
+pub enum PoolRiskParamsError {
+    #[msg("Unauthorized - not admin")]
+    Unauthorized,
+}
+
+#[event]
+pub struct PoolRiskParamsUpdated {
+    pub admin: Pubkey,
+    pub whirlpool: Pubkey,
+    pub min_liquidity: u128,
+    pub max_liquidity: u128,
+    pub default_max_slippage_bps: u16,
+    pub timestamp: i64,
+}
@@ -1,191 +1,361 @@
 //! Rebalance - Close old position → Open new position at new tick range
 //!
 //! This instruction implements CORRECT rebalance semantics:
-//! 1. Collect all fees and rewards first
+//! 1. Require fees and rewards already collected (e.g. `collect_all_profits`
+//!    earlier in the same transaction)
 //! 2. Remove all liquidity from old position
 //! 3. Close old position (burns LP NFT)
 //! 4. Open new position at new tick range (mints new LP NFT)
 //! 5. Add liquidity to new position
 //! 6. Update tracker with new position reference
+//!
+//! The old position's mint and token account are checked against the
+//! tracker (vault-owned, holding exactly the tracked LP NFT) before any CPI.
+//! Whirlpool only closes a position that owes nothing, and the decrease
+//! settles fees accrued up to now, so step 1 is checked after step 2.
+//!
+//! Between steps 3 and 4 the freed tokens can optionally be routed through
+//! a Whirlpool `two_hop_swap` to rebalance the A/B ratio. Only the position
+//! owner may set a route: the pools, amount and minimum output are chosen by
+//! the caller, which a delegate must not control. The route must start and
+//! end in the vault's token accounts, and every intermediate account must
+//! also be vault-owned.
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
 
-use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use crate::math;
+use crate::state::{PoolRiskParams, PositionTracker, VaultPDA, VaultConfig};
 use super::create_position::WHIRLPOOL_PROGRAM_ID;
+use super::whirlpool_cpi::{self, OpenPositionBumps, TwoHopSwapAccounts, TwoHopSwapParams};
+use super::set_delegate::{DelegateActed, DelegatedAction};
+use super::compute_hint::{self, ComputeMeter};
 
 /// Rebalance position to new tick range
-pub fn handler(
-    ctx: Context<RebalancePosition>,
+///
+/// `max_price_age_secs` rejects pools whose state has not been touched
+/// recently. Whirlpool does not record a last-update slot, so freshness is
+/// measured in seconds against `reward_last_updated_timestamp`, which every
+/// swap refreshes.
+///
+/// `old_token_min_a`/`old_token_min_b` are the least the old position's
+/// exit may return (expected amounts minus slippage, computed by the
+/// client); a smaller return fails the whole rebalance.
+///
+/// When `route` is set (owner only), the freed tokens are swapped through
+/// two pools before re-depositing; the 18 swap accounts are passed as
+/// `remaining_accounts` in `TwoHopSwapAccounts` order.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RebalancePosition<'info>>,
     new_tick_lower: i32,
     new_tick_upper: i32,
+    old_token_min_a: u64,
+    old_token_min_b: u64,
     max_slippage_bps: Option<u16>,
+    max_price_age_secs: Option<u64>,
+    route: Option<TwoHopSwapParams>,
 ) -> Result<()> {
+    let meter = ComputeMeter::start();
+    let new_position_bump = ctx.bumps.new_whirlpool_position;
+
     // Step 0: Validate and lock
     ctx.accounts.vault_config.require_not_paused()?;
-    ctx.accounts.vault_pda.lock()?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+    let pool_risk = PoolRiskParams::load(&ctx.accounts.pool_risk_params)?;
+    let instruction = crate::instruction::RebalancePosition::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+        // Step 5 redeposits the vault's whole balance, which an owed fee is paid from
+        accounts.vault_pda.require_no_fee_owed()?;
+        if accounts.position_tracker.is_delegate_action(&accounts.authority.key()) {
+            emit!(DelegateActed {
+                position_tracker: accounts.position_tracker.key(),
+                owner: accounts.position_tracker.user,
+                delegate: accounts.authority.key(),
+                action: DelegatedAction::Rebalance,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
 
-    let vault_seeds = &[
-        b"vault".as_ref(),
-        ctx.accounts.position_tracker.user.as_ref(),
-        &[ctx.accounts.vault_pda.bump],
-    ];
-    let _signer_seeds = &[&vault_seeds[..]];
+        accounts.vault_config.validate_tick_width(new_tick_lower, new_tick_upper)?;
 
-    let slippage = max_slippage_bps.unwrap_or(ctx.accounts.vault_config.default_max_slippage_bps);
+        let whirlpool_info = accounts.whirlpool.to_account_info();
+        if let Some(max_age) = max_price_age_secs {
+            let last_updated = whirlpool_cpi::read_whirlpool_last_updated_timestamp(&whirlpool_info)?;
+            let now = Clock::get()?.unix_timestamp.max(0) as u64;
+            require!(
+                now.saturating_sub(last_updated) <= max_age,
+                RebalanceError::StalePrice
+            );
+        }
+        whirlpool_cpi::validate_tick_arrays(
+            &whirlpool_info,
+            accounts.old_tick_array_lower.key,
+            accounts.old_tick_array_upper.key,
+            accounts.position_tracker.tick_lower,
+            accounts.position_tracker.tick_upper,
+        )?;
+        whirlpool_cpi::validate_tick_arrays(
+            &whirlpool_info,
+            accounts.new_tick_array_lower.key,
+            accounts.new_tick_array_upper.key,
+            new_tick_lower,
+            new_tick_upper,
+        )?;
 
-    // ========== STEP 1: COLLECT ALL FEES AND REWARDS FIRST ==========
-    // (This should be done via separate CPI or inlined - simplified here)
-    msg!("Step 1: Collecting fees and rewards before rebalance...");
-    // CPI to collect_fees and collect_reward would go here
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            accounts.position_tracker.user.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
 
-    // ========== STEP 2: REMOVE ALL LIQUIDITY FROM OLD POSITION ==========
-    // Read current liquidity from position account
-    // Note: In production, deserialize WhirlpoolPosition to get liquidity
-    let current_liquidity: u128 = 0; // Would read from old_whirlpool_position
-    
-    if current_liquidity > 0 {
-        /*
-        let decrease_cpi = CpiContext::new_with_signer(
-            ctx.accounts.whirlpool_program.to_account_info(),
-            whirlpool::cpi::accounts::ModifyLiquidity {
-                whirlpool: ctx.accounts.whirlpool.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                position_authority: ctx.accounts.vault_pda.to_account_info(),
-                position: ctx.accounts.old_whirlpool_position.to_account_info(),
-                position_token_account: ctx.accounts.old_position_token_account.to_account_info(),
-                token_owner_account_a: ctx.accounts.vault_token_a.to_account_info(),
-                token_owner_account_b: ctx.accounts.vault_token_b.to_account_info(),
-                token_vault_a: ctx.accounts.token_vault_a.to_account_info(),
-                token_vault_b: ctx.accounts.token_vault_b.to_account_info(),
-                tick_array_lower: ctx.accounts.old_tick_array_lower.to_account_info(),
-                tick_array_upper: ctx.accounts.old_tick_array_upper.to_account_info(),
-            },
+        let slippage = accounts
+            .vault_config
+            .resolve_slippage(pool_risk.as_ref(), max_slippage_bps)?;
+
+        // ========== STEP 1: FEES AND REWARDS MUST ALREADY BE COLLECTED ==========
+        // Checked after the decrease below, which settles what is owed
+
+        compute_hint::checkpoint("rebalance: validated");
+
+        // ========== STEP 2: REMOVE ALL LIQUIDITY FROM OLD POSITION ==========
+        let current_liquidity = whirlpool_cpi::read_position_liquidity(
+            &accounts.old_whirlpool_position.to_account_info(),
+        )?;
+        
+        // Nothing to decrease on an empty position (Whirlpool rejects zero)
+        if current_liquidity > 0 {
+            let pre_balance_a = accounts.vault_token_a.amount;
+            let pre_balance_b = accounts.vault_token_b.amount;
+
+            // Whirlpool enforces the minimums; re-checked below on the measured delta
+            whirlpool_cpi::cpi_decrease_liquidity(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.whirlpool.to_account_info(),
+                accounts.token_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                accounts.old_whirlpool_position.to_account_info(),
+                accounts.old_position_token_account.to_account_info(),
+                accounts.vault_token_a.to_account_info(),
+                accounts.vault_token_b.to_account_info(),
+                accounts.token_vault_a.to_account_info(),
+                accounts.token_vault_b.to_account_info(),
+                accounts.old_tick_array_lower.to_account_info(),
+                accounts.old_tick_array_upper.to_account_info(),
+                signer_seeds,
+                current_liquidity,
+                old_token_min_a,
+                old_token_min_b,
+            )?;
+
+            accounts.vault_token_a.reload()?;
+            accounts.vault_token_b.reload()?;
+            let received_a = accounts.vault_token_a.amount.saturating_sub(pre_balance_a);
+            let received_b = accounts.vault_token_b.amount.saturating_sub(pre_balance_b);
+            require!(
+                received_a >= old_token_min_a && received_b >= old_token_min_b,
+                RebalanceError::DecreaseBelowMinimum
+            );
+
+            msg!(
+                "Step 2: Removed {} liquidity from old position ({} A, {} B)",
+                current_liquidity, received_a, received_b
+            );
+            accounts.vault_pda.remove_liquidity(current_liquidity);
+        }
+
+        compute_hint::checkpoint("rebalance: liquidity removed");
+
+        require!(
+            !whirlpool_cpi::position_has_owed_tokens(&accounts.old_whirlpool_position.to_account_info())?,
+            RebalanceError::UncollectedProfits
+        );
+
+        // ========== STEP 3: CLOSE OLD POSITION (BURNS LP NFT) ==========
+        // Rent goes to the caller, who funds the new position
+        whirlpool_cpi::cpi_close_position(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.authority.to_account_info(),
+            accounts.old_whirlpool_position.to_account_info(),
+            accounts.old_position_mint.to_account_info(),
+            accounts.old_position_token_account.to_account_info(),
+            accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        msg!("Step 3: Old position closed, LP NFT burned: {}", accounts.old_position_mint.key());
+
+        // ========== STEP 3.5: OPTIONAL ROUTING SWAP ==========
+        if let Some(route) = route {
+            let swap_accounts = TwoHopSwapAccounts::from_accounts(ctx.remaining_accounts)?;
+            validate_route(accounts, &swap_accounts, &route)?;
+            whirlpool_cpi::cpi_two_hop_swap(
+                accounts.whirlpool_program.to_account_info(),
+                accounts.token_program.to_account_info(),
+                accounts.vault_pda.to_account_info(),
+                swap_accounts,
+                signer_seeds,
+                &route,
+            )?;
+            msg!("Step 3.5: Routed {} through two-hop swap", route.amount);
+        }
+
+        compute_hint::checkpoint("rebalance: old position closed");
+
+        // ========== STEP 4: OPEN NEW POSITION AT NEW TICK RANGE ==========
+        whirlpool_cpi::cpi_open_position(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.authority.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.new_whirlpool_position.to_account_info(),
+            accounts.new_position_mint.to_account_info(),
+            accounts.new_position_token_account.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.associated_token_program.to_account_info(),
             signer_seeds,
+            OpenPositionBumps { position_bump: new_position_bump },
+            new_tick_lower,
+            new_tick_upper,
+        )?;
+        msg!("Step 4: New position opened at [{}, {}]", new_tick_lower, new_tick_upper);
+
+        compute_hint::checkpoint("rebalance: new position opened");
+
+        // ========== STEP 5: ADD LIQUIDITY TO NEW POSITION ==========
+        // Get vault token balances (tokens returned from decrease_liquidity)
+        accounts.vault_token_a.reload()?;
+        accounts.vault_token_b.reload()?;
+        let balance_a = accounts.vault_token_a.amount;
+        let balance_b = accounts.vault_token_b.amount;
+
+        // Liquidity the balances fund with the slippage margin held back, so
+        // the balances themselves can serve as hard maxima
+        let min_a = math::amount_with_slippage_min(balance_a, slippage);
+        let min_b = math::amount_with_slippage_min(balance_b, slippage);
+        let target_liquidity = math::get_liquidity_from_amounts(
+            whirlpool_cpi::read_whirlpool_sqrt_price(&whirlpool_info)?,
+            math::sqrt_price_from_tick_index(new_tick_lower)?,
+            math::sqrt_price_from_tick_index(new_tick_upper)?,
+            min_a,
+            min_b,
+        )?;
+        require!(target_liquidity > 0, RebalanceError::NothingToRedeposit);
+
+        whirlpool_cpi::cpi_increase_liquidity(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.new_whirlpool_position.to_account_info(),
+            accounts.new_position_token_account.to_account_info(),
+            accounts.vault_token_a.to_account_info(),
+            accounts.vault_token_b.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.new_tick_array_lower.to_account_info(),
+            accounts.new_tick_array_upper.to_account_info(),
+            signer_seeds,
+            target_liquidity,
+            balance_a,
+            balance_b,
+        )?;
+
+        // Count only what the new position actually holds
+        let new_liquidity = whirlpool_cpi::read_position_liquidity(
+            &accounts.new_whirlpool_position.to_account_info(),
+        )?;
+        msg!("Step 5: Added {} liquidity to new position", new_liquidity);
+        let vault_cap = accounts.vault_config.max_total_liquidity_per_vault;
+        accounts.vault_pda.add_liquidity(new_liquidity, vault_cap)?;
+
+        compute_hint::checkpoint("rebalance: liquidity added");
+
+        // ========== STEP 6: UPDATE TRACKER ==========
+        // Pool price the new position was entered at (after any route swap)
+        let pool = whirlpool_cpi::read_whirlpool_meta(&whirlpool_info)?;
+        let (entry_sqrt_price, entry_tick) = (pool.sqrt_price, pool.tick_current_index);
+
+        let tracker = &mut accounts.position_tracker;
+        let (old_tick_lower, old_tick_upper) = (tracker.tick_lower, tracker.tick_upper);
+        tracker.update_after_rebalance(
+            accounts.new_position_mint.key(),
+            new_tick_lower,
+            new_tick_upper,
+            entry_sqrt_price,
+            Clock::get()?.unix_timestamp,
         );
-        
-        // Remove ALL liquidity (min tokens = 0 since we want all out)
-        whirlpool::cpi::decrease_liquidity(decrease_cpi, current_liquidity, 0, 0)?;
-        */
-        msg!("Step 2: Removed {} liquidity from old position", current_liquidity);
-    }
 
-    // ========== STEP 3: CLOSE OLD POSITION (BURNS LP NFT) ==========
-    /*
-    let close_cpi = CpiContext::new_with_signer(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        whirlpool::cpi::accounts::ClosePosition {
-            position_authority: ctx.accounts.vault_pda.to_account_info(),
-            receiver: ctx.accounts.authority.to_account_info(), // Rent goes to user
-            position: ctx.accounts.old_whirlpool_position.to_account_info(),
-            position_mint: ctx.accounts.old_position_mint.to_account_info(),
-            position_token_account: ctx.accounts.old_position_token_account.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-        },
-        signer_seeds,
+        emit!(PositionRebalanced {
+            user: tracker.user,
+            old_position: accounts.old_position_mint.key(),
+            new_position: accounts.new_position_mint.key(),
+            old_tick_lower,
+            old_tick_upper,
+            new_tick_lower,
+            new_tick_upper,
+            liquidity: new_liquidity,
+            rebalance_count: tracker.rebalance_count,
+            entry_sqrt_price,
+            entry_tick,
+            timestamp: tracker.last_update,
+        });
+
+        msg!("Rebalance complete! Count: {}", tracker.rebalance_count);
+        Ok(())
+    })?;
+
+    meter.finish("rebalance_position");
+    Ok(())
+}
+
+/// Check the route only moves the vault's own tokens
+///
+/// Only the position owner may route; a delegate rebalances without a swap.
+/// The route must start and end in `vault_token_a`/`vault_token_b`, and
+/// every owner account (including the intermediate ones) must be a vault
+/// token account holding its pool's mint for that side.
+fn validate_route(
+    accounts: &RebalancePosition,
+    swap: &TwoHopSwapAccounts,
+    route: &TwoHopSwapParams,
+) -> Result<()> {
+    require!(
+        accounts.authority.key() == accounts.position_tracker.user,
+        RebalanceError::RouteRequiresOwner
     );
-    whirlpool::cpi::close_position(close_cpi)?;
-    */
-    msg!("Step 3: Old position closed, LP NFT burned: {}", ctx.accounts.old_position_mint.key());
-
-    // ========== STEP 4: OPEN NEW POSITION AT NEW TICK RANGE ==========
-    /*
-    let open_cpi = CpiContext::new_with_signer(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        whirlpool::cpi::accounts::OpenPosition {
-            funder: ctx.accounts.authority.to_account_info(),
-            owner: ctx.accounts.vault_pda.to_account_info(),
-            position: ctx.accounts.new_whirlpool_position.to_account_info(),
-            position_mint: ctx.accounts.new_position_mint.to_account_info(),
-            position_token_account: ctx.accounts.new_position_token_account.to_account_info(),
-            whirlpool: ctx.accounts.whirlpool.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-        },
-        signer_seeds,
+    let pool_one = whirlpool_cpi::read_whirlpool_meta(&swap.whirlpool_one)?;
+    let pool_two = whirlpool_cpi::read_whirlpool_meta(&swap.whirlpool_two)?;
+    let owner_accounts = [
+        (&swap.token_owner_account_one_a, pool_one.token_mint_a),
+        (&swap.token_owner_account_one_b, pool_one.token_mint_b),
+        (&swap.token_owner_account_two_a, pool_two.token_mint_a),
+        (&swap.token_owner_account_two_b, pool_two.token_mint_b),
+    ];
+    for (info, mint) in owner_accounts {
+        require!(info.owner == &anchor_spl::token::ID, RebalanceError::InvalidSwapAccount);
+        let token_account = TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])
+            .map_err(|_| RebalanceError::InvalidSwapAccount)?;
+        require!(
+            token_account.owner == accounts.vault_pda.key() && token_account.mint == mint,
+            RebalanceError::InvalidSwapAccount
+        );
+    }
+
+    let (input, output) = (
+        if route.a_to_b_one { &swap.token_owner_account_one_a } else { &swap.token_owner_account_one_b },
+        if route.a_to_b_two { &swap.token_owner_account_two_b } else { &swap.token_owner_account_two_a },
     );
-    whirlpool::cpi::open_position(
-        open_cpi,
-        whirlpool::state::OpenPositionBumps { position_bump: ctx.bumps.new_whirlpool_position },
-        new_tick_lower,
-        new_tick_upper,
-    )?;
-    */
-    msg!("Step 4: New position opened at [{}, {}]", new_tick_lower, new_tick_upper);
-
-    // ========== STEP 5: ADD LIQUIDITY TO NEW POSITION ==========
-    // Get vault token balances (tokens returned from decrease_liquidity)
-    ctx.accounts.vault_token_a.reload()?;
-    ctx.accounts.vault_token_b.reload()?;
-    let balance_a = ctx.accounts.vault_token_a.amount;
-    let balance_b = ctx.accounts.vault_token_b.amount;
-
-    // Calculate liquidity from token amounts
-    // In production: use whirlpool math to calculate liquidity from amounts
-    let new_liquidity: u128 = 0; // Would be calculated
-    
-    // Apply slippage
-    let _max_a = balance_a
-        .checked_mul(10000 + slippage as u64)
-        .ok_or(RebalanceError::Overflow)?
-        .checked_div(10000)
-        .ok_or(RebalanceError::Overflow)?;
-    let _max_b = balance_b
-        .checked_mul(10000 + slippage as u64)
-        .ok_or(RebalanceError::Overflow)?
-        .checked_div(10000)
-        .ok_or(RebalanceError::Overflow)?;
-
-    /*
-    let increase_cpi = CpiContext::new_with_signer(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        whirlpool::cpi::accounts::ModifyLiquidity {
-            whirlpool: ctx.accounts.whirlpool.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            position_authority: ctx.accounts.vault_pda.to_account_info(),
-            position: ctx.accounts.new_whirlpool_position.to_account_info(),
-            position_token_account: ctx.accounts.new_position_token_account.to_account_info(),
-            token_owner_account_a: ctx.accounts.vault_token_a.to_account_info(),
-            token_owner_account_b: ctx.accounts.vault_token_b.to_account_info(),
-            token_vault_a: ctx.accounts.token_vault_a.to_account_info(),
-            token_vault_b: ctx.accounts.token_vault_b.to_account_info(),
-            tick_array_lower: ctx.accounts.new_tick_array_lower.to_account_info(),
-            tick_array_upper: ctx.accounts.new_tick_array_upper.to_account_info(),
-        },
-        signer_seeds,
+    let vault_tokens = [accounts.vault_token_a.key(), accounts.vault_token_b.key()];
+    require!(
+        vault_tokens.contains(input.key) && vault_tokens.contains(output.key),
+        RebalanceError::InvalidSwapAccount
     );
-    whirlpool::cpi::increase_liquidity(increase_cpi, new_liquidity, max_a, max_b)?;
-    */
-    msg!("Step 5: Added {} liquidity to new position", new_liquidity);
-
-    // ========== STEP 6: UPDATE TRACKER ==========
-    let tracker = &mut ctx.accounts.position_tracker;
-    tracker.update_after_rebalance(
-        ctx.accounts.new_position_mint.key(),
-        new_tick_lower,
-        new_tick_upper,
-    )?;
-
-    // Unlock vault
-    ctx.accounts.vault_pda.unlock();
-
-    emit!(PositionRebalanced {
-        user: ctx.accounts.authority.key(),
-        old_position: ctx.accounts.old_position_mint.key(),
-        new_position: ctx.accounts.new_position_mint.key(),
-        old_tick_lower: tracker.tick_lower,
-        old_tick_upper: tracker.tick_upper,
-        new_tick_lower,
-        new_tick_upper,
-        liquidity: new_liquidity,
-        rebalance_count: tracker.rebalance_count,
-        timestamp: tracker.last_update,
-    });
-
-    msg!("Rebalance complete! Count: {}", tracker.rebalance_count);
     Ok(())
 }
 
@@ -208,23 +378,40 @@ pub struct RebalancePosition<'info> {
         mut,
         seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
         bump = position_tracker.bump,
-        constraint = position_tracker.user == authority.key() @ RebalanceError::Unauthorized
+        constraint = position_tracker.is_owner_or_delegate(&authority.key()) @ RebalanceError::Unauthorized
     )]
     pub position_tracker: Account<'info, PositionTracker>,
     
     // Whirlpool
-    /// CHECK: Whirlpool (validated by CPI)
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ RebalanceError::WhirlpoolMismatch
+    )]
     pub whirlpool: UncheckedAccount<'info>,
     
     // OLD position accounts (to be closed)
-    /// CHECK: Old position (validated by CPI)
-    #[account(mut)]
+    /// CHECK: Old position at its canonical PDA (closed by CPI)
+    #[account(
+        mut,
+        seeds = [b"position", old_position_mint.key().as_ref()],
+        bump,
+        seeds::program = WHIRLPOOL_PROGRAM_ID
+    )]
     pub old_whirlpool_position: UncheckedAccount<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        address = position_tracker.lp_position_mint @ RebalanceError::OldPositionMismatch
+    )]
     pub old_position_mint: Account<'info, Mint>,
     
-    #[account(mut)]
+    // Must hold the tracked LP NFT in the vault before it is burned
+    #[account(
+        mut,
+        constraint = old_position_token_account.owner == vault_pda.key() @ RebalanceError::OldPositionMismatch,
+        constraint = old_position_token_account.mint == position_tracker.lp_position_mint @ RebalanceError::OldPositionMismatch,
+        constraint = old_position_token_account.amount == 1 @ RebalanceError::OldPositionMismatch
+    )]
     pub old_position_token_account: Account<'info, TokenAccount>,
     
     /// CHECK: Old tick array lower
@@ -236,15 +423,32 @@ pub struct RebalancePosition<'info> {
     pub old_tick_array_upper: UncheckedAccount<'info>,
     
     // NEW position accounts (to be created)
-    /// CHECK: New position (created by CPI)
-    #[account(mut)]
+    /// CHECK: New position (created by CPI at its canonical PDA)
+    #[account(
+        mut,
+        seeds = [b"position", new_position_mint.key().as_ref()],
+        bump,
+        seeds::program = WHIRLPOOL_PROGRAM_ID
+    )]
     pub new_whirlpool_position: UncheckedAccount<'info>,
     
-    #[account(mut)]
-    pub new_position_mint: Account<'info, Mint>,
+    /// CHECK: New LP NFT mint keypair, initialized by Whirlpool's open_position
+    #[account(
+        mut,
+        signer,
+        constraint = new_position_mint.data_is_empty()
+            && new_position_mint.owner == &anchor_lang::system_program::ID
+            @ RebalanceError::InvalidNewPosition
+    )]
+    pub new_position_mint: UncheckedAccount<'info>,
     
-    #[account(mut)]
-    pub new_position_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Vault's new LP NFT associated token account, created by open_position
+    #[account(
+        mut,
+        address = get_associated_token_address(&vault_pda.key(), &new_position_mint.key())
+            @ RebalanceError::InvalidNewPosition
+    )]
+    pub new_position_token_account: UncheckedAccount<'info>,
     
     /// CHECK: New tick array lower
     #[account(mut)]
@@ -254,11 +458,20 @@ pub struct RebalancePosition<'info> {
     #[account(mut)]
     pub new_tick_array_upper: UncheckedAccount<'info>,
     
-    // Vault token accounts (hold tokens during rebalance)
-    #[account(mut)]
+    // Vault token accounts (hold tokens during rebalance); pinned to the
+    // vault and the pool mints because delegates may rebalance
+    #[account(
+        mut,
+        constraint = vault_token_a.owner == vault_pda.key() @ RebalanceError::InvalidTokenAccount,
+        constraint = vault_token_a.mint == position_tracker.token_mint_a @ RebalanceError::InvalidTokenAccount
+    )]
     pub vault_token_a: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault_token_b.owner == vault_pda.key() @ RebalanceError::InvalidTokenAccount,
+        constraint = vault_token_b.mint == position_tracker.token_mint_b @ RebalanceError::InvalidTokenAccount
+    )]
     pub vault_token_b: Account<'info, TokenAccount>,
     
     // Pool vaults
@@ -270,6 +483,11 @@ pub struct RebalancePosition<'info> {
     #[account(mut)]
     pub token_vault_b: UncheckedAccount<'info>,
     
+    // Per-pool slippage default (always passed, so it can't be skipped)
+    /// CHECK: Per-pool limits PDA; read whenever initialized
+    #[account(seeds = [b"pool_risk_params", whirlpool.key().as_ref()], bump)]
+    pub pool_risk_params: UncheckedAccount<'info>,
+    
     // Programs
     /// CHECK: Whirlpool program
     #[account(address = WHIRLPOOL_PROGRAM_ID)]
@@ -283,10 +501,30 @@ pub struct RebalancePosition<'info> {
 
 #[error_code]
 pub enum RebalanceError {
-    #[msg("Unauthorized - not position owner")]
+    #[msg("Unauthorized - not position owner or delegate")]
     Unauthorized,
     #[msg("Arithmetic overflow")]
     Overflow,
+    #[msg("Pool price is older than the allowed maximum age")]
+    StalePrice,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Old position mint or token account does not hold the tracked LP NFT in the vault")]
+    OldPositionMismatch,
+    #[msg("Removing the old position's liquidity returned less than the minimum")]
+    DecreaseBelowMinimum,
+    #[msg("Token account must be owned by the vault and hold the pool's mint")]
+    InvalidTokenAccount,
+    #[msg("Swap route token accounts must be vault-owned and hold each pool's mints")]
+    InvalidSwapAccount,
+    #[msg("New position mint must be a fresh keypair and its token account the vault's ATA")]
+    InvalidNewPosition,
+    #[msg("Freed balances fund no liquidity in the new range")]
+    NothingToRedeposit,
+    #[msg("Old position still owes fees or rewards; collect them earlier in the transaction")]
+    UncollectedProfits,
+    #[msg("Only the position owner may route the rebalance through a swap")]
+    RouteRequiresOwner,
 }
 
 #[event]
@@ -300,5 +538,7 @@ pub struct PositionRebalanced {
     pub new_tick_upper: i32,
     pub liquidity: u128,
     pub rebalance_count: u16,
+    pub entry_sqrt_price: u128,
+    pub entry_tick: i32,
     pub timestamp: i64,
 }
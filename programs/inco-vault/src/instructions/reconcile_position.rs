@@ -0,0 +1,112 @@
+//! Reconcile Position - Report drift between a tracker and its Whirlpool position
+//!
+//! This instruction:
+//! 1. Reads the position's actual liquidity and tick range
+//! 2. Compares them with what the tracker expects: the same tick range, and
+//!    liquidity only while the position is not closed
+//! 3. Emits `PositionReconciled` with the findings
+//!
+//! Nothing is mutated or corrected, so anyone may call it. Encrypted deposit
+//! amounts cannot be checked on-chain and are left to off-chain monitoring.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PositionStatus, PositionTracker};
+use super::whirlpool_cpi;
+
+/// Read the on-chain position and emit how it compares to the tracker
+pub fn handler(ctx: Context<ReconcilePosition>) -> Result<()> {
+    let tracker = &ctx.accounts.position_tracker;
+    let position_info = ctx.accounts.whirlpool_position.to_account_info();
+
+    require!(
+        whirlpool_cpi::read_position_mint(&position_info)? == tracker.lp_position_mint,
+        ReconcileError::PositionMismatch
+    );
+
+    let liquidity = whirlpool_cpi::read_position_liquidity(&position_info)?;
+    let (tick_lower, tick_upper) = whirlpool_cpi::read_position_tick_range(&position_info)?;
+
+    let tick_range_matches = tick_lower == tracker.tick_lower && tick_upper == tracker.tick_upper;
+    let liquidity_matches_status = liquidity_matches_status(tracker.status, liquidity);
+    if !tick_range_matches || !liquidity_matches_status {
+        msg!(
+            "Drift: ticks [{}, {}] vs tracked [{}, {}], liquidity {} while {:?}",
+            tick_lower, tick_upper, tracker.tick_lower, tracker.tick_upper, liquidity, tracker.status
+        );
+    }
+
+    emit!(PositionReconciled {
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        liquidity,
+        tick_lower,
+        tick_upper,
+        tracked_tick_lower: tracker.tick_lower,
+        tracked_tick_upper: tracker.tick_upper,
+        tick_range_matches,
+        liquidity_matches_status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Whether `liquidity` is what a position in `status` should hold
+///
+/// A closed position holds none; an open one may legitimately drain to zero
+/// through partial withdrawals, so only the closed case is strict.
+pub fn liquidity_matches_status(status: PositionStatus, liquidity: u128) -> bool {
+    match status {
+        PositionStatus::Closed => liquidity == 0,
+        PositionStatus::PartiallyWithdrawn => true,
+        PositionStatus::Open => liquidity > 0,
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReconcilePosition<'info> {
+    #[account(
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Whirlpool position (owner and mint checked when read)
+    pub whirlpool_position: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ReconcileError {
+    #[msg("Whirlpool position does not match position tracker")]
+    PositionMismatch,
+}
+
+#[event]
+pub struct PositionReconciled {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    /// Liquidity actually held by the Whirlpool position
+    pub liquidity: u128,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub tracked_tick_lower: i32,
+    pub tracked_tick_upper: i32,
+    pub tick_range_matches: bool,
+    pub liquidity_matches_status: bool,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidity_matches_status() {
+        assert!(liquidity_matches_status(PositionStatus::Open, 1));
+        assert!(!liquidity_matches_status(PositionStatus::Open, 0));
+        assert!(liquidity_matches_status(PositionStatus::PartiallyWithdrawn, 0));
+        assert!(liquidity_matches_status(PositionStatus::Closed, 0));
+        assert!(!liquidity_matches_status(PositionStatus::Closed, 1));
+    }
+}
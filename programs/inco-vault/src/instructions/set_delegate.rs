@@ -0,0 +1,79 @@
+//! Set Delegate - Lets a position owner authorize a keeper
+//!
+//! The delegate may call `collect_all_profits`, `collect_and_compound`,
+//! `encrypt_collected_profits` and `rebalance_position` for the owner. Deposits and withdrawals remain
+//! owner-only.
+
+use anchor_lang::prelude::*;
+
+use crate::state::PositionTracker;
+
+/// Set (or clear with `None`) the tracker's delegate
+pub fn handler(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let new_delegate = delegate.unwrap_or_default();
+    require!(new_delegate != owner, DelegateError::InvalidDelegate);
+
+    let tracker = &mut ctx.accounts.position_tracker;
+    let previous = tracker.delegate;
+    tracker.delegate = new_delegate;
+
+    emit!(DelegateSet {
+        position_tracker: tracker.key(),
+        owner,
+        previous_delegate: previous,
+        new_delegate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Delegate set to {}", new_delegate);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ DelegateError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+/// Instruction a delegate performed on the owner's behalf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegatedAction {
+    CollectProfits,
+    EncryptProfits,
+    Rebalance,
+    Compound,
+}
+
+#[error_code]
+pub enum DelegateError {
+    #[msg("Unauthorized - not position owner")]
+    Unauthorized,
+    #[msg("Owner cannot be their own delegate")]
+    InvalidDelegate,
+}
+
+#[event]
+pub struct DelegateSet {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub previous_delegate: Pubkey,
+    pub new_delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateActed {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub action: DelegatedAction,
+    pub timestamp: i64,
+}
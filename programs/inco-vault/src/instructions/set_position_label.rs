@@ -0,0 +1,52 @@
+//! Set Position Label - Lets a position owner name a position for display
+//!
+//! The label is cosmetic: the program stores the 32 raw bytes and never
+//! reads them. Clients encode it as zero-padded UTF-8 and should validate
+//! that before sending; all zeros clears it.
+
+use anchor_lang::prelude::*;
+
+use crate::state::PositionTracker;
+
+/// Replace the tracker's display label
+pub fn handler(ctx: Context<SetPositionLabel>, label: [u8; 32]) -> Result<()> {
+    let tracker = &mut ctx.accounts.position_tracker;
+    tracker.label = label;
+
+    emit!(PositionLabelSet {
+        position_tracker: tracker.key(),
+        owner: ctx.accounts.owner.key(),
+        label,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Position label updated");
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPositionLabel<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ PositionLabelError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+#[error_code]
+pub enum PositionLabelError {
+    #[msg("Unauthorized - not position owner")]
+    Unauthorized,
+}
+
+#[event]
+pub struct PositionLabelSet {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub label: [u8; 32],
+    pub timestamp: i64,
+}
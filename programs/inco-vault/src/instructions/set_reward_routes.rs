@@ -0,0 +1,66 @@
+//! Set Reward Routes - Lets a position owner choose where each reward goes
+//!
+//! Routes take effect on the next `collect_all_profits`. Changing a route
+//! does not move rewards that were already collected.
+//!
+//! The owner also names the pool `collect_and_compound` may swap each reward
+//! through (`Pubkey::default()` = none), so a delegate cannot pick the pool.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PositionTracker, RewardRoute};
+
+/// Replace the tracker's per-index reward routes and compound swap pools
+pub fn handler(
+    ctx: Context<SetRewardRoutes>,
+    routes: [RewardRoute; 3],
+    swap_pools: [Pubkey; 3],
+) -> Result<()> {
+    let tracker = &mut ctx.accounts.position_tracker;
+    tracker.require_not_closed()?;
+
+    let previous = tracker.reward_routes;
+    tracker.reward_routes = routes;
+    tracker.reward_swap_pools = swap_pools;
+
+    emit!(RewardRoutesSet {
+        position_tracker: tracker.key(),
+        owner: ctx.accounts.owner.key(),
+        previous_routes: previous,
+        new_routes: routes,
+        swap_pools,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Reward routes set to {:?}", routes);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRewardRoutes<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ RewardRouteError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+#[error_code]
+pub enum RewardRouteError {
+    #[msg("Unauthorized - not position owner")]
+    Unauthorized,
+}
+
+#[event]
+pub struct RewardRoutesSet {
+    pub position_tracker: Pubkey,
+    pub owner: Pubkey,
+    pub previous_routes: [RewardRoute; 3],
+    pub new_routes: [RewardRoute; 3],
+    pub swap_pools: [Pubkey; 3],
+    pub timestamp: i64,
+}
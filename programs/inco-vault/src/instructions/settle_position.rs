@@ -0,0 +1,128 @@
+//! Settle Position - Reveal the final realized profit as cleartext
+//!
+//! This instruction:
+//! 1. Requires an Inco Ed25519 attestation of the tracker's realized-profit
+//!    handles EARLIER in the same transaction (e.g. a `verify_decryption`)
+//! 2. Reveals each non-zero handle through Inco's `decrypt_to_public`
+//! 3. Checks every revealed value equals its attested plaintext
+//! 4. Stores the cleartext totals as `settled_profit_a/b`
+//!
+//! Only the owner can settle: it makes the position's profit public for
+//! good. Settling again overwrites the stored totals with the current ones.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{CovalidatorSet, PositionTracker, VaultConfig};
+use super::create_position::INCO_LIGHTNING_ID;
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+use super::verify_decryption;
+
+/// Reveal and store the position's realized profit
+pub fn handler(ctx: Context<SettlePosition>) -> Result<()> {
+    let accepted_versions = ctx.accounts.vault_config.accepted_message_versions;
+    let inco_discriminators = IncoDiscriminators::load(&ctx.accounts.inco_config)?;
+    let inco_program = ctx.accounts.inco_lightning_program.to_account_info();
+    let authority = ctx.accounts.owner.to_account_info();
+    let tracker = &mut ctx.accounts.position_tracker;
+
+    let profit_handles = [tracker.encrypted_realized_profit_a, tracker.encrypted_realized_profit_b];
+    let handles: Vec<[u8; 16]> = profit_handles
+        .into_iter()
+        .filter(|handle| *handle != 0)
+        .map(u128::to_le_bytes)
+        .collect();
+    require!(!handles.is_empty(), SettleError::NothingToSettle);
+
+    // No cleartext without a covalidator signature over these exact handles
+    let attested = verify_decryption::preceding_attestation(
+        &ctx.accounts.instructions,
+        CovalidatorSet::load(&ctx.accounts.covalidator_set)?.as_ref(),
+        &handles,
+        accepted_versions,
+    )?
+    .ok_or(SettleError::MissingAttestation)?;
+
+    let mut attested = attested.into_iter();
+    let mut settled = [0u128; 2];
+    for (i, handle) in profit_handles.into_iter().enumerate() {
+        if handle == 0 {
+            continue;
+        }
+        let revealed = inco_lightning_cpi::cpi_decrypt_public(
+            inco_program.clone(),
+            authority.clone(),
+            &inco_discriminators,
+            handle,
+        )?;
+        let expected = attested.next().ok_or(SettleError::MissingAttestation)?;
+        require!(revealed.to_le_bytes() == expected, SettleError::RevealMismatch);
+        settled[i] = revealed;
+    }
+
+    [tracker.settled_profit_a, tracker.settled_profit_b] = settled;
+    tracker.last_update = Clock::get()?.unix_timestamp;
+
+    emit!(PositionSettled {
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        settled_profit_a: tracker.settled_profit_a,
+        settled_profit_b: tracker.settled_profit_b,
+        timestamp: tracker.last_update,
+    });
+
+    msg!("Position settled: A {}, B {}", settled[0], settled[1]);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettlePosition<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", owner.key().as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ SettleError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar for reading the Ed25519 attestation
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum SettleError {
+    #[msg("Unauthorized - only the position owner can settle")]
+    Unauthorized,
+    #[msg("Position has no realized profit to settle")]
+    NothingToSettle,
+    #[msg("No earlier attestation in this transaction covers the realized-profit handles")]
+    MissingAttestation,
+    #[msg("Inco revealed a value that differs from the attested plaintext")]
+    RevealMismatch,
+}
+
+#[event]
+pub struct PositionSettled {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub settled_profit_a: u128,
+    pub settled_profit_b: u128,
+    pub timestamp: i64,
+}
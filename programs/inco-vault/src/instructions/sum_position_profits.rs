@@ -0,0 +1,76 @@
+//! Sum Position Profits - Rolls all encrypted profit handles into one
+//!
+//! This instruction:
+//! 1. Chains Inco `e_add` over profit A, profit B and the three reward handles
+//! 2. Returns the summed handle via return data (the tracker is not modified)
+//!
+//! Same-decimal aggregation: the handles track different tokens, so the sum
+//! is only meaningful when they share units (e.g. same decimals and price).
+//! Callers decide whether that holds; a dashboard then needs one decryption
+//! attestation instead of five.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PositionTracker};
+use super::create_position::INCO_LIGHTNING_ID;
+use super::inco_lightning_cpi::{self, IncoDiscriminators};
+
+/// Return a single encrypted handle for the position's total realized value
+pub fn handler(ctx: Context<SumPositionProfits>) -> Result<u128> {
+    let tracker = &ctx.accounts.position_tracker;
+    let inco_discriminators = IncoDiscriminators::load(&ctx.accounts.inco_config)?;
+
+    // Unset handles (0) have never been credited and are skipped
+    let handles = [
+        tracker.encrypted_realized_profit_a,
+        tracker.encrypted_realized_profit_b,
+        tracker.encrypted_reward_0,
+        tracker.encrypted_reward_1,
+        tracker.encrypted_reward_2,
+    ];
+    let mut set_handles = handles.into_iter().filter(|handle| *handle != 0);
+
+    let Some(mut total) = set_handles.next() else {
+        return err!(SumError::NothingToSum);
+    };
+    for handle in set_handles {
+        total = inco_lightning_cpi::cpi_e_add(
+            ctx.accounts.inco_lightning_program.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            &inco_discriminators,
+            total,
+            handle,
+        )?;
+    }
+
+    msg!("Summed profit handle: {}", total);
+    Ok(total)
+}
+
+#[derive(Accounts)]
+pub struct SumPositionProfits<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"tracker", position_tracker.user.as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.is_owner_or_delegate(&authority.key()) @ SumError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+
+    /// CHECK: Inco discriminator overrides PDA; read whenever initialized
+    #[account(seeds = [b"inco_config"], bump)]
+    pub inco_config: UncheckedAccount<'info>,
+
+    /// CHECK: Inco Lightning
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum SumError {
+    #[msg("Unauthorized - not position owner or delegate")]
+    Unauthorized,
+    #[msg("Position has no encrypted profits to sum")]
+    NothingToSum,
+}
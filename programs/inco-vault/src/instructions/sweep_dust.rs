@@ -0,0 +1,127 @@
+//! Sweep Dust - Returns stranded token remainders from vault accounts
+//!
+//! This instruction:
+//! 1. Reads the full balance of the vault's token A/B accounts
+//! 2. Transfers both balances back to the owner's token accounts
+//!
+//! Rebalances and withdrawals leave small remainders behind; this lets the
+//! owner recover them whenever the vault is not mid-operation and owes no
+//! settled performance fee (which is paid from these balances).
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::VaultPDA;
+
+/// Transfer all vault-held token A/B back to the owner
+pub fn handler(ctx: Context<SweepDust>) -> Result<()> {
+    // Lock also rejects calls while another operation holds the vault
+    let instruction = crate::instruction::SweepDust::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        accounts.vault_pda.require_no_fee_owed()?;
+        let owner_key = accounts.owner.key();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            owner_key.as_ref(),
+            &[accounts.vault_pda.bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let amount_a = accounts.vault_token_a.amount;
+        let amount_b = accounts.vault_token_b.amount;
+
+        for (from, to, amount) in [
+            (&accounts.vault_token_a, &accounts.user_token_a, amount_a),
+            (&accounts.vault_token_b, &accounts.user_token_b, amount_b),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: accounts.vault_pda.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+
+        emit!(DustSwept {
+            owner: owner_key,
+            vault: accounts.vault_pda.key(),
+            amount_a,
+            amount_b,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Dust swept: {} token_a, {} token_b", amount_a, amount_b);
+        Ok(())
+    })
+}
+
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump = vault_pda.bump,
+        constraint = vault_pda.owner == owner.key() @ SweepError::Unauthorized
+    )]
+    pub vault_pda: Account<'info, VaultPDA>,
+
+    // Vault-owned token accounts holding the remainders
+    #[account(
+        mut,
+        constraint = vault_token_a.owner == vault_pda.key() @ SweepError::InvalidVaultTokenAccount
+    )]
+    pub vault_token_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_token_b.owner == vault_pda.key() @ SweepError::InvalidVaultTokenAccount
+    )]
+    pub vault_token_b: Account<'info, TokenAccount>,
+
+    // Owner's destination token accounts
+    #[account(
+        mut,
+        constraint = user_token_a.owner == owner.key() @ SweepError::InvalidUserTokenAccount,
+        constraint = user_token_a.mint == vault_token_a.mint @ SweepError::InvalidUserTokenAccount
+    )]
+    pub user_token_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_b.owner == owner.key() @ SweepError::InvalidUserTokenAccount,
+        constraint = user_token_b.mint == vault_token_b.mint @ SweepError::InvalidUserTokenAccount
+    )]
+    pub user_token_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum SweepError {
+    #[msg("Unauthorized - not vault owner")]
+    Unauthorized,
+    #[msg("Token account is not owned by the vault")]
+    InvalidVaultTokenAccount,
+    #[msg("Destination token account owner or mint mismatch")]
+    InvalidUserTokenAccount,
+}
+
+#[event]
+pub struct DustSwept {
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub timestamp: i64,
+}
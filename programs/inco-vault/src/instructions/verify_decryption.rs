@@ -1,14 +1,29 @@
 //! Verify Decryption - Full Ed25519 signature validation for Inco attestations
 //!
 //! This instruction provides COMPLETE on-chain verification:
-//! 1. Validates Ed25519 instruction is present at index 0
-//! 2. Verifies signer is the trusted Inco covalidator
+//! 1. Validates Ed25519 instruction(s) are present, starting at
+//!    `ed25519_ix_index` (default 0) so compute-budget or ATA-create
+//!    instructions can precede them
+//! 2. Verifies signer is a trusted Inco covalidator (any member of the
+//!    `CovalidatorSet` if passed, else `INCO_COVALIDATOR_PUBKEY`), with
+//!    signature, key and message all read from the Ed25519 instruction itself
 //! 3. Validates message hash matches provided handles + plaintexts
 //! 4. Signature verification is done by Solana runtime (Ed25519 precompile)
+//!
+//! The signed message layout is selected by `message_version` and must be
+//! one of `VaultConfig::accepted_message_versions`:
+//! - v0: `handle(16) || plaintext(16)` pairs
+//! - v1: a 4-byte domain prefix, then `handle(16) || plaintext(32)` pairs
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 // Pubkey is imported from anchor_lang::prelude::*;
 
+use crate::state::{CovalidatorSet, VaultConfig};
+
 /// Ed25519 program ID (native precompile for signature verification)
 // Ed25519SigVerify111111111111111111111111111
 pub const ED25519_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
@@ -26,6 +41,25 @@ pub const INCO_COVALIDATOR_PUBKEY: [u8; 32] = [
     // TODO: Replace with actual Inco covalidator pubkey before deployment
 ];
 
+/// `*_instruction_index` value meaning "this same instruction"
+pub const ED25519_SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Maximum Ed25519 instructions verified in one call (bounds compute)
+pub const MAX_ED25519_INSTRUCTIONS: u8 = 8;
+
+/// Attestation message layouts
+pub mod message_versions {
+    /// `handle(16) || plaintext(16)` pairs
+    pub const V0: u8 = 0;
+    /// `V1_MESSAGE_PREFIX` then `handle(16) || plaintext(32)` pairs
+    pub const V1: u8 = 1;
+    /// Bitmask of every version this program can parse
+    pub const KNOWN_MASK: u8 = (1 << V0) | (1 << V1);
+}
+
+/// Domain-separation prefix of a v1 attestation message
+pub const V1_MESSAGE_PREFIX: [u8; 4] = *b"inco";
+
 /// Ed25519 instruction data layout:
 /// - num_signatures (1 byte)
 /// - padding (1 byte)
@@ -36,16 +70,28 @@ pub const INCO_COVALIDATOR_PUBKEY: [u8; 32] = [
 /// - message_offset (2 bytes)
 /// - message_size (2 bytes)
 /// - message_instruction_index (2 bytes)
+///
 /// [signature data follows if in same instruction]
 /// [public key data follows]
 /// [message data follows]
-
 pub fn handler(
     ctx: Context<VerifyDecryption>,
     num_handles: u8,
     handles: Vec<[u8; 16]>,
     plaintexts: Vec<[u8; 16]>,
+    num_ed25519_ix: u8,
+    ed25519_ix_index: Option<u8>,
+    message_version: u8,
 ) -> Result<()> {
+    ctx.accounts.vault_config.require_message_version(message_version)?;
+
+    // Bound the worst-case cost before any per-handle work
+    ctx.accounts.vault_config.require_handle_count(num_handles as usize)?;
+    require!(
+        handles.len() == plaintexts.len(),
+        VerifyError::PlaintextCountMismatch
+    );
+
     // Validate input lengths match
     require!(
         handles.len() == num_handles as usize,
@@ -55,31 +101,144 @@ pub fn handler(
         plaintexts.len() == num_handles as usize,
         VerifyError::PlaintextCountMismatch
     );
+    require!(
+        num_ed25519_ix <= MAX_ED25519_INSTRUCTIONS,
+        VerifyError::TooManyEd25519Instructions
+    );
 
     let instructions_account = &ctx.accounts.instructions;
+    let covalidator_set = CovalidatorSet::load(&ctx.accounts.covalidator_set)?;
+    let covalidators = covalidator_set.as_ref();
+    let first_ix = ed25519_ix_index.unwrap_or(0) as usize;
 
-    // ========== STEP 1: Load Ed25519 instruction (MUST be index 0) ==========
-    let ed25519_ix = anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked(
-        0, 
-        instructions_account,
-    ).map_err(|_| VerifyError::MissingEd25519Instruction)?;
+    if num_ed25519_ix <= 1 {
+        // Fast path: a single attestation covers every handle
+        let verified = verify_ed25519_instruction(
+            instructions_account,
+            covalidators,
+            first_ix,
+            message_version,
+            &handles,
+            &plaintexts,
+        )?;
+        require!(
+            verified == num_handles as usize,
+            VerifyError::MessageLengthMismatch
+        );
+    } else {
+        // Batched path: consecutive attestations each cover the next slice of handles
+        let mut verified = 0usize;
+        for ix_index in first_ix..first_ix + num_ed25519_ix as usize {
+            verified += verify_ed25519_instruction(
+                instructions_account,
+                covalidators,
+                ix_index,
+                message_version,
+                &handles[verified..],
+                &plaintexts[verified..],
+            )?;
+        }
+        require!(
+            verified == num_handles as usize,
+            VerifyError::HandleCountMismatch
+        );
+    }
+
+    // ========== STEP 6: Signature verification ==========
+    // The Ed25519 precompile instruction is verified by the Solana runtime
+    // BEFORE our program executes. If we reach this point, the signature is valid.
+    msg!("✓ Ed25519 signature verified by Solana runtime");
 
-    // ========== STEP 2: Verify program ID is Ed25519 precompile ==========
+    // ========== STEP 7: Emit verification event ==========
+    emit!(DecryptionVerified {
+        authority: ctx.accounts.authority.key(),
+        num_handles,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Decryption verification complete! {} handles verified on-chain", num_handles);
+    Ok(())
+}
+
+/// Verify the Ed25519 instruction at `ix_index` attests to a prefix of
+/// `handles`/`plaintexts`. Returns the number of pairs it covered.
+fn verify_ed25519_instruction(
+    instructions_account: &AccountInfo,
+    covalidators: Option<&CovalidatorSet>,
+    ix_index: usize,
+    message_version: u8,
+    handles: &[[u8; 16]],
+    plaintexts: &[[u8; 16]],
+) -> Result<usize> {
+    // ========== STEP 1: Load Ed25519 instruction ==========
+    let ed25519_ix = load_instruction_at_checked(ix_index, instructions_account)
+        .map_err(|_| VerifyError::MissingEd25519Instruction)?;
+
+    // ========== STEPS 2-4: Program ID, layout and covalidator signer ==========
+    let message = verify_attestation(&ed25519_ix, ix_index, covalidators)?;
+    msg!("✓ Ed25519 attestation from Inco covalidator (ix {})", ix_index);
+
+    // ========== STEP 5: CRITICAL - Verify message matches handles + plaintexts ==========
+    let pairs = decode_message(message_version, message)?;
+    let num_pairs = pairs.len();
+    require!(
+        num_pairs <= handles.len(),
+        VerifyError::MessageLengthMismatch
+    );
+
+    // Verify each handle-plaintext pair matches what we expect
+    for (i, (handle, plaintext)) in pairs.iter().enumerate() {
+        require!(
+            *handle == handles[i],
+            VerifyError::HandleMismatch
+        );
+        require!(
+            *plaintext == plaintexts[i],
+            VerifyError::PlaintextMismatch
+        );
+    }
+    msg!("✓ Message content verified ({} handle-plaintext pairs)", num_pairs);
+
+    Ok(num_pairs)
+}
+
+/// Validate an Inco attestation and return its signed message
+///
+/// Checks the instruction targets the Ed25519 precompile, carries exactly
+/// one signature from the Inco covalidator, and that the message is
+/// non-empty. The layout is checked by `decode_message`.
+///
+/// `ix_index` is the instruction's position in the transaction. The
+/// signature, key and message must all live in it: an index pointing
+/// elsewhere would have the precompile verify bytes we never read.
+pub fn verify_attestation<'a>(
+    ed25519_ix: &'a Instruction,
+    ix_index: usize,
+    covalidators: Option<&CovalidatorSet>,
+) -> Result<&'a [u8]> {
+    // Verify program ID is Ed25519 precompile
     require!(
         ed25519_ix.program_id == ED25519_PROGRAM_ID,
         VerifyError::InvalidEd25519Program
     );
-    msg!("✓ Ed25519 program ID verified");
 
-    // ========== STEP 3: Parse Ed25519 instruction data ==========
+    // Parse Ed25519 instruction data
     let data = &ed25519_ix.data;
     require!(data.len() >= 16, VerifyError::Ed25519DataTooShort);
 
     let num_signatures = data[0];
     require!(num_signatures == 1, VerifyError::InvalidSignatureCount);
 
+    // Signature, public key and message instruction indices
+    for at in [4, 8, 14] {
+        let index = u16::from_le_bytes([data[at], data[at + 1]]);
+        require!(
+            index == ED25519_SAME_INSTRUCTION || index as usize == ix_index,
+            VerifyError::Ed25519IndexMismatch
+        );
+    }
+
     // Parse offsets (little-endian u16)
-    let _signature_offset = u16::from_le_bytes([data[2], data[3]]) as usize;
     let pubkey_offset = u16::from_le_bytes([data[6], data[7]]) as usize;
     let message_offset = u16::from_le_bytes([data[10], data[11]]) as usize;
     let message_size = u16::from_le_bytes([data[12], data[13]]) as usize;
@@ -88,69 +247,173 @@ pub fn handler(
     let required_len = message_offset + message_size;
     require!(data.len() >= required_len, VerifyError::Ed25519DataTooShort);
 
-    // ========== STEP 4: CRITICAL - Verify signer is Inco covalidator ==========
+    // CRITICAL - Verify signer is Inco covalidator
     require!(
         pubkey_offset + 32 <= data.len(),
         VerifyError::Ed25519DataTooShort
     );
-    let signer_pubkey = &data[pubkey_offset..pubkey_offset + 32];
-    
+    let signer_pubkey: &[u8; 32] = data[pubkey_offset..pubkey_offset + 32]
+        .try_into()
+        .map_err(|_| VerifyError::Ed25519DataTooShort)?;
     require!(
-        signer_pubkey == INCO_COVALIDATOR_PUBKEY,
+        is_trusted_covalidator(covalidators, signer_pubkey),
         VerifyError::UnauthorizedCovalidator
     );
-    msg!("✓ Inco covalidator pubkey verified");
 
-    // ========== STEP 5: CRITICAL - Verify message matches handles + plaintexts ==========
-    let message = &data[message_offset..message_offset + message_size];
-    
-    // Expected message format: handle0 || plaintext0 || handle1 || plaintext1 || ...
-    // Each pair is 32 bytes (16 handle + 16 plaintext)
-    let expected_len = (num_handles as usize) * 32;
+    let message = &data[message_offset..required_len];
+    require!(!message.is_empty(), VerifyError::MessageLengthMismatch);
+
+    Ok(message)
+}
+
+/// Whether `signer` may attest: any member of `covalidators` once the set is
+/// initialized, otherwise the compiled-in `INCO_COVALIDATOR_PUBKEY`
+///
+/// Callers get `covalidators` from `CovalidatorSet::load` on the always-passed
+/// PDA, so `None` only ever means the set does not exist yet.
+pub fn is_trusted_covalidator(covalidators: Option<&CovalidatorSet>, signer: &[u8; 32]) -> bool {
+    match covalidators {
+        Some(set) => set.contains(signer),
+        None => *signer == INCO_COVALIDATOR_PUBKEY,
+    }
+}
+
+/// Decode a signed attestation message into `(handle, plaintext)` pairs
+///
+/// Plaintexts wider than 16 bytes (v1) are little-endian and must fit in a
+/// u128, the width every tracked handle uses.
+pub fn decode_message(message_version: u8, message: &[u8]) -> Result<Vec<([u8; 16], [u8; 16])>> {
+    let (body, plaintext_width) = match message_version {
+        message_versions::V0 => (message, 16),
+        message_versions::V1 => {
+            require!(
+                message.starts_with(&V1_MESSAGE_PREFIX),
+                VerifyError::MessagePrefixMismatch
+            );
+            (&message[V1_MESSAGE_PREFIX.len()..], 32)
+        }
+        _ => return err!(VerifyError::UnsupportedMessageVersion),
+    };
+
+    let pair_len = 16 + plaintext_width;
     require!(
-        message.len() == expected_len,
+        !body.is_empty() && body.len().is_multiple_of(pair_len),
         VerifyError::MessageLengthMismatch
     );
 
-    // Verify each handle-plaintext pair matches what we expect
-    for i in 0..num_handles as usize {
-        let msg_handle = &message[i * 32..i * 32 + 16];
-        let msg_plaintext = &message[i * 32 + 16..i * 32 + 32];
-        
-        require!(
-            msg_handle == &handles[i],
-            VerifyError::HandleMismatch
-        );
-        require!(
-            msg_plaintext == &plaintexts[i],
-            VerifyError::PlaintextMismatch
-        );
-    }
-    msg!("✓ Message content verified ({} handle-plaintext pairs)", num_handles);
+    body.chunks_exact(pair_len)
+        .map(|pair| {
+            let (handle, plaintext) = pair.split_at(16);
+            let (low, high) = plaintext.split_at(16);
+            require!(high.iter().all(|b| *b == 0), VerifyError::PlaintextOutOfRange);
+            let handle = handle.try_into().map_err(|_| VerifyError::MessageLengthMismatch)?;
+            let low = low.try_into().map_err(|_| VerifyError::MessageLengthMismatch)?;
+            Ok((handle, low))
+        })
+        .collect()
+}
 
-    // ========== STEP 6: Signature verification ==========
-    // The Ed25519 precompile instruction is verified by the Solana runtime
-    // BEFORE our program executes. If we reach this point, the signature is valid.
-    msg!("✓ Ed25519 signature verified by Solana runtime");
+/// Whether an Ed25519 instruction earlier in this transaction attests to
+/// every handle in `handles`
+///
+/// Used to gate sensitive operations on a fresh decryption in the same
+/// transaction. Non-attestation instructions are skipped.
+pub fn has_preceding_attestation(
+    instructions_account: &AccountInfo,
+    covalidators: Option<&CovalidatorSet>,
+    handles: &[[u8; 16]],
+    accepted_versions: u8,
+) -> Result<bool> {
+    Ok(preceding_attestation(instructions_account, covalidators, handles, accepted_versions)?.is_some())
+}
 
-    // ========== STEP 7: Emit verification event ==========
-    emit!(DecryptionVerified {
-        authority: ctx.accounts.authority.key(),
-        num_handles,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+/// Plaintexts for `handles` from an Ed25519 attestation earlier in this
+/// transaction, or `None` when no single one covers every handle
+pub fn preceding_attestation(
+    instructions_account: &AccountInfo,
+    covalidators: Option<&CovalidatorSet>,
+    handles: &[[u8; 16]],
+    accepted_versions: u8,
+) -> Result<Option<Vec<[u8; 16]>>> {
+    let current_index = load_current_index_checked(instructions_account)? as usize;
+    find_attested_plaintexts(
+        instructions_account,
+        covalidators,
+        handles,
+        accepted_versions,
+        0..current_index,
+    )
+}
 
-    msg!("Decryption verification complete! {} handles verified on-chain", num_handles);
-    Ok(())
+/// Plaintexts for `handles` from an Ed25519 attestation anywhere in this
+/// transaction (before or after the current instruction)
+///
+/// The runtime verifies every precompile signature before any program runs,
+/// so a later attestation is as binding as an earlier one. Returns `None`
+/// when no single attestation covers every handle.
+pub fn find_transaction_attestation(
+    instructions_account: &AccountInfo,
+    covalidators: Option<&CovalidatorSet>,
+    handles: &[[u8; 16]],
+    accepted_versions: u8,
+) -> Result<Option<Vec<[u8; 16]>>> {
+    find_attested_plaintexts(instructions_account, covalidators, handles, accepted_versions, 0..usize::MAX)
+}
+
+/// Scan instructions in `ix_range` for one attestation covering all `handles`
+///
+/// Each attestation is decoded with every version set in `accepted_versions`.
+fn find_attested_plaintexts(
+    instructions_account: &AccountInfo,
+    covalidators: Option<&CovalidatorSet>,
+    handles: &[[u8; 16]],
+    accepted_versions: u8,
+    ix_range: core::ops::Range<usize>,
+) -> Result<Option<Vec<[u8; 16]>>> {
+    for ix_index in ix_range {
+        // Past the last instruction of the transaction
+        let Ok(ix) = load_instruction_at_checked(ix_index, instructions_account) else {
+            break;
+        };
+        let Ok(message) = verify_attestation(&ix, ix_index, covalidators) else {
+            continue;
+        };
+        for version in (0..8).filter(|v| accepted_versions & (1 << v) != 0) {
+            let Ok(pairs) = decode_message(version, message) else {
+                continue;
+            };
+            let plaintexts: Option<Vec<[u8; 16]>> = handles
+                .iter()
+                .map(|handle| {
+                    pairs
+                        .iter()
+                        .find(|(attested, _)| attested == handle)
+                        .map(|(_, plaintext)| *plaintext)
+                })
+                .collect();
+            if plaintexts.is_some() {
+                return Ok(plaintexts);
+            }
+        }
+    }
+
+    Ok(None)
 }
 
 #[derive(Accounts)]
 pub struct VerifyDecryption<'info> {
     pub authority: Signer<'info>,
     
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+    
     /// CHECK: Instructions sysvar for reading Ed25519 instruction
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     pub instructions: AccountInfo<'info>,
+    
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
 }
 
 #[error_code]
@@ -161,7 +424,7 @@ pub enum VerifyError {
     #[msg("Plaintext count does not match expected")]
     PlaintextCountMismatch,
     
-    #[msg("Missing Ed25519 instruction at index 0")]
+    #[msg("Missing Ed25519 instruction at the expected index")]
     MissingEd25519Instruction,
     
     #[msg("Invalid Ed25519 program ID")]
@@ -184,6 +447,24 @@ pub enum VerifyError {
     
     #[msg("Plaintext in message does not match provided plaintext")]
     PlaintextMismatch,
+    
+    #[msg("Too many Ed25519 instructions in one verification")]
+    TooManyEd25519Instructions,
+    
+    #[msg("Attestation message version is unknown or not accepted")]
+    UnsupportedMessageVersion,
+    
+    #[msg("Attestation message is missing its version prefix")]
+    MessagePrefixMismatch,
+    
+    #[msg("Attested plaintext does not fit in a u128")]
+    PlaintextOutOfRange,
+    
+    #[msg("Too many handles in one verification")]
+    TooManyHandles,
+    
+    #[msg("Ed25519 signature, key or message is read from another instruction")]
+    Ed25519IndexMismatch,
 }
 
 #[event]
@@ -192,3 +473,91 @@ pub struct DecryptionVerified {
     pub num_handles: u8,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_message_versions_agree() {
+        let handle = [1u8; 16];
+        let plaintext = [2u8; 16];
+
+        let v0 = [handle, plaintext].concat();
+        let v1 = [&V1_MESSAGE_PREFIX[..], &handle, &plaintext, &[0u8; 16]].concat();
+
+        let expected = vec![(handle, plaintext)];
+        assert_eq!(decode_message(message_versions::V0, &v0).unwrap(), expected);
+        assert_eq!(decode_message(message_versions::V1, &v1).unwrap(), expected);
+
+        // Each layout is rejected under the other version
+        assert!(decode_message(message_versions::V1, &v0).is_err());
+        assert!(decode_message(message_versions::V0, &v1).is_err());
+    }
+
+    /// Single-signature Ed25519 data with the given instruction indices
+    fn ed25519_ix(indices: [u16; 3]) -> Instruction {
+        let message = [[1u8; 16], [2u8; 16]].concat();
+        let (sig_at, key_at, msg_at) = (16u16, 80u16, 112u16);
+        let mut data = vec![1, 0];
+        for (offset, index) in [(sig_at, indices[0]), (key_at, indices[1])] {
+            data.extend_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&index.to_le_bytes());
+        }
+        data.extend_from_slice(&msg_at.to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.extend_from_slice(&indices[2].to_le_bytes());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(&INCO_COVALIDATOR_PUBKEY);
+        data.extend_from_slice(&message);
+        Instruction::new_with_bytes(ED25519_PROGRAM_ID, &data, vec![])
+    }
+
+    #[test]
+    fn test_verify_attestation_requires_own_instruction_index() {
+        let same = ED25519_SAME_INSTRUCTION;
+        assert!(verify_attestation(&ed25519_ix([same; 3]), 2, None).is_ok());
+        assert!(verify_attestation(&ed25519_ix([2, same, 2]), 2, None).is_ok());
+
+        for indices in [[0, same, same], [same, 1, same], [same, same, 3]] {
+            assert_eq!(
+                verify_attestation(&ed25519_ix(indices), 2, None).unwrap_err(),
+                error!(VerifyError::Ed25519IndexMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trusted_covalidator_set_replaces_compiled_key() {
+        let rotated = Pubkey::new_unique();
+        let mut set = CovalidatorSet {
+            covalidators: [Pubkey::default(); CovalidatorSet::MAX_COVALIDATORS],
+            count: 0,
+            bump: 0,
+        };
+        set.add(rotated).unwrap();
+
+        assert!(is_trusted_covalidator(None, &INCO_COVALIDATOR_PUBKEY));
+        assert!(!is_trusted_covalidator(None, &rotated.to_bytes()));
+        assert!(is_trusted_covalidator(Some(&set), &rotated.to_bytes()));
+        assert!(!is_trusted_covalidator(Some(&set), &INCO_COVALIDATOR_PUBKEY));
+    }
+
+    #[test]
+    fn test_decode_message_rejects_malformed() {
+        let mut wide = [&V1_MESSAGE_PREFIX[..], &[1u8; 16], &[2u8; 16], &[0u8; 16]].concat();
+        *wide.last_mut().unwrap() = 1;
+        assert_eq!(
+            decode_message(message_versions::V1, &wide).unwrap_err(),
+            error!(VerifyError::PlaintextOutOfRange)
+        );
+        assert_eq!(
+            decode_message(message_versions::V1, &V1_MESSAGE_PREFIX).unwrap_err(),
+            error!(VerifyError::MessageLengthMismatch)
+        );
+        assert_eq!(
+            decode_message(2, &[0u8; 32]).unwrap_err(),
+            error!(VerifyError::UnsupportedMessageVersion)
+        );
+    }
+}
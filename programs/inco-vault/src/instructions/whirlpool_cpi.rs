@@ -2,11 +2,14 @@
 //! 
 //! Uses raw invoke_signed since whirlpool crate isn't available as dependency.
 //! Instruction discriminators are from Orca's Anchor IDL.
+//! Position instructions are built by `*_ix` functions that the `cpi_*`
+//! helpers invoke, so their encoding is tested against IDL fixtures.
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
-use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
 
+use crate::math;
 use super::create_position::WHIRLPOOL_PROGRAM_ID;
 
 /// Whirlpool instruction discriminators (from Anchor IDL)
@@ -16,13 +19,281 @@ pub mod discriminators {
     /// increase_liquidity: sha256("global:increase_liquidity")[0..8]
     pub const INCREASE_LIQUIDITY: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
     /// decrease_liquidity: sha256("global:decrease_liquidity")[0..8]
-    pub const DECREASE_LIQUIDITY: [u8; 8] = [160, 38, 208, 111, 172, 195, 133, 136];
+    pub const DECREASE_LIQUIDITY: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
     /// collect_fees: sha256("global:collect_fees")[0..8]
     pub const COLLECT_FEES: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
     /// collect_reward: sha256("global:collect_reward")[0..8]
     pub const COLLECT_REWARD: [u8; 8] = [70, 5, 132, 87, 86, 235, 177, 34];
     /// close_position: sha256("global:close_position")[0..8]
     pub const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+    /// two_hop_swap: sha256("global:two_hop_swap")[0..8]
+    pub const TWO_HOP_SWAP: [u8; 8] = [195, 96, 237, 108, 68, 162, 219, 230];
+    /// swap: sha256("global:swap")[0..8]
+    pub const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    /// initialize_tick_array: sha256("global:initialize_tick_array")[0..8]
+    pub const INITIALIZE_TICK_ARRAY: [u8; 8] = [11, 188, 193, 214, 141, 91, 149, 184];
+}
+
+/// Byte offsets into Whirlpool program accounts (including the 8-byte discriminator)
+pub mod layout {
+    /// Whirlpool.tick_spacing (u16)
+    pub const WHIRLPOOL_TICK_SPACING: usize = 41;
+    /// Whirlpool.sqrt_price (u128)
+    pub const WHIRLPOOL_SQRT_PRICE: usize = 65;
+    /// Whirlpool.tick_current_index (i32)
+    pub const WHIRLPOOL_TICK_CURRENT_INDEX: usize = 81;
+    /// Whirlpool.token_mint_a (Pubkey)
+    pub const WHIRLPOOL_TOKEN_MINT_A: usize = 101;
+    /// Whirlpool.token_mint_b (Pubkey)
+    pub const WHIRLPOOL_TOKEN_MINT_B: usize = 181;
+    /// Whirlpool.reward_last_updated_timestamp (u64, unix seconds)
+    pub const WHIRLPOOL_REWARD_LAST_UPDATED_TIMESTAMP: usize = 261;
+    /// Whirlpool.reward_infos ([WhirlpoolRewardInfo; 3])
+    pub const WHIRLPOOL_REWARD_INFOS: usize = 269;
+    /// Size of one WhirlpoolRewardInfo (mint is its first field)
+    pub const WHIRLPOOL_REWARD_INFO_LEN: usize = 128;
+    /// Whirlpool account size (ends with the reward infos)
+    pub const WHIRLPOOL_LEN: usize = WHIRLPOOL_REWARD_INFOS + 3 * WHIRLPOOL_REWARD_INFO_LEN;
+    /// Position.position_mint (Pubkey)
+    pub const POSITION_MINT: usize = 40;
+    /// Position.liquidity (u128)
+    pub const POSITION_LIQUIDITY: usize = 72;
+    /// Position.tick_lower_index (i32)
+    pub const POSITION_TICK_LOWER: usize = 88;
+    /// Position.tick_upper_index (i32)
+    pub const POSITION_TICK_UPPER: usize = 92;
+    /// Position.fee_owed_a (u64)
+    pub const POSITION_FEE_OWED_A: usize = 112;
+    /// Position.fee_owed_b (u64)
+    pub const POSITION_FEE_OWED_B: usize = 136;
+    /// Position.reward_infos ([PositionRewardInfo; 3])
+    pub const POSITION_REWARD_INFOS: usize = 144;
+    /// Size of one PositionRewardInfo (growth_inside u128, amount_owed u64)
+    pub const POSITION_REWARD_INFO_LEN: usize = 24;
+    /// Offset of amount_owed within a PositionRewardInfo
+    pub const POSITION_REWARD_AMOUNT_OWED: usize = 16;
+}
+
+/// Anchor account discriminator of a Whirlpool: sha256("account:Whirlpool")[0..8]
+pub const WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
+
+/// Pool fields the program reads from a Whirlpool account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WhirlpoolMeta {
+    pub tick_spacing: u16,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub reward_last_updated_timestamp: u64,
+    /// Uninitialized reward slots are `Pubkey::default()`
+    pub reward_mints: [Pubkey; 3],
+}
+
+impl WhirlpoolMeta {
+    /// Decode from raw Whirlpool account data (discriminator included)
+    pub fn from_data(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= layout::WHIRLPOOL_LEN, ErrorCode::AccountDataTooShort);
+        require!(data[..8] == WHIRLPOOL_DISCRIMINATOR, ErrorCode::InvalidWhirlpoolAccount);
+
+        let pubkey = |offset: usize| slice_bytes(data, offset).map(Pubkey::new_from_array);
+
+        let mut reward_mints = [Pubkey::default(); NUM_REWARDS];
+        for (i, mint) in reward_mints.iter_mut().enumerate() {
+            *mint = pubkey(layout::WHIRLPOOL_REWARD_INFOS + i * layout::WHIRLPOOL_REWARD_INFO_LEN)?;
+        }
+
+        Ok(Self {
+            tick_spacing: u16::from_le_bytes(slice_bytes(data, layout::WHIRLPOOL_TICK_SPACING)?),
+            sqrt_price: u128::from_le_bytes(slice_bytes(data, layout::WHIRLPOOL_SQRT_PRICE)?),
+            tick_current_index: i32::from_le_bytes(
+                slice_bytes(data, layout::WHIRLPOOL_TICK_CURRENT_INDEX)?,
+            ),
+            token_mint_a: pubkey(layout::WHIRLPOOL_TOKEN_MINT_A)?,
+            token_mint_b: pubkey(layout::WHIRLPOOL_TOKEN_MINT_B)?,
+            reward_last_updated_timestamp: u64::from_le_bytes(
+                slice_bytes(data, layout::WHIRLPOOL_REWARD_LAST_UPDATED_TIMESTAMP)?,
+            ),
+            reward_mints,
+        })
+    }
+}
+
+/// Read every pool field the program uses from a Whirlpool account
+///
+/// Checks the account is Whirlpool-owned and carries the Whirlpool
+/// discriminator before decoding. All other pool readers go through this.
+pub fn read_whirlpool_meta(whirlpool: &AccountInfo) -> Result<WhirlpoolMeta> {
+    require!(
+        whirlpool.owner == &WHIRLPOOL_PROGRAM_ID,
+        ErrorCode::InvalidAccountOwner
+    );
+    WhirlpoolMeta::from_data(&whirlpool.try_borrow_data()?)
+}
+
+/// Read the tick spacing from a Whirlpool account
+pub fn read_whirlpool_tick_spacing(whirlpool: &AccountInfo) -> Result<u16> {
+    Ok(read_whirlpool_meta(whirlpool)?.tick_spacing)
+}
+
+/// Read the current Q64.64 sqrt price from a Whirlpool account
+pub fn read_whirlpool_sqrt_price(whirlpool: &AccountInfo) -> Result<u128> {
+    Ok(read_whirlpool_meta(whirlpool)?.sqrt_price)
+}
+
+/// Read the current tick index from a Whirlpool account
+pub fn read_whirlpool_tick_current_index(whirlpool: &AccountInfo) -> Result<i32> {
+    Ok(read_whirlpool_meta(whirlpool)?.tick_current_index)
+}
+
+/// Read the last time the Whirlpool's reward/price state was updated
+///
+/// Whirlpool stores no last-update slot; this timestamp is refreshed on every
+/// swap and liquidity change, so it is the closest proxy for price freshness.
+pub fn read_whirlpool_last_updated_timestamp(whirlpool: &AccountInfo) -> Result<u64> {
+    Ok(read_whirlpool_meta(whirlpool)?.reward_last_updated_timestamp)
+}
+
+/// Read the token A and B mints from a Whirlpool account
+pub fn read_whirlpool_token_mints(whirlpool: &AccountInfo) -> Result<(Pubkey, Pubkey)> {
+    let meta = read_whirlpool_meta(whirlpool)?;
+    Ok((meta.token_mint_a, meta.token_mint_b))
+}
+
+/// Read the three reward mints from a Whirlpool account
+///
+/// Uninitialized reward slots are returned as `Pubkey::default()`.
+pub fn read_whirlpool_reward_mints(whirlpool: &AccountInfo) -> Result<[Pubkey; 3]> {
+    Ok(read_whirlpool_meta(whirlpool)?.reward_mints)
+}
+
+/// Number of reward slots on a Whirlpool (valid indexes are `0..NUM_REWARDS`)
+pub const NUM_REWARDS: usize = 3;
+
+/// Check `reward_index` is in bounds and not yet collected in this call
+///
+/// Marks the index in `collected` so collecting it twice (which would
+/// double-count the reward) is rejected.
+pub fn mark_reward_index(collected: &mut [bool; NUM_REWARDS], reward_index: u8) -> Result<()> {
+    let i = reward_index as usize;
+    require!(i < NUM_REWARDS, ErrorCode::InvalidRewardIndex);
+    require!(!collected[i], ErrorCode::DuplicateRewardIndex);
+    collected[i] = true;
+    Ok(())
+}
+
+/// Number of active reward slots (Whirlpool initializes them in index order)
+pub fn reward_count_from_mints(reward_mints: &[Pubkey; 3]) -> u8 {
+    reward_mints
+        .iter()
+        .take_while(|mint| **mint != Pubkey::default())
+        .count() as u8
+}
+
+/// Read the liquidity held by a Whirlpool position account
+pub fn read_position_liquidity(position: &AccountInfo) -> Result<u128> {
+    let bytes = read_account_bytes::<16>(position, layout::POSITION_LIQUIDITY)?;
+    Ok(u128::from_le_bytes(bytes))
+}
+
+/// Read the (lower, upper) tick range of a Whirlpool position account
+pub fn read_position_tick_range(position: &AccountInfo) -> Result<(i32, i32)> {
+    let lower = i32::from_le_bytes(read_account_bytes::<4>(position, layout::POSITION_TICK_LOWER)?);
+    let upper = i32::from_le_bytes(read_account_bytes::<4>(position, layout::POSITION_TICK_UPPER)?);
+    Ok((lower, upper))
+}
+
+/// Whether a Whirlpool position still has fees or rewards owed
+///
+/// Whirlpool refuses to close a position until these are collected.
+pub fn position_has_owed_tokens(position: &AccountInfo) -> Result<bool> {
+    let fee_owed_a = u64::from_le_bytes(read_account_bytes::<8>(position, layout::POSITION_FEE_OWED_A)?);
+    let fee_owed_b = u64::from_le_bytes(read_account_bytes::<8>(position, layout::POSITION_FEE_OWED_B)?);
+    if fee_owed_a > 0 || fee_owed_b > 0 {
+        return Ok(true);
+    }
+    for i in 0..3 {
+        let offset = layout::POSITION_REWARD_INFOS
+            + i * layout::POSITION_REWARD_INFO_LEN
+            + layout::POSITION_REWARD_AMOUNT_OWED;
+        if u64::from_le_bytes(read_account_bytes::<8>(position, offset)?) > 0 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Read the LP NFT mint a Whirlpool position account belongs to
+pub fn read_position_mint(position: &AccountInfo) -> Result<Pubkey> {
+    let bytes = read_account_bytes::<32>(position, layout::POSITION_MINT)?;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+/// Derive the tick array PDA for `start_tick_index` in a Whirlpool
+pub fn tick_array_address(whirlpool: &Pubkey, start_tick_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"tick_array",
+            whirlpool.as_ref(),
+            start_tick_index.to_string().as_bytes(),
+        ],
+        &WHIRLPOOL_PROGRAM_ID,
+    ).0
+}
+
+/// Check the passed tick arrays are the ones covering `tick_lower`/`tick_upper`
+pub fn validate_tick_arrays(
+    whirlpool: &AccountInfo,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<()> {
+    let tick_spacing = read_whirlpool_tick_spacing(whirlpool)?;
+    require!(tick_spacing > 0, ErrorCode::WrongTickArray);
+
+    let expected_lower = tick_array_address(
+        whirlpool.key,
+        math::tick_array_start_index(tick_lower, tick_spacing),
+    );
+    let expected_upper = tick_array_address(
+        whirlpool.key,
+        math::tick_array_start_index(tick_upper, tick_spacing),
+    );
+    require!(*tick_array_lower == expected_lower, ErrorCode::WrongTickArray);
+    require!(*tick_array_upper == expected_upper, ErrorCode::WrongTickArray);
+    Ok(())
+}
+
+/// Whether a tick array account has been initialized by the Whirlpool program
+pub fn is_tick_array_initialized(tick_array: &AccountInfo) -> bool {
+    tick_array.owner == &WHIRLPOOL_PROGRAM_ID && !tick_array.data_is_empty()
+}
+
+/// Fail with an actionable error if a tick array has not been initialized
+pub fn require_tick_array_initialized(tick_array: &AccountInfo) -> Result<()> {
+    if !is_tick_array_initialized(tick_array) {
+        msg!("Tick array {} is not initialized; run init_tick_arrays first", tick_array.key);
+        return err!(ErrorCode::TickArrayNotInitialized);
+    }
+    Ok(())
+}
+
+/// Copy `N` bytes at `offset` out of a Whirlpool-owned account
+fn read_account_bytes<const N: usize>(account: &AccountInfo, offset: usize) -> Result<[u8; N]> {
+    require!(
+        account.owner == &WHIRLPOOL_PROGRAM_ID,
+        ErrorCode::InvalidAccountOwner
+    );
+    let data = account.try_borrow_data()?;
+    slice_bytes(&data, offset)
+}
+
+/// Copy `N` bytes at `offset` out of raw account data
+fn slice_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| error!(ErrorCode::AccountDataTooShort))
 }
 
 /// OpenPosition bumps struct
@@ -31,7 +302,51 @@ pub struct OpenPositionBumps {
     pub position_bump: u8,
 }
 
+/// Build an open_position instruction
+#[allow(clippy::too_many_arguments)]
+pub fn open_position_ix(
+    funder: &Pubkey,
+    owner: &Pubkey,
+    position: &Pubkey,
+    position_mint: &Pubkey,
+    position_token_account: &Pubkey,
+    whirlpool: &Pubkey,
+    token_program: &Pubkey,
+    system_program: &Pubkey,
+    rent: &Pubkey,
+    associated_token_program: &Pubkey,
+    bumps: OpenPositionBumps,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Instruction {
+    let mut data = Vec::with_capacity(8 + 1 + 4 + 4);
+    data.extend_from_slice(&discriminators::OPEN_POSITION);
+    data.push(bumps.position_bump);
+    data.extend_from_slice(&tick_lower_index.to_le_bytes());
+    data.extend_from_slice(&tick_upper_index.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new_readonly(*owner, false),
+        AccountMeta::new(*position, false),
+        AccountMeta::new(*position_mint, true),
+        AccountMeta::new(*position_token_account, false),
+        AccountMeta::new_readonly(*whirlpool, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(*system_program, false),
+        AccountMeta::new_readonly(*rent, false),
+        AccountMeta::new_readonly(*associated_token_program, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
 /// CPI to open_position on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_open_position<'info>(
     whirlpool_program: AccountInfo<'info>,
     funder: AccountInfo<'info>,
@@ -49,31 +364,21 @@ pub fn cpi_open_position<'info>(
     tick_lower_index: i32,
     tick_upper_index: i32,
 ) -> Result<()> {
-    // Build instruction data
-    let mut data = Vec::with_capacity(8 + 1 + 4 + 4);
-    data.extend_from_slice(&discriminators::OPEN_POSITION);
-    data.push(bumps.position_bump);
-    data.extend_from_slice(&tick_lower_index.to_le_bytes());
-    data.extend_from_slice(&tick_upper_index.to_le_bytes());
-
-    let accounts = vec![
-        AccountMeta::new(*funder.key, true),
-        AccountMeta::new_readonly(*owner.key, false),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new(*position_mint.key, true),
-        AccountMeta::new(*position_token_account.key, false),
-        AccountMeta::new_readonly(*whirlpool.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-        AccountMeta::new_readonly(*system_program.key, false),
-        AccountMeta::new_readonly(*rent.key, false),
-        AccountMeta::new_readonly(*associated_token_program.key, false),
-    ];
-
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = open_position_ix(
+        funder.key,
+        owner.key,
+        position.key,
+        position_mint.key,
+        position_token_account.key,
+        whirlpool.key,
+        token_program.key,
+        system_program.key,
+        rent.key,
+        associated_token_program.key,
+        bumps,
+        tick_lower_index,
+        tick_upper_index,
+    );
 
     invoke_signed(
         &ix,
@@ -96,7 +401,87 @@ pub fn cpi_open_position<'info>(
     Ok(())
 }
 
+/// CPI to initialize_tick_array on Whirlpool
+pub fn cpi_initialize_tick_array<'info>(
+    whirlpool_program: AccountInfo<'info>,
+    whirlpool: AccountInfo<'info>,
+    funder: AccountInfo<'info>,
+    tick_array: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    start_tick_index: i32,
+) -> Result<()> {
+    let mut data = Vec::with_capacity(8 + 4);
+    data.extend_from_slice(&discriminators::INITIALIZE_TICK_ARRAY);
+    data.extend_from_slice(&start_tick_index.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*whirlpool.key, false),
+        AccountMeta::new(*funder.key, true),
+        AccountMeta::new(*tick_array.key, false),
+        AccountMeta::new_readonly(*system_program.key, false),
+    ];
+
+    let ix = Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    invoke(
+        &ix,
+        &[whirlpool, funder, tick_array, system_program, whirlpool_program],
+    ).map_err(|_e| error!(ErrorCode::CpiError))?;
+
+    Ok(())
+}
+
+/// Build a increase_liquidity instruction
+#[allow(clippy::too_many_arguments)]
+pub fn increase_liquidity_ix(
+    whirlpool: &Pubkey,
+    token_program: &Pubkey,
+    position_authority: &Pubkey,
+    position: &Pubkey,
+    position_token_account: &Pubkey,
+    token_owner_account_a: &Pubkey,
+    token_owner_account_b: &Pubkey,
+    token_vault_a: &Pubkey,
+    token_vault_b: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(8 + 16 + 8 + 8);
+    data.extend_from_slice(&discriminators::INCREASE_LIQUIDITY);
+    data.extend_from_slice(&liquidity_amount.to_le_bytes());
+    data.extend_from_slice(&token_max_a.to_le_bytes());
+    data.extend_from_slice(&token_max_b.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(*whirlpool, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(*position_authority, true),
+        AccountMeta::new(*position, false),
+        AccountMeta::new_readonly(*position_token_account, false),
+        AccountMeta::new(*token_owner_account_a, false),
+        AccountMeta::new(*token_owner_account_b, false),
+        AccountMeta::new(*token_vault_a, false),
+        AccountMeta::new(*token_vault_b, false),
+        AccountMeta::new(*tick_array_lower, false),
+        AccountMeta::new(*tick_array_upper, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
 /// CPI to increase_liquidity on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_increase_liquidity<'info>(
     whirlpool_program: AccountInfo<'info>,
     whirlpool: AccountInfo<'info>,
@@ -115,32 +500,22 @@ pub fn cpi_increase_liquidity<'info>(
     token_max_a: u64,
     token_max_b: u64,
 ) -> Result<()> {
-    // Build instruction data
-    let mut data = Vec::with_capacity(8 + 16 + 8 + 8);
-    data.extend_from_slice(&discriminators::INCREASE_LIQUIDITY);
-    data.extend_from_slice(&liquidity_amount.to_le_bytes());
-    data.extend_from_slice(&token_max_a.to_le_bytes());
-    data.extend_from_slice(&token_max_b.to_le_bytes());
-
-    let accounts = vec![
-        AccountMeta::new(*whirlpool.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-        AccountMeta::new_readonly(*position_authority.key, true),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new_readonly(*position_token_account.key, false),
-        AccountMeta::new(*token_owner_account_a.key, false),
-        AccountMeta::new(*token_owner_account_b.key, false),
-        AccountMeta::new(*token_vault_a.key, false),
-        AccountMeta::new(*token_vault_b.key, false),
-        AccountMeta::new(*tick_array_lower.key, false),
-        AccountMeta::new(*tick_array_upper.key, false),
-    ];
-
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = increase_liquidity_ix(
+        whirlpool.key,
+        token_program.key,
+        position_authority.key,
+        position.key,
+        position_token_account.key,
+        token_owner_account_a.key,
+        token_owner_account_b.key,
+        token_vault_a.key,
+        token_vault_b.key,
+        tick_array_lower.key,
+        tick_array_upper.key,
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+    );
 
     invoke_signed(
         &ix,
@@ -164,7 +539,53 @@ pub fn cpi_increase_liquidity<'info>(
     Ok(())
 }
 
+/// Build a decrease_liquidity instruction
+#[allow(clippy::too_many_arguments)]
+pub fn decrease_liquidity_ix(
+    whirlpool: &Pubkey,
+    token_program: &Pubkey,
+    position_authority: &Pubkey,
+    position: &Pubkey,
+    position_token_account: &Pubkey,
+    token_owner_account_a: &Pubkey,
+    token_owner_account_b: &Pubkey,
+    token_vault_a: &Pubkey,
+    token_vault_b: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(8 + 16 + 8 + 8);
+    data.extend_from_slice(&discriminators::DECREASE_LIQUIDITY);
+    data.extend_from_slice(&liquidity_amount.to_le_bytes());
+    data.extend_from_slice(&token_min_a.to_le_bytes());
+    data.extend_from_slice(&token_min_b.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(*whirlpool, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(*position_authority, true),
+        AccountMeta::new(*position, false),
+        AccountMeta::new_readonly(*position_token_account, false),
+        AccountMeta::new(*token_owner_account_a, false),
+        AccountMeta::new(*token_owner_account_b, false),
+        AccountMeta::new(*token_vault_a, false),
+        AccountMeta::new(*token_vault_b, false),
+        AccountMeta::new(*tick_array_lower, false),
+        AccountMeta::new(*tick_array_upper, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
 /// CPI to decrease_liquidity on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_decrease_liquidity<'info>(
     whirlpool_program: AccountInfo<'info>,
     whirlpool: AccountInfo<'info>,
@@ -183,31 +604,22 @@ pub fn cpi_decrease_liquidity<'info>(
     token_min_a: u64,
     token_min_b: u64,
 ) -> Result<()> {
-    let mut data = Vec::with_capacity(8 + 16 + 8 + 8);
-    data.extend_from_slice(&discriminators::DECREASE_LIQUIDITY);
-    data.extend_from_slice(&liquidity_amount.to_le_bytes());
-    data.extend_from_slice(&token_min_a.to_le_bytes());
-    data.extend_from_slice(&token_min_b.to_le_bytes());
-
-    let accounts = vec![
-        AccountMeta::new(*whirlpool.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-        AccountMeta::new_readonly(*position_authority.key, true),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new_readonly(*position_token_account.key, false),
-        AccountMeta::new(*token_owner_account_a.key, false),
-        AccountMeta::new(*token_owner_account_b.key, false),
-        AccountMeta::new(*token_vault_a.key, false),
-        AccountMeta::new(*token_vault_b.key, false),
-        AccountMeta::new(*tick_array_lower.key, false),
-        AccountMeta::new(*tick_array_upper.key, false),
-    ];
-
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = decrease_liquidity_ix(
+        whirlpool.key,
+        token_program.key,
+        position_authority.key,
+        position.key,
+        position_token_account.key,
+        token_owner_account_a.key,
+        token_owner_account_b.key,
+        token_vault_a.key,
+        token_vault_b.key,
+        tick_array_lower.key,
+        tick_array_upper.key,
+        liquidity_amount,
+        token_min_a,
+        token_min_b,
+    );
 
     invoke_signed(
         &ix,
@@ -231,7 +643,299 @@ pub fn cpi_decrease_liquidity<'info>(
     Ok(())
 }
 
+/// Arguments for a two_hop_swap (slippage is bounded on both hops)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TwoHopSwapParams {
+    /// Amount in (or out, if `amount_specified_is_input` is false)
+    pub amount: u64,
+    /// Minimum out (or maximum in) across the full route
+    pub other_amount_threshold: u64,
+    pub amount_specified_is_input: bool,
+    pub a_to_b_one: bool,
+    pub a_to_b_two: bool,
+    /// Price limit for the first hop
+    pub sqrt_price_limit_one: u128,
+    /// Price limit for the second hop
+    pub sqrt_price_limit_two: u128,
+}
+
+/// Accounts for a two_hop_swap, in Whirlpool's instruction order
+pub struct TwoHopSwapAccounts<'info> {
+    pub whirlpool_one: AccountInfo<'info>,
+    pub whirlpool_two: AccountInfo<'info>,
+    pub token_owner_account_one_a: AccountInfo<'info>,
+    pub token_vault_one_a: AccountInfo<'info>,
+    pub token_owner_account_one_b: AccountInfo<'info>,
+    pub token_vault_one_b: AccountInfo<'info>,
+    pub token_owner_account_two_a: AccountInfo<'info>,
+    pub token_vault_two_a: AccountInfo<'info>,
+    pub token_owner_account_two_b: AccountInfo<'info>,
+    pub token_vault_two_b: AccountInfo<'info>,
+    pub tick_array_one_0: AccountInfo<'info>,
+    pub tick_array_one_1: AccountInfo<'info>,
+    pub tick_array_one_2: AccountInfo<'info>,
+    pub tick_array_two_0: AccountInfo<'info>,
+    pub tick_array_two_1: AccountInfo<'info>,
+    pub tick_array_two_2: AccountInfo<'info>,
+    pub oracle_one: AccountInfo<'info>,
+    pub oracle_two: AccountInfo<'info>,
+}
+
+impl<'info> TwoHopSwapAccounts<'info> {
+    /// Number of accounts expected by `from_accounts`
+    pub const LEN: usize = 18;
+
+    /// Build from a slice (e.g. `remaining_accounts`) in instruction order
+    pub fn from_accounts(accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        require!(accounts.len() >= Self::LEN, ErrorCode::MissingSwapAccounts);
+        let a = &accounts[..Self::LEN];
+        Ok(Self {
+            whirlpool_one: a[0].clone(),
+            whirlpool_two: a[1].clone(),
+            token_owner_account_one_a: a[2].clone(),
+            token_vault_one_a: a[3].clone(),
+            token_owner_account_one_b: a[4].clone(),
+            token_vault_one_b: a[5].clone(),
+            token_owner_account_two_a: a[6].clone(),
+            token_vault_two_a: a[7].clone(),
+            token_owner_account_two_b: a[8].clone(),
+            token_vault_two_b: a[9].clone(),
+            tick_array_one_0: a[10].clone(),
+            tick_array_one_1: a[11].clone(),
+            tick_array_one_2: a[12].clone(),
+            tick_array_two_0: a[13].clone(),
+            tick_array_two_1: a[14].clone(),
+            tick_array_two_2: a[15].clone(),
+            oracle_one: a[16].clone(),
+            oracle_two: a[17].clone(),
+        })
+    }
+}
+
+/// CPI to two_hop_swap on Whirlpool
+pub fn cpi_two_hop_swap<'info>(
+    whirlpool_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    token_authority: AccountInfo<'info>,
+    accounts: TwoHopSwapAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+    params: &TwoHopSwapParams,
+) -> Result<()> {
+    // amount, other_amount_threshold, 3 bools, 2 sqrt price limits
+    let mut data = Vec::with_capacity(8 + 8 + 8 + 3 + 16 + 16);
+    data.extend_from_slice(&discriminators::TWO_HOP_SWAP);
+    data.extend_from_slice(&params.amount.to_le_bytes());
+    data.extend_from_slice(&params.other_amount_threshold.to_le_bytes());
+    data.push(params.amount_specified_is_input as u8);
+    data.push(params.a_to_b_one as u8);
+    data.push(params.a_to_b_two as u8);
+    data.extend_from_slice(&params.sqrt_price_limit_one.to_le_bytes());
+    data.extend_from_slice(&params.sqrt_price_limit_two.to_le_bytes());
+
+    let metas = vec![
+        AccountMeta::new_readonly(*token_program.key, false),
+        AccountMeta::new_readonly(*token_authority.key, true),
+        AccountMeta::new(*accounts.whirlpool_one.key, false),
+        AccountMeta::new(*accounts.whirlpool_two.key, false),
+        AccountMeta::new(*accounts.token_owner_account_one_a.key, false),
+        AccountMeta::new(*accounts.token_vault_one_a.key, false),
+        AccountMeta::new(*accounts.token_owner_account_one_b.key, false),
+        AccountMeta::new(*accounts.token_vault_one_b.key, false),
+        AccountMeta::new(*accounts.token_owner_account_two_a.key, false),
+        AccountMeta::new(*accounts.token_vault_two_a.key, false),
+        AccountMeta::new(*accounts.token_owner_account_two_b.key, false),
+        AccountMeta::new(*accounts.token_vault_two_b.key, false),
+        AccountMeta::new(*accounts.tick_array_one_0.key, false),
+        AccountMeta::new(*accounts.tick_array_one_1.key, false),
+        AccountMeta::new(*accounts.tick_array_one_2.key, false),
+        AccountMeta::new(*accounts.tick_array_two_0.key, false),
+        AccountMeta::new(*accounts.tick_array_two_1.key, false),
+        AccountMeta::new(*accounts.tick_array_two_2.key, false),
+        // Oracles are writable in current Whirlpool releases (adaptive fee)
+        AccountMeta::new(*accounts.oracle_one.key, false),
+        AccountMeta::new(*accounts.oracle_two.key, false),
+    ];
+
+    let ix = Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts: metas,
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            token_program,
+            token_authority,
+            accounts.whirlpool_one,
+            accounts.whirlpool_two,
+            accounts.token_owner_account_one_a,
+            accounts.token_vault_one_a,
+            accounts.token_owner_account_one_b,
+            accounts.token_vault_one_b,
+            accounts.token_owner_account_two_a,
+            accounts.token_vault_two_a,
+            accounts.token_owner_account_two_b,
+            accounts.token_vault_two_b,
+            accounts.tick_array_one_0,
+            accounts.tick_array_one_1,
+            accounts.tick_array_one_2,
+            accounts.tick_array_two_0,
+            accounts.tick_array_two_1,
+            accounts.tick_array_two_2,
+            accounts.oracle_one,
+            accounts.oracle_two,
+            whirlpool_program,
+        ],
+        signer_seeds,
+    ).map_err(|_e| error!(ErrorCode::CpiError))?;
+
+    Ok(())
+}
+
+/// Arguments for a single-pool swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwapParams {
+    /// Amount in (or out, if `amount_specified_is_input` is false)
+    pub amount: u64,
+    /// Minimum out (or maximum in)
+    pub other_amount_threshold: u64,
+    /// Price limit for the swap
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+}
+
+/// Accounts for a swap, in Whirlpool's instruction order (minus the token
+/// program and authority)
+pub struct SwapAccounts<'info> {
+    pub whirlpool: AccountInfo<'info>,
+    pub token_owner_account_a: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_owner_account_b: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub tick_array_0: AccountInfo<'info>,
+    pub tick_array_1: AccountInfo<'info>,
+    pub tick_array_2: AccountInfo<'info>,
+    pub oracle: AccountInfo<'info>,
+}
+
+impl<'info> SwapAccounts<'info> {
+    /// Number of accounts expected by `from_accounts`
+    pub const LEN: usize = 9;
+
+    /// Build from a slice (e.g. a chunk of `remaining_accounts`) in instruction order
+    pub fn from_accounts(accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        require!(accounts.len() >= Self::LEN, ErrorCode::MissingSwapAccounts);
+        let a = &accounts[..Self::LEN];
+        Ok(Self {
+            whirlpool: a[0].clone(),
+            token_owner_account_a: a[1].clone(),
+            token_vault_a: a[2].clone(),
+            token_owner_account_b: a[3].clone(),
+            token_vault_b: a[4].clone(),
+            tick_array_0: a[5].clone(),
+            tick_array_1: a[6].clone(),
+            tick_array_2: a[7].clone(),
+            oracle: a[8].clone(),
+        })
+    }
+}
+
+/// CPI to swap on Whirlpool
+pub fn cpi_swap<'info>(
+    whirlpool_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    token_authority: AccountInfo<'info>,
+    accounts: SwapAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+    params: &SwapParams,
+) -> Result<()> {
+    // amount, other_amount_threshold, sqrt_price_limit, 2 bools
+    let mut data = Vec::with_capacity(8 + 8 + 8 + 16 + 2);
+    data.extend_from_slice(&discriminators::SWAP);
+    data.extend_from_slice(&params.amount.to_le_bytes());
+    data.extend_from_slice(&params.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&params.sqrt_price_limit.to_le_bytes());
+    data.push(params.amount_specified_is_input as u8);
+    data.push(params.a_to_b as u8);
+
+    let metas = vec![
+        AccountMeta::new_readonly(*token_program.key, false),
+        AccountMeta::new_readonly(*token_authority.key, true),
+        AccountMeta::new(*accounts.whirlpool.key, false),
+        AccountMeta::new(*accounts.token_owner_account_a.key, false),
+        AccountMeta::new(*accounts.token_vault_a.key, false),
+        AccountMeta::new(*accounts.token_owner_account_b.key, false),
+        AccountMeta::new(*accounts.token_vault_b.key, false),
+        AccountMeta::new(*accounts.tick_array_0.key, false),
+        AccountMeta::new(*accounts.tick_array_1.key, false),
+        AccountMeta::new(*accounts.tick_array_2.key, false),
+        AccountMeta::new(*accounts.oracle.key, false),
+    ];
+
+    let ix = Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts: metas,
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            token_program,
+            token_authority,
+            accounts.whirlpool,
+            accounts.token_owner_account_a,
+            accounts.token_vault_a,
+            accounts.token_owner_account_b,
+            accounts.token_vault_b,
+            accounts.tick_array_0,
+            accounts.tick_array_1,
+            accounts.tick_array_2,
+            accounts.oracle,
+            whirlpool_program,
+        ],
+        signer_seeds,
+    ).map_err(|_e| error!(ErrorCode::CpiError))?;
+
+    Ok(())
+}
+
+/// Build a collect_fees instruction
+#[allow(clippy::too_many_arguments)]
+pub fn collect_fees_ix(
+    whirlpool: &Pubkey,
+    position_authority: &Pubkey,
+    position: &Pubkey,
+    position_token_account: &Pubkey,
+    token_owner_account_a: &Pubkey,
+    token_vault_a: &Pubkey,
+    token_owner_account_b: &Pubkey,
+    token_vault_b: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*whirlpool, false),
+        AccountMeta::new_readonly(*position_authority, true),
+        AccountMeta::new(*position, false),
+        AccountMeta::new_readonly(*position_token_account, false),
+        AccountMeta::new(*token_owner_account_a, false),
+        AccountMeta::new(*token_vault_a, false),
+        AccountMeta::new(*token_owner_account_b, false),
+        AccountMeta::new(*token_vault_b, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data: discriminators::COLLECT_FEES.to_vec(),
+    }
+}
+
 /// CPI to collect_fees on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_collect_fees<'info>(
     whirlpool_program: AccountInfo<'info>,
     whirlpool: AccountInfo<'info>,
@@ -245,26 +949,17 @@ pub fn cpi_collect_fees<'info>(
     token_program: AccountInfo<'info>,
     signer_seeds: &[&[&[u8]]],
 ) -> Result<()> {
-    let mut data = Vec::with_capacity(8);
-    data.extend_from_slice(&discriminators::COLLECT_FEES);
-
-    let accounts = vec![
-        AccountMeta::new_readonly(*whirlpool.key, false),
-        AccountMeta::new_readonly(*position_authority.key, true),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new_readonly(*position_token_account.key, false),
-        AccountMeta::new(*token_owner_account_a.key, false),
-        AccountMeta::new(*token_vault_a.key, false),
-        AccountMeta::new(*token_owner_account_b.key, false),
-        AccountMeta::new(*token_vault_b.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-    ];
-
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = collect_fees_ix(
+        whirlpool.key,
+        position_authority.key,
+        position.key,
+        position_token_account.key,
+        token_owner_account_a.key,
+        token_vault_a.key,
+        token_owner_account_b.key,
+        token_vault_b.key,
+        token_program.key,
+    );
 
     invoke_signed(
         &ix,
@@ -286,7 +981,41 @@ pub fn cpi_collect_fees<'info>(
     Ok(())
 }
 
+/// Build a collect_reward instruction
+#[allow(clippy::too_many_arguments)]
+pub fn collect_reward_ix(
+    whirlpool: &Pubkey,
+    position_authority: &Pubkey,
+    position: &Pubkey,
+    position_token_account: &Pubkey,
+    reward_owner_account: &Pubkey,
+    reward_vault: &Pubkey,
+    token_program: &Pubkey,
+    reward_index: u8,
+) -> Instruction {
+    let mut data = Vec::with_capacity(8 + 1);
+    data.extend_from_slice(&discriminators::COLLECT_REWARD);
+    data.push(reward_index);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*whirlpool, false),
+        AccountMeta::new_readonly(*position_authority, true),
+        AccountMeta::new(*position, false),
+        AccountMeta::new_readonly(*position_token_account, false),
+        AccountMeta::new(*reward_owner_account, false),
+        AccountMeta::new(*reward_vault, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
 /// CPI to collect_reward on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_collect_reward<'info>(
     whirlpool_program: AccountInfo<'info>,
     whirlpool: AccountInfo<'info>,
@@ -299,25 +1028,19 @@ pub fn cpi_collect_reward<'info>(
     signer_seeds: &[&[&[u8]]],
     reward_index: u8,
 ) -> Result<()> {
-    let mut data = Vec::with_capacity(8 + 1);
-    data.extend_from_slice(&discriminators::COLLECT_REWARD);
-    data.push(reward_index);
-
-    let accounts = vec![
-        AccountMeta::new_readonly(*whirlpool.key, false),
-        AccountMeta::new_readonly(*position_authority.key, true),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new_readonly(*position_token_account.key, false),
-        AccountMeta::new(*reward_owner_account.key, false),
-        AccountMeta::new(*reward_vault.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-    ];
+    // Whirlpool rejects anything past the last slot with an opaque error
+    require!((reward_index as usize) < NUM_REWARDS, ErrorCode::InvalidRewardIndex);
 
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = collect_reward_ix(
+        whirlpool.key,
+        position_authority.key,
+        position.key,
+        position_token_account.key,
+        reward_owner_account.key,
+        reward_vault.key,
+        token_program.key,
+        reward_index,
+    );
 
     invoke_signed(
         &ix,
@@ -337,7 +1060,33 @@ pub fn cpi_collect_reward<'info>(
     Ok(())
 }
 
+/// Build a close_position instruction
+pub fn close_position_ix(
+    position_authority: &Pubkey,
+    receiver: &Pubkey,
+    position: &Pubkey,
+    position_mint: &Pubkey,
+    position_token_account: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*position_authority, true),
+        AccountMeta::new(*receiver, false),
+        AccountMeta::new(*position, false),
+        AccountMeta::new(*position_mint, false),
+        AccountMeta::new(*position_token_account, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+
+    Instruction {
+        program_id: WHIRLPOOL_PROGRAM_ID,
+        accounts,
+        data: discriminators::CLOSE_POSITION.to_vec(),
+    }
+}
+
 /// CPI to close_position on Whirlpool
+#[allow(clippy::too_many_arguments)]
 pub fn cpi_close_position<'info>(
     whirlpool_program: AccountInfo<'info>,
     position_authority: AccountInfo<'info>,
@@ -348,23 +1097,14 @@ pub fn cpi_close_position<'info>(
     token_program: AccountInfo<'info>,
     signer_seeds: &[&[&[u8]]],
 ) -> Result<()> {
-    let mut data = Vec::with_capacity(8);
-    data.extend_from_slice(&discriminators::CLOSE_POSITION);
-
-    let accounts = vec![
-        AccountMeta::new_readonly(*position_authority.key, true),
-        AccountMeta::new(*receiver.key, false),
-        AccountMeta::new(*position.key, false),
-        AccountMeta::new(*position_mint.key, false),
-        AccountMeta::new(*position_token_account.key, false),
-        AccountMeta::new_readonly(*token_program.key, false),
-    ];
-
-    let ix = Instruction {
-        program_id: WHIRLPOOL_PROGRAM_ID,
-        accounts,
-        data,
-    };
+    let ix = close_position_ix(
+        position_authority.key,
+        receiver.key,
+        position.key,
+        position_mint.key,
+        position_token_account.key,
+        token_program.key,
+    );
 
     invoke_signed(
         &ix,
@@ -388,4 +1128,187 @@ pub fn cpi_close_position<'info>(
 pub enum ErrorCode {
     #[msg("CPI call to Whirlpool program failed")]
     CpiError,
+    #[msg("Account is not owned by the Whirlpool program")]
+    InvalidAccountOwner,
+    #[msg("Whirlpool account data too short")]
+    AccountDataTooShort,
+    #[msg("Tick array does not match the position's tick range")]
+    WrongTickArray,
+    #[msg("Not enough accounts supplied for swap")]
+    MissingSwapAccounts,
+    #[msg("Reward index out of bounds (Whirlpool supports 0-2)")]
+    InvalidRewardIndex,
+    #[msg("Reward index collected more than once")]
+    DuplicateRewardIndex,
+    #[msg("Tick array is not initialized on the pool")]
+    TickArrayNotInitialized,
+    #[msg("Account is not a Whirlpool (discriminator mismatch)")]
+    InvalidWhirlpoolAccount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Distinct key per account slot, so a swapped account shows up as a mismatch
+    fn keys<const N: usize>() -> [Pubkey; N] {
+        core::array::from_fn(|i| Pubkey::new_from_array([i as u8 + 1; 32]))
+    }
+
+    /// Compare metas against `(key, is_signer, is_writable)` fixtures in Orca IDL order
+    fn assert_metas(ix: &Instruction, keys: &[Pubkey], flags: &[(bool, bool)]) {
+        assert_eq!(ix.program_id, WHIRLPOOL_PROGRAM_ID);
+        assert_eq!(ix.accounts.len(), flags.len());
+        for (i, (meta, (is_signer, is_writable))) in ix.accounts.iter().zip(flags).enumerate() {
+            assert_eq!(meta.pubkey, keys[i], "account {} out of order", i);
+            assert_eq!(meta.is_signer, *is_signer, "account {} signer flag", i);
+            assert_eq!(meta.is_writable, *is_writable, "account {} writable flag", i);
+        }
+    }
+
+    #[test]
+    fn test_whirlpool_meta_decoding() {
+        let mut data = vec![0u8; layout::WHIRLPOOL_LEN];
+        data[..8].copy_from_slice(&WHIRLPOOL_DISCRIMINATOR);
+        let mut put = |offset: usize, bytes: &[u8]| {
+            data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        let k = keys::<3>();
+        put(layout::WHIRLPOOL_TICK_SPACING, &64u16.to_le_bytes());
+        put(layout::WHIRLPOOL_SQRT_PRICE, &(1u128 << 64).to_le_bytes());
+        put(layout::WHIRLPOOL_TICK_CURRENT_INDEX, &(-1_234i32).to_le_bytes());
+        put(layout::WHIRLPOOL_TOKEN_MINT_A, k[0].as_ref());
+        put(layout::WHIRLPOOL_TOKEN_MINT_B, k[1].as_ref());
+        put(layout::WHIRLPOOL_REWARD_LAST_UPDATED_TIMESTAMP, &1_700_000_000u64.to_le_bytes());
+        put(layout::WHIRLPOOL_REWARD_INFOS + layout::WHIRLPOOL_REWARD_INFO_LEN, k[2].as_ref());
+
+        let meta = WhirlpoolMeta::from_data(&data).unwrap();
+        assert_eq!(
+            meta,
+            WhirlpoolMeta {
+                tick_spacing: 64,
+                sqrt_price: 1 << 64,
+                tick_current_index: -1_234,
+                token_mint_a: k[0],
+                token_mint_b: k[1],
+                reward_last_updated_timestamp: 1_700_000_000,
+                reward_mints: [Pubkey::default(), k[2], Pubkey::default()],
+            }
+        );
+
+        assert_eq!(
+            WhirlpoolMeta::from_data(&data[..layout::WHIRLPOOL_LEN - 1]).unwrap_err(),
+            error!(ErrorCode::AccountDataTooShort)
+        );
+        data[0] ^= 1;
+        assert_eq!(
+            WhirlpoolMeta::from_data(&data).unwrap_err(),
+            error!(ErrorCode::InvalidWhirlpoolAccount)
+        );
+    }
+
+    const R: (bool, bool) = (false, false);
+    const W: (bool, bool) = (false, true);
+    const S: (bool, bool) = (true, false);
+    const SW: (bool, bool) = (true, true);
+
+    #[test]
+    fn test_open_position_encoding() {
+        let k = keys::<10>();
+        let ix = open_position_ix(
+            &k[0], &k[1], &k[2], &k[3], &k[4], &k[5], &k[6], &k[7], &k[8], &k[9],
+            OpenPositionBumps { position_bump: 254 },
+            -128,
+            64,
+        );
+        assert_eq!(
+            ix.data,
+            [
+                135, 128, 47, 77, 15, 152, 240, 49, // discriminator
+                254, // position_bump
+                0x80, 0xff, 0xff, 0xff, // tick_lower_index = -128
+                64, 0, 0, 0, // tick_upper_index = 64
+            ]
+        );
+        assert_metas(&ix, &k, &[SW, R, W, SW, W, R, R, R, R, R]);
+    }
+
+    #[test]
+    fn test_modify_liquidity_encoding() {
+        let k = keys::<11>();
+        let mut args = vec![];
+        args.extend_from_slice(&1_000_000u128.to_le_bytes());
+        args.extend_from_slice(&500u64.to_le_bytes());
+        args.extend_from_slice(&700u64.to_le_bytes());
+        let flags = [W, R, S, W, R, W, W, W, W, W, W];
+
+        let increase = increase_liquidity_ix(
+            &k[0], &k[1], &k[2], &k[3], &k[4], &k[5], &k[6], &k[7], &k[8], &k[9], &k[10],
+            1_000_000, 500, 700,
+        );
+        assert_eq!(increase.data[..8], [46, 156, 243, 118, 13, 205, 251, 178]);
+        assert_eq!(increase.data[8..], args[..]);
+        assert_metas(&increase, &k, &flags);
+
+        let decrease = decrease_liquidity_ix(
+            &k[0], &k[1], &k[2], &k[3], &k[4], &k[5], &k[6], &k[7], &k[8], &k[9], &k[10],
+            1_000_000, 500, 700,
+        );
+        assert_eq!(decrease.data[..8], [160, 38, 208, 111, 104, 91, 44, 1]);
+        assert_eq!(decrease.data[8..], args[..]);
+        assert_metas(&decrease, &k, &flags);
+    }
+
+    #[test]
+    fn test_collect_fees_encoding() {
+        let k = keys::<9>();
+        let ix = collect_fees_ix(&k[0], &k[1], &k[2], &k[3], &k[4], &k[5], &k[6], &k[7], &k[8]);
+        assert_eq!(ix.data, [164, 152, 207, 99, 30, 186, 19, 182]);
+        assert_metas(&ix, &k, &[R, S, W, R, W, W, W, W, R]);
+    }
+
+    #[test]
+    fn test_collect_reward_encoding() {
+        let k = keys::<7>();
+        let ix = collect_reward_ix(&k[0], &k[1], &k[2], &k[3], &k[4], &k[5], &k[6], 2);
+        assert_eq!(ix.data, [70, 5, 132, 87, 86, 235, 177, 34, 2]);
+        assert_metas(&ix, &k, &[R, S, W, R, W, W, R]);
+    }
+
+    #[test]
+    fn test_close_position_encoding() {
+        let k = keys::<6>();
+        let ix = close_position_ix(&k[0], &k[1], &k[2], &k[3], &k[4], &k[5]);
+        assert_eq!(ix.data, [123, 134, 81, 0, 49, 68, 98, 98]);
+        assert_metas(&ix, &k, &[S, W, W, W, W, R]);
+    }
+
+    #[test]
+    fn test_reward_indexes_in_bounds() {
+        let mut collected = [false; NUM_REWARDS];
+        for i in 0..NUM_REWARDS as u8 {
+            assert!(mark_reward_index(&mut collected, i).is_ok());
+        }
+        assert_eq!(collected, [true; NUM_REWARDS]);
+    }
+
+    #[test]
+    fn test_reward_index_out_of_bounds() {
+        let mut collected = [false; NUM_REWARDS];
+        assert_eq!(
+            mark_reward_index(&mut collected, 3).unwrap_err(),
+            error!(ErrorCode::InvalidRewardIndex)
+        );
+        assert_eq!(collected, [false; NUM_REWARDS]);
+    }
+
+    #[test]
+    fn test_duplicate_reward_index() {
+        let mut collected = [false; NUM_REWARDS];
+        mark_reward_index(&mut collected, 1).unwrap();
+        assert_eq!(
+            mark_reward_index(&mut collected, 1).unwrap_err(),
+            error!(ErrorCode::DuplicateRewardIndex)
+        );
+    }
 }
@@ -5,13 +5,37 @@
 //! 2. Decreases liquidity from the Whirlpool position
 //! 3. Optionally closes the position if all liquidity is removed
 //! 4. Returns tokens to user
+//!
+//! Fees go to `fee_destination_a`/`fee_destination_b` when passed (vault- or
+//! delegate-owned harvest accounts), otherwise to the user's token accounts.
+//! With `collect_fees_first = Some(false)` step 1 is skipped and fees stay
+//! owed on the position for a separate collect.
+//!
+//! A requested close is skipped (not failed) when liquidity remains or fees
+//! or rewards are still owed, since Whirlpool would reject it; the
+//! withdrawal itself still succeeds and the event says why.
+//!
+//! Passing `liquidity_amount = WITHDRAW_ALL` removes exactly the position's
+//! current on-chain liquidity and closes it, so a full exit leaves no dust.
+//!
+//! With `require_attestation`, the transaction must also carry an earlier
+//! Inco Ed25519 attestation covering the position's realized-profit handles.
+//!
+//! Withdrawing more than `VaultConfig::large_withdrawal_threshold` requires
+//! a ready `request_withdrawal` for exactly that liquidity (see
+//! `withdrawal_queue`).
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint};
 
-use crate::state::{PositionTracker, VaultPDA, VaultConfig};
+use crate::state::{CovalidatorSet, PositionTracker, VaultPDA, VaultConfig};
 use super::create_position::WHIRLPOOL_PROGRAM_ID;
 use super::whirlpool_cpi;
+use super::verify_decryption;
+use super::withdrawal_queue::WithdrawalRequestExecuted;
+
+/// Sentinel `liquidity_amount`: withdraw everything and close the position
+pub const WITHDRAW_ALL: u128 = u128::MAX;
 
 /// Withdraw liquidity from position
 pub fn handler(
@@ -20,102 +44,220 @@ pub fn handler(
     token_min_a: u64,
     token_min_b: u64,
     close_position: bool,
+    require_attestation: bool,
+    collect_fees_first: Option<bool>,
 ) -> Result<()> {
-    // Step 0: Check vault not paused + lock
+    // Step 0: Check vault not paused + position still open + lock
     ctx.accounts.vault_config.require_not_paused()?;
-    ctx.accounts.vault_pda.lock()?;
+    ctx.accounts.position_tracker.require_not_closed()?;
+    let instruction = crate::instruction::WithdrawPosition::DISCRIMINATOR;
+    VaultPDA::with_lock(ctx.accounts, |a| &mut a.vault_pda, instruction, |accounts| {
+        withdraw_locked(
+            accounts,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+            close_position,
+            require_attestation,
+            collect_fees_first,
+        )
+    })
+}
+
+/// Body of `withdraw_position` for callers that already hold the vault lock
+pub(crate) fn withdraw_locked(
+    accounts: &mut WithdrawPosition,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+    close_position: bool,
+    require_attestation: bool,
+    collect_fees_first: Option<bool>,
+) -> Result<()> {
+    accounts.vault_pda.require_locked()?;
+    accounts.position_tracker.require_not_frozen_for(&accounts.authority.key())?;
+
+    if require_attestation {
+        require_profit_attestation(
+            accounts.instructions.as_ref(),
+            CovalidatorSet::load(&accounts.covalidator_set)?.as_ref(),
+            &accounts.position_tracker,
+            accounts.vault_config.accepted_message_versions,
+        )?;
+        msg!("✓ Realized-profit attestation found");
+    }
+
+    whirlpool_cpi::validate_tick_arrays(
+        &accounts.whirlpool.to_account_info(),
+        accounts.tick_array_lower.key,
+        accounts.tick_array_upper.key,
+        accounts.position_tracker.tick_lower,
+        accounts.position_tracker.tick_upper,
+    )?;
 
     let vault_seeds = &[
         b"vault".as_ref(),
-        ctx.accounts.authority.key.as_ref(),
-        &[ctx.accounts.vault_pda.bump],
+        accounts.authority.key.as_ref(),
+        &[accounts.vault_pda.bump],
     ];
     let signer_seeds = &[&vault_seeds[..]];
 
-    // Step 1: Collect any pending fees first
-    let pre_balance_a = ctx.accounts.token_account_a.amount;
-    let pre_balance_b = ctx.accounts.token_account_b.amount;
-
-    whirlpool_cpi::cpi_collect_fees(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        ctx.accounts.whirlpool.to_account_info(),
-        ctx.accounts.vault_pda.to_account_info(),
-        ctx.accounts.whirlpool_position.to_account_info(),
-        ctx.accounts.position_token_account.to_account_info(),
-        ctx.accounts.token_account_a.to_account_info(),
-        ctx.accounts.token_vault_a.to_account_info(),
-        ctx.accounts.token_account_b.to_account_info(),
-        ctx.accounts.token_vault_b.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        signer_seeds,
+    // Step 1: Collect any pending fees first (unless opted out)
+    let fee_destination_a = accounts.fee_destination_a
+        .as_ref()
+        .map(|a| a.to_account_info())
+        .unwrap_or_else(|| accounts.token_account_a.to_account_info());
+    let fee_destination_b = accounts.fee_destination_b
+        .as_ref()
+        .map(|a| a.to_account_info())
+        .unwrap_or_else(|| accounts.token_account_b.to_account_info());
+
+    if collect_fees_first.unwrap_or(true) {
+        whirlpool_cpi::cpi_collect_fees(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            fee_destination_a.clone(),
+            accounts.token_vault_a.to_account_info(),
+            fee_destination_b.clone(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        msg!("Fees collected before withdrawal to {} / {}", fee_destination_a.key, fee_destination_b.key);
+        accounts.token_account_a.reload()?;
+        accounts.token_account_b.reload()?;
+    } else {
+        msg!("Fee collection skipped");
+    }
+
+    // Measure after the collect so received amounts are the decrease only
+    let pre_balance_a = accounts.token_account_a.amount;
+    let pre_balance_b = accounts.token_account_b.amount;
+
+    // Step 2: Decrease liquidity, measuring the actual on-chain delta
+    let liquidity_before = whirlpool_cpi::read_position_liquidity(
+        &accounts.whirlpool_position.to_account_info(),
     )?;
 
-    msg!("Fees collected before withdrawal");
-
-    // Step 2: Decrease liquidity
-    whirlpool_cpi::cpi_decrease_liquidity(
-        ctx.accounts.whirlpool_program.to_account_info(),
-        ctx.accounts.whirlpool.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.vault_pda.to_account_info(),
-        ctx.accounts.whirlpool_position.to_account_info(),
-        ctx.accounts.position_token_account.to_account_info(),
-        ctx.accounts.token_account_a.to_account_info(),
-        ctx.accounts.token_account_b.to_account_info(),
-        ctx.accounts.token_vault_a.to_account_info(),
-        ctx.accounts.token_vault_b.to_account_info(),
-        ctx.accounts.tick_array_lower.to_account_info(),
-        ctx.accounts.tick_array_upper.to_account_info(),
-        signer_seeds,
-        liquidity_amount,
-        token_min_a,
-        token_min_b,
+    let withdraw_all = liquidity_amount == WITHDRAW_ALL;
+    let liquidity_amount = if withdraw_all { liquidity_before } else { liquidity_amount };
+    let close_position = close_position || withdraw_all;
+
+    // Large exits must have been queued with `request_withdrawal`. Un-queued
+    // withdrawals add up over a `withdrawal_delay` window, so splitting a
+    // large exit into small calls still hits the queue
+    let now = Clock::get()?.unix_timestamp;
+    let window_secs = accounts.vault_config.withdrawal_delay;
+    let window_total = accounts.position_tracker
+        .unqueued_window_total(window_secs, now)
+        .saturating_add(liquidity_amount);
+    if accounts.vault_config.is_large_withdrawal(window_total) {
+        accounts.position_tracker.take_withdrawal_request(liquidity_amount, now)?;
+        emit!(WithdrawalRequestExecuted {
+            position: accounts.position_tracker.lp_position_mint,
+            liquidity: liquidity_amount,
+            timestamp: now,
+        });
+    } else {
+        accounts.position_tracker.record_unqueued_withdrawal(liquidity_amount, window_secs, now)?;
+    }
+
+    // Nothing to decrease on an empty position (Whirlpool rejects zero)
+    if liquidity_amount > 0 {
+        whirlpool_cpi::cpi_decrease_liquidity(
+            accounts.whirlpool_program.to_account_info(),
+            accounts.whirlpool.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_account_a.to_account_info(),
+            accounts.token_account_b.to_account_info(),
+            accounts.token_vault_a.to_account_info(),
+            accounts.token_vault_b.to_account_info(),
+            accounts.tick_array_lower.to_account_info(),
+            accounts.tick_array_upper.to_account_info(),
+            signer_seeds,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        )?;
+    }
+
+    let liquidity_after = whirlpool_cpi::read_position_liquidity(
+        &accounts.whirlpool_position.to_account_info(),
     )?;
+    let liquidity_removed = liquidity_before.saturating_sub(liquidity_after);
 
-    msg!("Liquidity decreased: {}", liquidity_amount);
+    msg!("Liquidity decreased: {} (requested {})", liquidity_removed, liquidity_amount);
+    accounts.vault_pda.remove_liquidity(liquidity_removed);
 
     // Step 3: Reload to calculate received amounts
-    ctx.accounts.token_account_a.reload()?;
-    ctx.accounts.token_account_b.reload()?;
+    accounts.token_account_a.reload()?;
+    accounts.token_account_b.reload()?;
 
-    let received_a = ctx.accounts.token_account_a.amount.saturating_sub(pre_balance_a);
-    let received_b = ctx.accounts.token_account_b.amount.saturating_sub(pre_balance_b);
+    let received_a = accounts.token_account_a.amount.saturating_sub(pre_balance_a);
+    let received_b = accounts.token_account_b.amount.saturating_sub(pre_balance_b);
 
     msg!("Tokens withdrawn: A={}, B={}", received_a, received_b);
 
-    // Step 4: Close position if requested and all liquidity removed
-    if close_position {
+    // Step 4: Close position if requested, empty, and owed nothing
+    let close_requested_but_nonempty = close_position && liquidity_after > 0;
+    let close_requested_but_owed = close_position
+        && !close_requested_but_nonempty
+        && whirlpool_cpi::position_has_owed_tokens(&accounts.whirlpool_position.to_account_info())?;
+    let position_closed = close_position && !close_requested_but_nonempty && !close_requested_but_owed;
+
+    if close_requested_but_nonempty {
+        msg!("Close skipped: {} liquidity remains in position", liquidity_after);
+    }
+    if close_requested_but_owed {
+        msg!("Close skipped: position still owes fees or rewards; collect them first");
+    }
+
+    if position_closed {
         whirlpool_cpi::cpi_close_position(
-            ctx.accounts.whirlpool_program.to_account_info(),
-            ctx.accounts.vault_pda.to_account_info(),
-            ctx.accounts.authority.to_account_info(),
-            ctx.accounts.whirlpool_position.to_account_info(),
-            ctx.accounts.position_mint.to_account_info(),
-            ctx.accounts.position_token_account.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
+            accounts.whirlpool_program.to_account_info(),
+            accounts.vault_pda.to_account_info(),
+            accounts.authority.to_account_info(),
+            accounts.whirlpool_position.to_account_info(),
+            accounts.position_mint.to_account_info(),
+            accounts.position_token_account.to_account_info(),
+            accounts.token_program.to_account_info(),
             signer_seeds,
         )?;
 
         msg!("Position closed");
 
         // Update vault stats
-        ctx.accounts.vault_pda.decrement_position_count();
+        accounts.vault_pda.decrement_position_count();
     }
 
-    // Step 5: Update position tracker
-    let tracker = &mut ctx.accounts.position_tracker;
-    tracker.last_update = Clock::get()?.unix_timestamp;
+    // Step 4.5: The LP NFT account is closed with the position, else still holds the NFT
+    require_position_token_state(
+        &accounts.position_token_account.to_account_info(),
+        position_closed,
+    )?;
 
-    // Unlock vault
-    ctx.accounts.vault_pda.unlock();
+    // Step 5: Update position tracker lifecycle
+    let tracker = &mut accounts.position_tracker;
+    tracker.record_withdrawal(liquidity_removed, position_closed, Clock::get()?.unix_timestamp)?;
 
     emit!(PositionWithdrawn {
-        user: ctx.accounts.authority.key(),
-        position_mint: ctx.accounts.position_mint.key(),
-        liquidity_withdrawn: liquidity_amount,
+        user: accounts.authority.key(),
+        position_mint: accounts.position_mint.key(),
+        liquidity_withdrawn: liquidity_removed,
         token_a_received: received_a,
         token_b_received: received_b,
-        position_closed: close_position,
+        position_closed,
+        close_requested_but_nonempty,
+        close_requested_but_owed,
+        fee_destination_a: fee_destination_a.key(),
+        fee_destination_b: fee_destination_b.key(),
         timestamp: tracker.last_update,
     });
 
@@ -123,6 +265,49 @@ pub fn handler(
     Ok(())
 }
 
+/// Check the LP NFT token account against the position's close state
+///
+/// Whirlpool's close_position burns the NFT and closes the account, so a
+/// closed position must leave it without lamports; an open one must still
+/// hold exactly the one NFT.
+fn require_position_token_state(account: &AccountInfo, position_closed: bool) -> Result<()> {
+    let valid = if position_closed {
+        account.lamports() == 0
+    } else {
+        account.owner == &anchor_spl::token::ID
+            && TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?.amount == 1
+    };
+    require!(valid, WithdrawError::InvalidPositionTokenState);
+    Ok(())
+}
+
+/// Require an earlier attestation covering the tracker's realized-profit handles
+///
+/// Handles that were never encrypted (zero) have nothing to attest and are
+/// not required.
+fn require_profit_attestation(
+    instructions: Option<&UncheckedAccount>,
+    covalidators: Option<&CovalidatorSet>,
+    tracker: &PositionTracker,
+    accepted_versions: u8,
+) -> Result<()> {
+    let instructions = instructions.ok_or(WithdrawError::MissingAttestation)?;
+    let handles: Vec<[u8; 16]> = [
+        tracker.encrypted_realized_profit_a,
+        tracker.encrypted_realized_profit_b,
+    ]
+    .into_iter()
+    .filter(|handle| *handle != 0)
+    .map(u128::to_le_bytes)
+    .collect();
+
+    require!(
+        verify_decryption::has_preceding_attestation(instructions, covalidators, &handles, accepted_versions)?,
+        WithdrawError::MissingAttestation
+    );
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct WithdrawPosition<'info> {
     #[account(mut)]
@@ -148,16 +333,25 @@ pub struct WithdrawPosition<'info> {
     pub position_tracker: Account<'info, PositionTracker>,
     
     // Whirlpool accounts
-    /// CHECK: Whirlpool (validated by CPI)
-    #[account(mut)]
+    /// CHECK: Whirlpool (must match tracker; validated by CPI)
+    #[account(
+        mut,
+        constraint = position_tracker.is_for_whirlpool(&whirlpool.key()) @ WithdrawError::WhirlpoolMismatch
+    )]
     pub whirlpool: UncheckedAccount<'info>,
     
     /// CHECK: Position (validated by CPI)
     #[account(mut)]
     pub whirlpool_position: UncheckedAccount<'info>,
     
-    // LP NFT
-    #[account(mut)]
+    // LP NFT (decimals 0, supply 1, mint authority revoked by Whirlpool)
+    #[account(
+        mut,
+        constraint = position_mint.key() == position_tracker.lp_position_mint @ WithdrawError::InvalidPositionMint,
+        constraint = position_mint.decimals == 0 @ WithdrawError::InvalidPositionMint,
+        constraint = position_mint.supply == 1 @ WithdrawError::InvalidPositionMint,
+        constraint = position_mint.mint_authority.is_none() @ WithdrawError::InvalidPositionMint
+    )]
     pub position_mint: Account<'info, Mint>,
     
     /// CHECK: Position token account (owned by vault PDA)
@@ -167,16 +361,37 @@ pub struct WithdrawPosition<'info> {
     // User token accounts to receive withdrawn tokens
     #[account(
         mut,
-        constraint = token_account_a.owner == authority.key() @ WithdrawError::InvalidOwner
+        constraint = token_account_a.owner == authority.key() @ WithdrawError::InvalidOwner,
+        constraint = token_account_a.mint == position_tracker.token_mint_a @ WithdrawError::InvalidTokenMint
     )]
     pub token_account_a: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = token_account_b.owner == authority.key() @ WithdrawError::InvalidOwner
+        constraint = token_account_b.owner == authority.key() @ WithdrawError::InvalidOwner,
+        constraint = token_account_b.mint == position_tracker.token_mint_b @ WithdrawError::InvalidTokenMint
     )]
     pub token_account_b: Account<'info, TokenAccount>,
     
+    // Optional fee harvest accounts (owned by the vault PDA, the user or the position's delegate)
+    #[account(
+        mut,
+        constraint = fee_destination_a.owner == vault_pda.key()
+            || position_tracker.is_owner_or_delegate(&fee_destination_a.owner)
+            @ WithdrawError::InvalidFeeDestination,
+        constraint = fee_destination_a.mint == position_tracker.token_mint_a @ WithdrawError::InvalidTokenMint
+    )]
+    pub fee_destination_a: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        constraint = fee_destination_b.owner == vault_pda.key()
+            || position_tracker.is_owner_or_delegate(&fee_destination_b.owner)
+            @ WithdrawError::InvalidFeeDestination,
+        constraint = fee_destination_b.mint == position_tracker.token_mint_b @ WithdrawError::InvalidTokenMint
+    )]
+    pub fee_destination_b: Option<Box<Account<'info, TokenAccount>>>,
+    
     // Pool vaults
     /// CHECK: Token vault A
     #[account(mut)]
@@ -201,6 +416,14 @@ pub struct WithdrawPosition<'info> {
     pub whirlpool_program: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+    
+    /// CHECK: Instructions sysvar (required when `require_attestation` is set)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Trusted covalidator set PDA; read whenever initialized
+    #[account(seeds = [b"covalidators"], bump)]
+    pub covalidator_set: UncheckedAccount<'info>,
 }
 
 #[error_code]
@@ -209,6 +432,18 @@ pub enum WithdrawError {
     InvalidOwner,
     #[msg("Insufficient liquidity")]
     InsufficientLiquidity,
+    #[msg("Position mint is not the tracked Whirlpool LP NFT")]
+    InvalidPositionMint,
+    #[msg("No Inco attestation for the position's profit handles in this transaction")]
+    MissingAttestation,
+    #[msg("Fee destination must be owned by the vault, the user or the position's delegate")]
+    InvalidFeeDestination,
+    #[msg("Whirlpool does not match position tracker")]
+    WhirlpoolMismatch,
+    #[msg("Token account mint does not match the pool's token mint")]
+    InvalidTokenMint,
+    #[msg("Position token account does not match the position's close state")]
+    InvalidPositionTokenState,
 }
 
 #[event]
@@ -219,5 +454,10 @@ pub struct PositionWithdrawn {
     pub token_a_received: u64,
     pub token_b_received: u64,
     pub position_closed: bool,
+    pub close_requested_but_nonempty: bool,
+    /// Close skipped because fees or rewards were still owed
+    pub close_requested_but_owed: bool,
+    pub fee_destination_a: Pubkey,
+    pub fee_destination_b: Pubkey,
     pub timestamp: i64,
 }
@@ -0,0 +1,103 @@
+//! Withdrawal Queue - Delayed exits for large withdrawals
+//!
+//! Withdrawing more than `VaultConfig::large_withdrawal_threshold` liquidity
+//! in one call needs a request made `withdrawal_delay` seconds earlier:
+//!
+//! `request_withdrawal`: records the liquidity and when it becomes ready
+//! `cancel_withdrawal_request`: drops the pending request
+//!
+//! `withdraw_position` then consumes the request, but only for exactly the
+//! requested liquidity and only once it is ready. Withdrawals at or below the
+//! threshold never touch the queue. One request may be pending per position.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{PositionTracker, VaultConfig};
+
+/// Queue a large withdrawal of `liquidity_amount`
+pub fn handler_request(ctx: Context<WithdrawalRequest>, liquidity_amount: u128) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let ready_at = now
+        .checked_add(ctx.accounts.vault_config.withdrawal_delay)
+        .ok_or(WithdrawalQueueError::ReadyTimeOverflow)?;
+
+    let tracker = &mut ctx.accounts.position_tracker;
+    tracker.require_not_closed()?;
+    tracker.request_withdrawal(liquidity_amount, ready_at)?;
+
+    emit!(WithdrawalRequested {
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        liquidity: liquidity_amount,
+        ready_at,
+        timestamp: now,
+    });
+
+    msg!("Withdrawal of {} liquidity queued until {}", liquidity_amount, ready_at);
+    Ok(())
+}
+
+/// Drop the pending withdrawal request
+pub fn handler_cancel(ctx: Context<WithdrawalRequest>) -> Result<()> {
+    let tracker = &mut ctx.accounts.position_tracker;
+    let liquidity = tracker.requested_withdrawal_liquidity;
+    tracker.cancel_withdrawal_request()?;
+
+    emit!(WithdrawalRequestCancelled {
+        position: tracker.lp_position_mint,
+        user: tracker.user,
+        liquidity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Withdrawal request for {} liquidity cancelled", liquidity);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawalRequest<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = vault_config.bump)]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", owner.key().as_ref(), position_tracker.whirlpool.as_ref()],
+        bump = position_tracker.bump,
+        constraint = position_tracker.user == owner.key() @ WithdrawalQueueError::Unauthorized
+    )]
+    pub position_tracker: Account<'info, PositionTracker>,
+}
+
+#[error_code]
+pub enum WithdrawalQueueError {
+    #[msg("Unauthorized - only the position owner can queue withdrawals")]
+    Unauthorized,
+    #[msg("Withdrawal ready time overflows")]
+    ReadyTimeOverflow,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub liquidity: u128,
+    pub ready_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalRequestExecuted {
+    pub position: Pubkey,
+    pub liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalRequestCancelled {
+    pub position: Pubkey,
+    pub user: Pubkey,
+    pub liquidity: u128,
+    pub timestamp: i64,
+}
@@ -8,12 +8,22 @@
 //! - Full Ed25519 attested decryption verification
 //! - Emergency controls (pause, admin rotation)
 
+// `#[program]` emits its IDL handlers at the crate root, and they still call
+// the deprecated `AccountInfo::realloc`; our own modules keep the lint.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
+#[warn(deprecated)]
 pub mod instructions;
+#[warn(deprecated)]
+pub mod math;
+#[warn(deprecated)]
 pub mod state;
 
 use instructions::*;
+use instructions::whirlpool_cpi::{SwapParams, TwoHopSwapParams};
+use state::{Beneficiary, RewardRoute};
 
 declare_id!("HrPBHxpacccsPyjYb3oADADQdG48Sf1j3tVHTDUhV69A");
 
@@ -29,13 +39,14 @@ pub mod inco_vault {
     }
 
     /// Initialize a user's vault PDA
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        instructions::initialize::handler_init_vault(ctx)
+    pub fn initialize_vault(ctx: Context<InitializeVault>, referrer: Option<Pubkey>) -> Result<()> {
+        instructions::initialize::handler_init_vault(ctx, referrer)
     }
 
     // ========== POSITION MANAGEMENT ==========
     
     /// Create a new LP position with encrypted tracking
+    #[allow(clippy::too_many_arguments)]
     pub fn create_position_with_liquidity(
         ctx: Context<CreatePositionWithLiquidity>,
         encrypted_amount_a: Vec<u8>,
@@ -47,6 +58,7 @@ pub mod inco_vault {
         token_max_a: u64,
         token_max_b: u64,
         max_slippage_bps: Option<u16>,
+        label: Option<[u8; 32]>,
     ) -> Result<()> {
         instructions::create_position::handler(
             ctx,
@@ -59,45 +71,324 @@ pub mod inco_vault {
             token_max_a,
             token_max_b,
             max_slippage_bps,
+            label,
+        )
+    }
+
+    /// Create a new LP position from token amounts; liquidity is computed on-chain
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_position_by_amounts(
+        ctx: Context<CreatePositionWithLiquidity>,
+        encrypted_amount_a: Vec<u8>,
+        encrypted_amount_b: Vec<u8>,
+        amount_type: u8,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        amount_a: u64,
+        amount_b: u64,
+        max_slippage_bps: Option<u16>,
+        label: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::create_position::handler_by_amounts(
+            ctx,
+            encrypted_amount_a,
+            encrypted_amount_b,
+            amount_type,
+            tick_lower_index,
+            tick_upper_index,
+            amount_a,
+            amount_b,
+            max_slippage_bps,
+            label,
+        )
+    }
+
+    /// Add liquidity to an existing position, updating its encrypted deposit
+    #[allow(clippy::too_many_arguments)]
+    pub fn increase_position(
+        ctx: Context<IncreasePosition>,
+        encrypted_amount_a: Vec<u8>,
+        encrypted_amount_b: Vec<u8>,
+        amount_type: u8,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        max_slippage_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::increase_position::handler(
+            ctx,
+            encrypted_amount_a,
+            encrypted_amount_b,
+            amount_type,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+            max_slippage_bps,
         )
     }
 
+    /// Permissionless: initialize the tick arrays a new position's range needs
+    pub fn init_tick_arrays(
+        ctx: Context<InitTickArrays>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        instructions::init_tick_arrays::handler(ctx, tick_lower_index, tick_upper_index)
+    }
+
     /// Collect all fees and rewards, update encrypted profit
-    pub fn collect_all_profits(ctx: Context<CollectAllProfits>) -> Result<()> {
-        instructions::collect_profits::handler(ctx)
+    pub fn collect_all_profits(
+        ctx: Context<CollectAllProfits>,
+        encrypt: bool,
+        reward_min: Option<[u64; 3]>,
+    ) -> Result<()> {
+        instructions::collect_profits::handler(ctx, encrypt, reward_min)
+    }
+
+    /// Collect and encrypt token A/B fees only, skipping every reward CPI
+    pub fn collect_fees_only(ctx: Context<CollectAllProfits>) -> Result<()> {
+        instructions::collect_profits::handler_fees_only(ctx)
+    }
+
+    /// Compare encrypted profit to the high-water mark and request decryption
+    pub fn request_hwm_check(ctx: Context<HighWaterMark>) -> Result<()> {
+        instructions::high_water_mark::handler_request_check(ctx)
+    }
+
+    /// Accrue the encrypted performance fee on profit above the high-water mark
+    pub fn settle_performance_fee(ctx: Context<HighWaterMark>) -> Result<()> {
+        instructions::high_water_mark::handler_settle(ctx)
+    }
+
+    /// Reveal the accrued performance fee and pay it from the vault to the fee recipient
+    pub fn claim_performance_fee(ctx: Context<ClaimPerformanceFee>) -> Result<()> {
+        instructions::high_water_mark::handler_claim(ctx)
+    }
+
+    /// Collect profits and require an Inco attestation of the new handles in the same tx
+    pub fn collect_and_attest(
+        ctx: Context<CollectAndAttest>,
+        reward_min: Option<[u64; 3]>,
+    ) -> Result<()> {
+        instructions::collect_and_attest::handler(ctx, reward_min)
+    }
+
+    /// Pay collected rewards out to the owner and debit their encrypted totals
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, amounts: Option<[u64; 3]>) -> Result<()> {
+        instructions::claim_rewards::handler(ctx, amounts)
+    }
+
+    /// Set what collection does with each of the position's rewards, and the
+    /// pool each may be swapped through when compounding
+    pub fn set_reward_routes(
+        ctx: Context<SetRewardRoutes>,
+        routes: [RewardRoute; 3],
+        swap_pools: [Pubkey; 3],
+    ) -> Result<()> {
+        instructions::set_reward_routes::handler(ctx, routes, swap_pools)
+    }
+
+    /// Set or clear the position's client display label
+    pub fn set_position_label(ctx: Context<SetPositionLabel>, label: [u8; 32]) -> Result<()> {
+        instructions::set_position_label::handler(ctx, label)
+    }
+
+    /// Freeze a position so only its owner can act on it
+    pub fn freeze_position(ctx: Context<SetPositionFrozen>) -> Result<()> {
+        instructions::freeze_position::handler_freeze(ctx)
+    }
+
+    /// Lift an owner freeze, re-enabling delegates and keepers
+    pub fn unfreeze_position(ctx: Context<SetPositionFrozen>) -> Result<()> {
+        instructions::freeze_position::handler_unfreeze(ctx)
+    }
+
+    /// Reveal a position's attested realized profit as cleartext on the tracker
+    pub fn settle_position(ctx: Context<SettlePosition>) -> Result<()> {
+        instructions::settle_position::handler(ctx)
+    }
+
+    /// Set the beneficiaries that share this vault's distributed fees
+    pub fn set_fee_split(ctx: Context<SetFeeSplit>, beneficiaries: Vec<Beneficiary>) -> Result<()> {
+        instructions::fee_split::handler_set_fee_split(ctx, beneficiaries)
+    }
+
+    /// Pay a vault-held token balance out to the fee split beneficiaries pro-rata
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::fee_split::handler_distribute_fees(ctx, amount)
+    }
+
+    /// Collect and encrypt fees for several positions passed as account groups
+    pub fn collect_all_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectAllPositions<'info>>,
+    ) -> Result<()> {
+        instructions::collect_all_positions::handler(ctx)
+    }
+
+    /// Collect fees and rewards, swap rewards to A/B and add them back as liquidity
+    pub fn collect_and_compound<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectAndCompound<'info>>,
+        liquidity_amount: u128,
+        swaps: Vec<SwapParams>,
+    ) -> Result<()> {
+        instructions::collect_and_compound::handler(ctx, liquidity_amount, swaps)
+    }
+
+    /// Permissionless: collect a position's fees into its vault for a small tip
+    pub fn crank_collect(ctx: Context<CrankCollect>) -> Result<()> {
+        instructions::crank_collect::handler(ctx)
+    }
+
+    /// Return stranded token A/B remainders from the vault to its owner
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        instructions::sweep_dust::handler(ctx)
     }
 
-    /// Withdraw liquidity from position (partial or full)
+    /// Close an empty vault (and its empty token accounts) to reclaim rent
+    pub fn close_vault<'info>(ctx: Context<'_, '_, 'info, 'info, CloseVault<'info>>) -> Result<()> {
+        instructions::close_vault::handler(ctx)
+    }
+
+    /// Authorize (or clear) a keeper to collect/rebalance a position
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+        instructions::set_delegate::handler(ctx, delegate)
+    }
+
+    /// Upgrade a PositionTracker to the current account layout
+    pub fn migrate_tracker(ctx: Context<MigrateTracker>) -> Result<()> {
+        instructions::migrate_tracker::handler(ctx)
+    }
+
+    /// Upgrade the global VaultConfig to the current account layout
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_vault::handler_config(ctx)
+    }
+
+    /// Upgrade the caller's VaultPDA to the current account layout
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault::handler_vault(ctx)
+    }
+
+    /// Encrypt profits left pending by a collect-only call
+    pub fn encrypt_collected_profits(ctx: Context<EncryptCollectedProfits>) -> Result<()> {
+        instructions::encrypt_collected_profits::handler(ctx)
+    }
+
+    /// Read aggregate vault stats (position count, lifetime fees, liquidity)
+    pub fn get_vault_stats(ctx: Context<GetVaultStats>) -> Result<VaultStats> {
+        instructions::get_vault_stats::handler(ctx)
+    }
+
+    /// Read the global config and program version (read-only, via return data)
+    pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigView> {
+        instructions::get_config::handler(ctx)
+    }
+
+    /// Sum all encrypted profit handles into one (same-decimal aggregation)
+    pub fn sum_position_profits(ctx: Context<SumPositionProfits>) -> Result<u128> {
+        instructions::sum_position_profits::handler(ctx)
+    }
+
+    /// Withdraw liquidity from position (partial, or full with `WITHDRAW_ALL`)
     pub fn withdraw_position(
         ctx: Context<WithdrawPosition>,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
         close_position: bool,
+        require_attestation: bool,
+        collect_fees_first: Option<bool>,
+    ) -> Result<()> {
+        instructions::withdraw_position::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+            close_position,
+            require_attestation,
+            collect_fees_first,
+        )
+    }
+
+    /// Queue a withdrawal above the large-withdrawal threshold
+    pub fn request_withdrawal(ctx: Context<WithdrawalRequest>, liquidity_amount: u128) -> Result<()> {
+        instructions::withdrawal_queue::handler_request(ctx, liquidity_amount)
+    }
+
+    /// Cancel the pending withdrawal request
+    pub fn cancel_withdrawal_request(ctx: Context<WithdrawalRequest>) -> Result<()> {
+        instructions::withdrawal_queue::handler_cancel(ctx)
+    }
+
+    /// Withdraw all liquidity while the vault is paused (skips fees and encryption)
+    pub fn emergency_withdraw(
+        ctx: Context<EmergencyWithdraw>,
+        token_min_a: u64,
+        token_min_b: u64,
     ) -> Result<()> {
-        instructions::withdraw_position::handler(ctx, liquidity_amount, token_min_a, token_min_b, close_position)
+        instructions::emergency_withdraw::handler(ctx, token_min_a, token_min_b)
     }
 
     /// Rebalance position to new tick range (close old, open new)
-    pub fn rebalance_position(
-        ctx: Context<RebalancePosition>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn rebalance_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebalancePosition<'info>>,
         new_tick_lower: i32,
         new_tick_upper: i32,
+        old_token_min_a: u64,
+        old_token_min_b: u64,
         max_slippage_bps: Option<u16>,
+        max_price_age_secs: Option<u64>,
+        route: Option<TwoHopSwapParams>,
     ) -> Result<()> {
-        instructions::rebalance::handler(ctx, new_tick_lower, new_tick_upper, max_slippage_bps)
+        instructions::rebalance::handler(
+            ctx,
+            new_tick_lower,
+            new_tick_upper,
+            old_token_min_a,
+            old_token_min_b,
+            max_slippage_bps,
+            max_price_age_secs,
+            route,
+        )
+    }
+
+    /// Estimate the position's current token value (read-only, via return data)
+    pub fn estimate_position_value(
+        ctx: Context<EstimatePositionValue>,
+    ) -> Result<PositionValueEstimate> {
+        instructions::estimate_position_value::handler(ctx)
+    }
+
+    /// Compare a tracker against its on-chain position and report any drift
+    pub fn reconcile_position(ctx: Context<ReconcilePosition>) -> Result<()> {
+        instructions::reconcile_position::handler(ctx)
     }
 
     // ========== VERIFICATION ==========
     
-    /// Verify decryption via Ed25519 attestation
+    /// Verify decryption via one or more Ed25519 attestations
     pub fn verify_decryption(
         ctx: Context<VerifyDecryption>,
         num_handles: u8,
         handles: Vec<[u8; 16]>,
         plaintexts: Vec<[u8; 16]>,
+        num_ed25519_ix: u8,
+        ed25519_ix_index: Option<u8>,
+        message_version: u8,
     ) -> Result<()> {
-        instructions::verify_decryption::handler(ctx, num_handles, handles, plaintexts)
+        instructions::verify_decryption::handler(
+            ctx,
+            num_handles,
+            handles,
+            plaintexts,
+            num_ed25519_ix,
+            ed25519_ix_index,
+            message_version,
+        )
     }
 
     // ========== ADMIN ==========
@@ -112,6 +403,11 @@ pub mod inco_vault {
         instructions::admin::handler_unpause(ctx)
     }
 
+    /// Permanently disable new deposits; collect and withdraw stay open
+    pub fn disable_deposits_forever(ctx: Context<AdminAction>) -> Result<()> {
+        instructions::admin::handler_disable_deposits_forever(ctx)
+    }
+
     /// Propose new admin (step 1 of 2-step rotation)
     pub fn propose_admin(ctx: Context<AdminAction>, new_admin: Pubkey) -> Result<()> {
         instructions::admin::handler_propose_admin(ctx, new_admin)
@@ -123,12 +419,143 @@ pub mod inco_vault {
     }
 
     /// Update protocol parameters
+    #[allow(clippy::too_many_arguments)]
     pub fn update_params(
         ctx: Context<AdminAction>,
         max_slippage_bps: Option<u16>,
         min_liquidity: Option<u128>,
         max_liquidity: Option<u128>,
+        max_total_liquidity_per_vault: Option<u128>,
+        absolute_max_slippage_bps: Option<u16>,
+        min_tick_width: Option<i32>,
+        max_tick_width: Option<i32>,
+        min_collect_threshold: Option<u64>,
+        safe_tick_lower: Option<i32>,
+        safe_tick_upper: Option<i32>,
+        abandonment_period: Option<i64>,
+        max_positions_per_vault: Option<u32>,
+        accepted_message_versions: Option<u8>,
+        max_handles_per_verify: Option<u8>,
+        large_withdrawal_threshold: Option<u128>,
+        withdrawal_delay: Option<i64>,
     ) -> Result<()> {
-        instructions::admin::handler_update_params(ctx, max_slippage_bps, min_liquidity, max_liquidity)
+        instructions::admin::handler_update_params(
+            ctx,
+            max_slippage_bps,
+            min_liquidity,
+            max_liquidity,
+            max_total_liquidity_per_vault,
+            absolute_max_slippage_bps,
+            min_tick_width,
+            max_tick_width,
+            min_collect_threshold,
+            safe_tick_lower,
+            safe_tick_upper,
+            abandonment_period,
+            max_positions_per_vault,
+            accepted_message_versions,
+            max_handles_per_verify,
+            large_withdrawal_threshold,
+            withdrawal_delay,
+        )
+    }
+
+    /// Update performance fee and referrer share parameters
+    pub fn set_fee_params(
+        ctx: Context<AdminAction>,
+        performance_fee_bps: Option<u16>,
+        referrer_share_bps: Option<u16>,
+        high_water_mark_fees: Option<bool>,
+    ) -> Result<()> {
+        instructions::admin::handler_set_fee_params(
+            ctx,
+            performance_fee_bps,
+            referrer_share_bps,
+            high_water_mark_fees,
+        )
+    }
+
+    /// Set or clear a position's performance fee rate override (admin only)
+    pub fn set_fee_override(
+        ctx: Context<SetFeeOverride>,
+        fee_bps_override: Option<u16>,
+    ) -> Result<()> {
+        instructions::admin::handler_set_fee_override(ctx, fee_bps_override)
+    }
+
+    /// Propose new fee recipient (step 1 of 2-step rotation)
+    pub fn propose_fee_recipient(ctx: Context<AdminAction>, new_recipient: Pubkey) -> Result<()> {
+        instructions::admin::handler_propose_fee_recipient(ctx, new_recipient)
+    }
+
+    /// Accept the fee recipient role (step 2 of 2-step rotation)
+    pub fn accept_fee_recipient(ctx: Context<AcceptFeeRecipient>) -> Result<()> {
+        instructions::admin::handler_accept_fee_recipient(ctx)
+    }
+
+    /// Clear a stuck reentrancy lock on a user's vault
+    pub fn force_unlock_vault(ctx: Context<ForceUnlockVault>) -> Result<()> {
+        instructions::admin::handler_force_unlock_vault(ctx)
+    }
+
+    /// Admin: close a long-inactive position, returning funds and rent to its owner
+    ///
+    /// `token_min_a`/`token_min_b` are raised to the position's value at the
+    /// current price less the default slippage if they are lower.
+    pub fn admin_force_close(
+        ctx: Context<AdminForceClose>,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        instructions::admin_force_close::handler(ctx, token_min_a, token_min_b)
+    }
+
+    /// Override the Inco Lightning CPI discriminators
+    pub fn set_inco_discriminators(
+        ctx: Context<SetIncoDiscriminators>,
+        new_euint128: Option<[u8; 8]>,
+        e_add: Option<[u8; 8]>,
+    ) -> Result<()> {
+        instructions::admin::handler_set_inco_discriminators(ctx, new_euint128, e_add)
+    }
+
+    /// Add per-pool liquidity and slippage limits (admin only)
+    pub fn add_pool_risk_params(
+        ctx: Context<AddPoolRiskParams>,
+        min_liquidity: u128,
+        max_liquidity: u128,
+        default_max_slippage_bps: u16,
+    ) -> Result<()> {
+        instructions::pool_risk_params::handler_add(
+            ctx,
+            min_liquidity,
+            max_liquidity,
+            default_max_slippage_bps,
+        )
+    }
+
+    /// Update a pool's liquidity and slippage limits (admin only)
+    pub fn update_pool_risk_params(
+        ctx: Context<UpdatePoolRiskParams>,
+        min_liquidity: Option<u128>,
+        max_liquidity: Option<u128>,
+        default_max_slippage_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::pool_risk_params::handler_update(
+            ctx,
+            min_liquidity,
+            max_liquidity,
+            default_max_slippage_bps,
+        )
+    }
+
+    /// Trust an additional Inco covalidator key (creates the set on first use)
+    pub fn add_covalidator(ctx: Context<ManageCovalidators>, covalidator: Pubkey) -> Result<()> {
+        instructions::admin::handler_add_covalidator(ctx, covalidator)
+    }
+
+    /// Stop trusting an Inco covalidator key
+    pub fn remove_covalidator(ctx: Context<ManageCovalidators>, covalidator: Pubkey) -> Result<()> {
+        instructions::admin::handler_remove_covalidator(ctx, covalidator)
     }
 }
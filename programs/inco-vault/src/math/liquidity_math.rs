@@ -0,0 +1,204 @@
+//! Liquidity math - Converts between liquidity and token amounts
+//!
+//! Mirrors Whirlpool's token delta formulas using U256 intermediates.
+
+use anchor_lang::prelude::*;
+use ethnum::U256;
+
+use super::MathError;
+
+/// Token A delta between two sqrt prices for a given liquidity
+///
+/// Δa = liquidity * (sqrt_upper - sqrt_lower) * 2^64 / (sqrt_upper * sqrt_lower)
+pub fn get_amount_delta_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64> {
+    let (sqrt_price_lower, sqrt_price_upper) = order_prices(sqrt_price_0, sqrt_price_1);
+    require!(sqrt_price_lower > 0, MathError::InvalidSqrtPrice);
+
+    // `checked_shl` only fails on a shift of 256 or more and drops high bits
+    // otherwise, so the product must leave room for the shift (as Whirlpool checks)
+    let product = U256::from(liquidity)
+        .checked_mul(U256::from(sqrt_price_upper - sqrt_price_lower))
+        .ok_or(MathError::Overflow)?;
+    require!(product >> 192u32 == U256::ZERO, MathError::Overflow);
+    let numerator = product << 64u32;
+    let denominator = U256::from(sqrt_price_lower)
+        .checked_mul(U256::from(sqrt_price_upper))
+        .ok_or(MathError::Overflow)?;
+
+    let quotient = numerator / denominator;
+    let result = if round_up && numerator % denominator != 0 {
+        quotient + U256::ONE
+    } else {
+        quotient
+    };
+
+    u64::try_from(result).map_err(|_| error!(MathError::AmountExceedsMax))
+}
+
+/// Token B delta between two sqrt prices for a given liquidity
+///
+/// Δb = liquidity * (sqrt_upper - sqrt_lower) / 2^64
+pub fn get_amount_delta_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64> {
+    let (sqrt_price_lower, sqrt_price_upper) = order_prices(sqrt_price_0, sqrt_price_1);
+
+    let product = U256::from(liquidity)
+        .checked_mul(U256::from(sqrt_price_upper - sqrt_price_lower))
+        .ok_or(MathError::Overflow)?;
+    let quotient = product >> 64;
+    let result = if round_up && product & U256::from(u64::MAX) > 0 {
+        quotient + U256::ONE
+    } else {
+        quotient
+    };
+
+    u64::try_from(result).map_err(|_| error!(MathError::AmountExceedsMax))
+}
+
+/// Token amounts a position holds at the current price
+///
+/// Returns `(amount_a, amount_b)`. Below the range the position is all
+/// token A, above it all token B, and in range a mix of both.
+pub fn get_amounts_from_liquidity(
+    liquidity: u128,
+    sqrt_price: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    round_up: bool,
+) -> Result<(u64, u64)> {
+    require!(sqrt_price_lower < sqrt_price_upper, MathError::InvalidTickRange);
+
+    if sqrt_price <= sqrt_price_lower {
+        let amount_a = get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+        Ok((amount_a, 0))
+    } else if sqrt_price < sqrt_price_upper {
+        let amount_a = get_amount_delta_a(sqrt_price, sqrt_price_upper, liquidity, round_up)?;
+        let amount_b = get_amount_delta_b(sqrt_price_lower, sqrt_price, liquidity, round_up)?;
+        Ok((amount_a, amount_b))
+    } else {
+        let amount_b = get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+        Ok((0, amount_b))
+    }
+}
+
+/// Largest liquidity the given token amounts can fund at the current price
+///
+/// Inverse of `get_amounts_from_liquidity`, rounded down so the amounts
+/// that liquidity requires never exceed `amount_a` / `amount_b`. In range
+/// the scarcer side decides.
+pub fn get_liquidity_from_amounts(
+    sqrt_price: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    require!(sqrt_price_lower < sqrt_price_upper, MathError::InvalidTickRange);
+
+    if sqrt_price <= sqrt_price_lower {
+        get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, amount_a)
+    } else if sqrt_price < sqrt_price_upper {
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price, sqrt_price_upper, amount_a)?;
+        let liquidity_b = get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price, amount_b)?;
+        Ok(liquidity_a.min(liquidity_b))
+    } else {
+        get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_upper, amount_b)
+    }
+}
+
+/// Liquidity funded by token A alone
+///
+/// L = amount_a * sqrt_lower * sqrt_upper / ((sqrt_upper - sqrt_lower) * 2^64)
+fn get_liquidity_from_amount_a(sqrt_price_lower: u128, sqrt_price_upper: u128, amount_a: u64) -> Result<u128> {
+    require!(sqrt_price_lower > 0, MathError::InvalidSqrtPrice);
+
+    let numerator = U256::from(amount_a)
+        .checked_mul(U256::from(sqrt_price_lower))
+        .ok_or(MathError::Overflow)?
+        .checked_mul(U256::from(sqrt_price_upper))
+        .ok_or(MathError::Overflow)?;
+    let denominator = U256::from(sqrt_price_upper - sqrt_price_lower)
+        .checked_shl(64)
+        .ok_or(MathError::Overflow)?;
+
+    u128::try_from(numerator / denominator).map_err(|_| error!(MathError::Overflow))
+}
+
+/// Liquidity funded by token B alone
+///
+/// L = amount_b * 2^64 / (sqrt_upper - sqrt_lower)
+fn get_liquidity_from_amount_b(sqrt_price_lower: u128, sqrt_price_upper: u128, amount_b: u64) -> Result<u128> {
+    let numerator = U256::from(amount_b) << 64;
+    let denominator = U256::from(sqrt_price_upper - sqrt_price_lower);
+
+    u128::try_from(numerator / denominator).map_err(|_| error!(MathError::Overflow))
+}
+
+fn order_prices(a: u128, b: u128) -> (u128, u128) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+
+    fn range() -> (u128, u128) {
+        (
+            sqrt_price_from_tick_index(-1_000).unwrap(),
+            sqrt_price_from_tick_index(1_000).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_liquidity_round_trip_stays_within_amounts() {
+        let (lower, upper) = range();
+        let amount = 1_000_000_000u64;
+        for sqrt_price in [lower / 2, lower, 1u128 << 64, upper, upper * 2] {
+            let liquidity = get_liquidity_from_amounts(sqrt_price, lower, upper, amount, amount).unwrap();
+            assert!(liquidity > 0);
+            let (a, b) = get_amounts_from_liquidity(liquidity, sqrt_price, lower, upper, true).unwrap();
+            assert!(a <= amount && b <= amount);
+        }
+    }
+
+    #[test]
+    fn test_liquidity_in_range_limited_by_scarcer_side() {
+        let (lower, upper) = range();
+        let price = 1u128 << 64;
+        let balanced = get_liquidity_from_amounts(price, lower, upper, 1_000_000, 1_000_000).unwrap();
+        let short_b = get_liquidity_from_amounts(price, lower, upper, 1_000_000, 1_000).unwrap();
+        assert!(short_b < balanced);
+        assert_eq!(get_liquidity_from_amounts(price, lower, upper, 1_000_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_liquidity_rejects_empty_range() {
+        let (lower, _) = range();
+        assert_eq!(
+            get_liquidity_from_amounts(lower, lower, lower, 1, 1).unwrap_err(),
+            error!(MathError::InvalidTickRange)
+        );
+    }
+
+    #[test]
+    fn test_amount_delta_a_rejects_shift_overflow() {
+        assert_eq!(
+            get_amount_delta_a(1, u128::MAX, u128::MAX, false).unwrap_err(),
+            error!(MathError::Overflow)
+        );
+    }
+}
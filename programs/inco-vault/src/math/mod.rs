@@ -0,0 +1,25 @@
+//! Math module - Whirlpool tick and liquidity math, slippage adjustments
+
+pub mod liquidity_math;
+pub mod slippage;
+pub mod tick_math;
+
+pub use liquidity_math::*;
+pub use slippage::*;
+pub use tick_math::*;
+
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MathError {
+    #[msg("Arithmetic overflow in liquidity math")]
+    Overflow,
+    #[msg("Token amount exceeds u64")]
+    AmountExceedsMax,
+    #[msg("Tick index out of bounds")]
+    TickOutOfBounds,
+    #[msg("Invalid tick range")]
+    InvalidTickRange,
+    #[msg("Invalid sqrt price")]
+    InvalidSqrtPrice,
+}
@@ -0,0 +1,70 @@
+//! Slippage math - Basis-point adjustments on token amounts
+//!
+//! Uses a u128 intermediate so large amounts only fail when the adjusted
+//! result itself no longer fits in a u64.
+
+/// Basis-point denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Raise `amount` by `slippage_bps` (max input tolerance)
+///
+/// Returns `None` only if the result exceeds `u64::MAX`.
+pub fn amount_with_slippage_max(amount: u64, slippage_bps: u16) -> Option<u64> {
+    let adjusted = amount as u128 * (BPS_DENOMINATOR + slippage_bps as u128) / BPS_DENOMINATOR;
+    adjusted.try_into().ok()
+}
+
+/// Lower `amount` by `slippage_bps` (min output tolerance)
+///
+/// Slippage of 100% or more floors the result at zero.
+pub fn amount_with_slippage_min(amount: u64, slippage_bps: u16) -> u64 {
+    let keep_bps = BPS_DENOMINATOR.saturating_sub(slippage_bps as u128);
+    (amount as u128 * keep_bps / BPS_DENOMINATOR) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_slippage_is_identity() {
+        assert_eq!(amount_with_slippage_max(12_345, 0), Some(12_345));
+        assert_eq!(amount_with_slippage_max(u64::MAX, 0), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_rounds_down() {
+        // 999 * 1.01 = 1008.99
+        assert_eq!(amount_with_slippage_max(999, 100), Some(1008));
+    }
+
+    #[test]
+    fn test_large_amount_no_spurious_overflow() {
+        // Previously overflowed in the u64 multiply
+        let amount = u64::MAX / 10_000 + 1;
+        let expected = (amount as u128 * 10_100 / 10_000) as u64;
+        assert_eq!(amount_with_slippage_max(amount, 100), Some(expected));
+    }
+
+    #[test]
+    fn test_boundary_fits_exactly() {
+        // Largest amount whose 1% increase still fits in a u64
+        let amount = ((u64::MAX as u128 * 10_000 + 9_999) / 10_100) as u64;
+        assert!(amount_with_slippage_max(amount, 100).is_some());
+        assert_eq!(amount_with_slippage_max(amount + 1, 100), None);
+    }
+
+    #[test]
+    fn test_result_overflow() {
+        assert_eq!(amount_with_slippage_max(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_min_rounds_down_and_floors_at_zero() {
+        // 1001 * 0.99 = 990.99
+        assert_eq!(amount_with_slippage_min(1_001, 100), 990);
+        assert_eq!(amount_with_slippage_min(u64::MAX, 0), u64::MAX);
+        assert_eq!(amount_with_slippage_min(1_000, 10_000), 0);
+        assert_eq!(amount_with_slippage_min(1_000, u16::MAX), 0);
+    }
+}
@@ -0,0 +1,174 @@
+//! Tick math - Converts tick indexes to Q64.64 sqrt prices and tick arrays
+//!
+//! Ported from Orca's whirlpools core math so on-chain estimates match the
+//! Whirlpool program exactly.
+
+use anchor_lang::prelude::*;
+use ethnum::U256;
+
+use super::MathError;
+
+/// Minimum tick index supported by Whirlpool
+pub const MIN_TICK_INDEX: i32 = -443636;
+
+/// Maximum tick index supported by Whirlpool
+pub const MAX_TICK_INDEX: i32 = 443636;
+
+/// Number of ticks stored in one Whirlpool tick array
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Start index of the tick array containing `tick_index`
+pub fn tick_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick_index.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/// Derive the Q64.64 sqrt price for a tick index
+pub fn sqrt_price_from_tick_index(tick_index: i32) -> Result<u128> {
+    require!(
+        (MIN_TICK_INDEX..=MAX_TICK_INDEX).contains(&tick_index),
+        MathError::TickOutOfBounds
+    );
+    if tick_index >= 0 {
+        Ok(get_sqrt_price_positive_tick(tick_index))
+    } else {
+        Ok(get_sqrt_price_negative_tick(tick_index))
+    }
+}
+
+fn mul_shift_96(n0: u128, n1: u128) -> u128 {
+    let mul: U256 = (<U256>::from(n0) * <U256>::from(n1)) >> 96;
+    mul.as_u128()
+}
+
+fn get_sqrt_price_positive_tick(tick: i32) -> u128 {
+    let mut ratio: u128 = if tick & 1 != 0 {
+        79232123823359799118286999567
+    } else {
+        79228162514264337593543950336
+    };
+
+    if tick & 2 != 0 {
+        ratio = mul_shift_96(ratio, 79236085330515764027303304731);
+    }
+    if tick & 4 != 0 {
+        ratio = mul_shift_96(ratio, 79244008939048815603706035061);
+    }
+    if tick & 8 != 0 {
+        ratio = mul_shift_96(ratio, 79259858533276714757314932305);
+    }
+    if tick & 16 != 0 {
+        ratio = mul_shift_96(ratio, 79291567232598584799939703904);
+    }
+    if tick & 32 != 0 {
+        ratio = mul_shift_96(ratio, 79355022692464371645785046466);
+    }
+    if tick & 64 != 0 {
+        ratio = mul_shift_96(ratio, 79482085999252804386437311141);
+    }
+    if tick & 128 != 0 {
+        ratio = mul_shift_96(ratio, 79736823300114093921829183326);
+    }
+    if tick & 256 != 0 {
+        ratio = mul_shift_96(ratio, 80248749790819932309965073892);
+    }
+    if tick & 512 != 0 {
+        ratio = mul_shift_96(ratio, 81282483887344747381513967011);
+    }
+    if tick & 1024 != 0 {
+        ratio = mul_shift_96(ratio, 83390072131320151908154831281);
+    }
+    if tick & 2048 != 0 {
+        ratio = mul_shift_96(ratio, 87770609709833776024991924138);
+    }
+    if tick & 4096 != 0 {
+        ratio = mul_shift_96(ratio, 97234110755111693312479820773);
+    }
+    if tick & 8192 != 0 {
+        ratio = mul_shift_96(ratio, 119332217159966728226237229890);
+    }
+    if tick & 16384 != 0 {
+        ratio = mul_shift_96(ratio, 179736315981702064433883588727);
+    }
+    if tick & 32768 != 0 {
+        ratio = mul_shift_96(ratio, 407748233172238350107850275304);
+    }
+    if tick & 65536 != 0 {
+        ratio = mul_shift_96(ratio, 2098478828474011932436660412517);
+    }
+    if tick & 131072 != 0 {
+        ratio = mul_shift_96(ratio, 55581415166113811149459800483533);
+    }
+    if tick & 262144 != 0 {
+        ratio = mul_shift_96(ratio, 38992368544603139932233054999993551);
+    }
+
+    ratio >> 32
+}
+
+fn get_sqrt_price_negative_tick(tick: i32) -> u128 {
+    let abs_tick = tick.abs();
+
+    let mut ratio: u128 = if abs_tick & 1 != 0 {
+        18445821805675392311
+    } else {
+        18446744073709551616
+    };
+
+    if abs_tick & 2 != 0 {
+        ratio = (ratio * 18444899583751176498) >> 64
+    }
+    if abs_tick & 4 != 0 {
+        ratio = (ratio * 18443055278223354162) >> 64
+    }
+    if abs_tick & 8 != 0 {
+        ratio = (ratio * 18439367220385604838) >> 64
+    }
+    if abs_tick & 16 != 0 {
+        ratio = (ratio * 18431993317065449817) >> 64
+    }
+    if abs_tick & 32 != 0 {
+        ratio = (ratio * 18417254355718160513) >> 64
+    }
+    if abs_tick & 64 != 0 {
+        ratio = (ratio * 18387811781193591352) >> 64
+    }
+    if abs_tick & 128 != 0 {
+        ratio = (ratio * 18329067761203520168) >> 64
+    }
+    if abs_tick & 256 != 0 {
+        ratio = (ratio * 18212142134806087854) >> 64
+    }
+    if abs_tick & 512 != 0 {
+        ratio = (ratio * 17980523815641551639) >> 64
+    }
+    if abs_tick & 1024 != 0 {
+        ratio = (ratio * 17526086738831147013) >> 64
+    }
+    if abs_tick & 2048 != 0 {
+        ratio = (ratio * 16651378430235024244) >> 64
+    }
+    if abs_tick & 4096 != 0 {
+        ratio = (ratio * 15030750278693429944) >> 64
+    }
+    if abs_tick & 8192 != 0 {
+        ratio = (ratio * 12247334978882834399) >> 64
+    }
+    if abs_tick & 16384 != 0 {
+        ratio = (ratio * 8131365268884726200) >> 64
+    }
+    if abs_tick & 32768 != 0 {
+        ratio = (ratio * 3584323654723342297) >> 64
+    }
+    if abs_tick & 65536 != 0 {
+        ratio = (ratio * 696457651847595233) >> 64
+    }
+    if abs_tick & 131072 != 0 {
+        ratio = (ratio * 26294789957452057) >> 64
+    }
+    if abs_tick & 262144 != 0 {
+        ratio = (ratio * 37481735321082) >> 64
+    }
+
+    ratio
+}
@@ -0,0 +1,139 @@
+//! CovalidatorSet - Admin-managed list of trusted Inco covalidators
+//!
+//! This account stores:
+//! - Up to `MAX_COVALIDATORS` Ed25519 public keys whose attestations are
+//!   accepted
+//!
+//! Several keys may be trusted at once, so a covalidator key can be rotated
+//! (add the new key, then remove the old) without downtime. Until the account
+//! is initialized, attestation checks fall back to `INCO_COVALIDATOR_PUBKEY`;
+//! once it exists every attestation check reads it.
+
+use anchor_lang::prelude::*;
+
+/// Trusted covalidator public keys
+#[account]
+pub struct CovalidatorSet {
+    /// Trusted keys; only the first `count` slots are set
+    pub covalidators: [Pubkey; CovalidatorSet::MAX_COVALIDATORS],
+
+    /// Number of trusted keys
+    pub count: u8,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl CovalidatorSet {
+    /// Read the set from its PDA, or `None` if it has not been initialized
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        super::load_if_initialized(info)
+    }
+
+    /// Most covalidators the set may hold (keeps the membership scan small)
+    pub const MAX_COVALIDATORS: usize = 8;
+
+    /// Account size in bytes
+    pub const LEN: usize = 8 +  // discriminator
+        32 * Self::MAX_COVALIDATORS + // covalidators
+        1 +     // count
+        1;      // bump
+        // Total: 266 bytes
+
+    /// Whether `key` is a trusted covalidator
+    ///
+    /// Scans every slot and compares every byte, so the time taken does not
+    /// depend on which key (if any) matched.
+    pub fn contains(&self, key: &[u8; 32]) -> bool {
+        self.covalidators
+            .iter()
+            .enumerate()
+            .fold(false, |found, (i, slot)| {
+                let diff = slot.as_ref().iter().zip(key).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+                found | ((i < self.count as usize) & (diff == 0))
+            })
+    }
+
+    /// Trust `key`
+    pub fn add(&mut self, key: Pubkey) -> Result<()> {
+        require!(key != Pubkey::default(), CovalidatorError::InvalidCovalidator);
+        require!(!self.contains(&key.to_bytes()), CovalidatorError::AlreadyTrusted);
+        let count = self.count as usize;
+        require!(count < Self::MAX_COVALIDATORS, CovalidatorError::SetFull);
+
+        self.covalidators[count] = key;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Stop trusting `key`, moving the last key into its slot
+    pub fn remove(&mut self, key: &Pubkey) -> Result<()> {
+        let count = self.count as usize;
+        let index = self.covalidators[..count]
+            .iter()
+            .position(|slot| slot == key)
+            .ok_or(CovalidatorError::NotTrusted)?;
+
+        self.covalidators[index] = self.covalidators[count - 1];
+        self.covalidators[count - 1] = Pubkey::default();
+        self.count -= 1;
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum CovalidatorError {
+    #[msg("Covalidator key must not be the default pubkey")]
+    InvalidCovalidator,
+    #[msg("Covalidator is already trusted")]
+    AlreadyTrusted,
+    #[msg("Covalidator set is full")]
+    SetFull,
+    #[msg("Covalidator is not in the trusted set")]
+    NotTrusted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_set() -> CovalidatorSet {
+        CovalidatorSet {
+            covalidators: [Pubkey::default(); CovalidatorSet::MAX_COVALIDATORS],
+            count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_add_and_remove_covalidators() {
+        let mut set = empty_set();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        set.add(a).unwrap();
+        set.add(b).unwrap();
+        assert!(set.contains(&a.to_bytes()) && set.contains(&b.to_bytes()));
+        assert_eq!(set.add(a).unwrap_err(), error!(CovalidatorError::AlreadyTrusted));
+
+        set.remove(&a).unwrap();
+        assert!(!set.contains(&a.to_bytes()));
+        assert!(set.contains(&b.to_bytes()));
+        assert_eq!(set.covalidators[0], b);
+        assert_eq!(set.remove(&a).unwrap_err(), error!(CovalidatorError::NotTrusted));
+    }
+
+    #[test]
+    fn test_covalidator_set_bounds() {
+        let mut set = empty_set();
+        assert_eq!(
+            set.add(Pubkey::default()).unwrap_err(),
+            error!(CovalidatorError::InvalidCovalidator)
+        );
+        // Cleared slots are never matched
+        assert!(!set.contains(&[0u8; 32]));
+
+        for _ in 0..CovalidatorSet::MAX_COVALIDATORS {
+            set.add(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(set.add(Pubkey::new_unique()).unwrap_err(), error!(CovalidatorError::SetFull));
+    }
+}
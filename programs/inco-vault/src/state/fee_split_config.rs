@@ -0,0 +1,141 @@
+//! FeeSplitConfig - Per-vault list of fee beneficiaries
+//!
+//! This account stores:
+//! - The vault it belongs to
+//! - Up to `MAX_BENEFICIARIES` wallets with their share in basis points
+//!
+//! Shares always sum to exactly 10000 bps; `distribute_fees` pays each
+//! beneficiary its share of a vault-owned token balance.
+
+use anchor_lang::prelude::*;
+
+/// A wallet and its share of distributed fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Beneficiary {
+    pub wallet: Pubkey,
+    pub share_bps: u16,
+}
+
+impl Beneficiary {
+    /// Serialized size in bytes
+    pub const LEN: usize = 32 + 2;
+}
+
+/// Per-vault fee split between several beneficiaries
+#[account]
+pub struct FeeSplitConfig {
+    /// Vault PDA this split applies to
+    pub vault_pda: Pubkey,
+
+    /// Beneficiaries in payout order
+    pub beneficiaries: Vec<Beneficiary>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeSplitConfig {
+    /// Most beneficiaries a split may list (bounds account size and compute)
+    pub const MAX_BENEFICIARIES: usize = 8;
+
+    /// Account size in bytes (sized for the maximum list)
+    pub const LEN: usize = 8 +  // discriminator
+        32 +    // vault_pda
+        4 + Self::MAX_BENEFICIARIES * Beneficiary::LEN + // beneficiaries
+        1;      // bump
+        // Total: 317 bytes
+
+    /// Check a beneficiary list before storing it
+    ///
+    /// Requires 1..=MAX_BENEFICIARIES distinct, non-default wallets with
+    /// non-zero shares summing to exactly 10000 bps.
+    pub fn validate_beneficiaries(beneficiaries: &[Beneficiary]) -> Result<()> {
+        require!(
+            !beneficiaries.is_empty() && beneficiaries.len() <= Self::MAX_BENEFICIARIES,
+            FeeSplitError::InvalidBeneficiaryCount
+        );
+
+        let mut total: u32 = 0;
+        for (i, beneficiary) in beneficiaries.iter().enumerate() {
+            require!(beneficiary.wallet != Pubkey::default(), FeeSplitError::InvalidBeneficiary);
+            require!(beneficiary.share_bps > 0, FeeSplitError::InvalidBeneficiary);
+            require!(
+                beneficiaries[..i].iter().all(|b| b.wallet != beneficiary.wallet),
+                FeeSplitError::DuplicateBeneficiary
+            );
+            total += beneficiary.share_bps as u32;
+        }
+        require!(total == 10_000, FeeSplitError::SharesNotTotal);
+        Ok(())
+    }
+
+    /// Each beneficiary's share of `amount`, rounded down
+    ///
+    /// Rounding dust stays with the vault.
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        self.beneficiaries
+            .iter()
+            .map(|b| (amount as u128 * b.share_bps as u128 / 10_000) as u64)
+            .collect()
+    }
+}
+
+#[error_code]
+pub enum FeeSplitError {
+    #[msg("Fee split needs between 1 and MAX_BENEFICIARIES beneficiaries")]
+    InvalidBeneficiaryCount,
+    #[msg("Beneficiary wallet must be set and its share non-zero")]
+    InvalidBeneficiary,
+    #[msg("Beneficiary listed more than once")]
+    DuplicateBeneficiary,
+    #[msg("Beneficiary shares must sum to exactly 10000 bps")]
+    SharesNotTotal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beneficiary(share_bps: u16) -> Beneficiary {
+        Beneficiary { wallet: Pubkey::new_unique(), share_bps }
+    }
+
+    #[test]
+    fn test_validate_beneficiaries() {
+        let valid = [beneficiary(6_000), beneficiary(4_000)];
+        assert!(FeeSplitConfig::validate_beneficiaries(&valid).is_ok());
+
+        assert_eq!(
+            FeeSplitConfig::validate_beneficiaries(&[beneficiary(6_000), beneficiary(3_999)]).unwrap_err(),
+            error!(FeeSplitError::SharesNotTotal)
+        );
+        assert_eq!(
+            FeeSplitConfig::validate_beneficiaries(&[]).unwrap_err(),
+            error!(FeeSplitError::InvalidBeneficiaryCount)
+        );
+        let too_many = [beneficiary(1_000); FeeSplitConfig::MAX_BENEFICIARIES + 1];
+        assert_eq!(
+            FeeSplitConfig::validate_beneficiaries(&too_many).unwrap_err(),
+            error!(FeeSplitError::InvalidBeneficiaryCount)
+        );
+        assert_eq!(
+            FeeSplitConfig::validate_beneficiaries(&[valid[0], valid[0]]).unwrap_err(),
+            error!(FeeSplitError::DuplicateBeneficiary)
+        );
+        assert_eq!(
+            FeeSplitConfig::validate_beneficiaries(&[beneficiary(10_000), beneficiary(0)]).unwrap_err(),
+            error!(FeeSplitError::InvalidBeneficiary)
+        );
+    }
+
+    #[test]
+    fn test_split_rounds_down() {
+        let config = FeeSplitConfig {
+            vault_pda: Pubkey::default(),
+            beneficiaries: vec![beneficiary(3_333), beneficiary(3_333), beneficiary(3_334)],
+            bump: 255,
+        };
+        assert_eq!(config.split(100), vec![33, 33, 33]);
+        assert_eq!(config.split(10_000), vec![3_333, 3_333, 3_334]);
+    }
+}
@@ -0,0 +1,52 @@
+//! IncoConfig - Admin-set overrides for Inco Lightning discriminators
+//!
+//! This account stores:
+//! - The instruction discriminators used for raw Inco Lightning CPIs
+//!
+//! Handlers fall back to the compile-time constants until it is initialized;
+//! once it exists every Inco CPI reads it.
+//! Ops without an override field (e_sub, e_gt, e_ge, e_scalar_mul, decrypt,
+//! decrypt_to_public) always use the constants.
+
+use anchor_lang::prelude::*;
+
+use crate::instructions::inco_lightning_cpi::IncoDiscriminators;
+
+/// Admin-controlled Inco Lightning CPI configuration
+#[account]
+pub struct IncoConfig {
+    /// Discriminator for `new_euint128`
+    pub new_euint128_discriminator: [u8; 8],
+
+    /// Discriminator for `e_add`
+    pub e_add_discriminator: [u8; 8],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl IncoConfig {
+    /// Account size in bytes
+    pub const LEN: usize = 8 +  // discriminator
+        8 +     // new_euint128_discriminator
+        8 +     // e_add_discriminator
+        1;      // bump
+        // Total: 25 bytes
+
+    /// Initialize with the compile-time defaults
+    pub fn initialize(&mut self, bump: u8) {
+        let defaults = IncoDiscriminators::default();
+        self.new_euint128_discriminator = defaults.new_euint128;
+        self.e_add_discriminator = defaults.e_add;
+        self.bump = bump;
+    }
+
+    /// Discriminators to use for CPI
+    pub fn discriminators(&self) -> IncoDiscriminators {
+        IncoDiscriminators {
+            new_euint128: self.new_euint128_discriminator,
+            e_add: self.e_add_discriminator,
+            ..IncoDiscriminators::default()
+        }
+    }
+}
@@ -1,9 +1,36 @@
 //! State module - Account structures for the inco-vault program
 
+pub mod covalidator_set;
+pub mod fee_split_config;
+pub mod inco_config;
+pub mod pool_risk_params;
 pub mod position_tracker;
 pub mod vault_config;
 pub mod vault_pda;
 
+pub use covalidator_set::*;
+pub use fee_split_config::*;
+pub use inco_config::*;
+pub use pool_risk_params::*;
 pub use position_tracker::*;
 pub use vault_config::*;
 pub use vault_pda::*;
+
+use anchor_lang::prelude::*;
+
+/// Deserialize a singleton config PDA once it has been initialized
+///
+/// Callers pin the account to its PDA with a `seeds` constraint, so it is
+/// always passed: before initialization it is an empty system account and
+/// `None` lets the caller fall back to compiled-in defaults; afterwards it
+/// is always read, and a caller cannot skip it to get the defaults back.
+pub fn load_if_initialized<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.owner == &crate::ID {
+        return Ok(Some(T::try_deserialize(&mut &info.try_borrow_data()?[..])?));
+    }
+    require!(
+        info.owner == &anchor_lang::system_program::ID && info.data_is_empty(),
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+    Ok(None)
+}
@@ -0,0 +1,92 @@
+//! PoolRiskParams - Admin-set per-pool overrides of the global risk limits
+//!
+//! This account stores, for one Whirlpool:
+//! - Min and max liquidity per deposit
+//! - Default max slippage
+//!
+//! Once set, these replace `VaultConfig::min_liquidity`, `max_liquidity`
+//! and `default_max_slippage_bps` for positions in that pool. The global
+//! `absolute_max_slippage_bps` ceiling still applies.
+
+use anchor_lang::prelude::*;
+
+/// Risk limits for positions in a single Whirlpool
+#[account]
+pub struct PoolRiskParams {
+    /// Whirlpool these limits apply to
+    pub whirlpool: Pubkey,
+
+    /// Minimum liquidity per deposit
+    pub min_liquidity: u128,
+
+    /// Maximum liquidity per deposit
+    pub max_liquidity: u128,
+
+    /// Slippage used when a call passes none
+    pub default_max_slippage_bps: u16,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PoolRiskParams {
+    /// Read the pool's limits from its PDA, or `None` if none were set
+    pub fn load(info: &AccountInfo) -> Result<Option<Self>> {
+        super::load_if_initialized(info)
+    }
+
+    /// Account size in bytes
+    pub const LEN: usize = 8 +  // discriminator
+        32 +    // whirlpool
+        16 +    // min_liquidity
+        16 +    // max_liquidity
+        2 +     // default_max_slippage_bps
+        1;      // bump
+        // Total: 75 bytes
+
+    /// Check the limits are coherent: `min < max` and the default slippage
+    /// within the global `absolute_max_slippage_bps`
+    pub fn validate(&self, absolute_max_slippage_bps: u16) -> Result<()> {
+        require!(
+            self.min_liquidity < self.max_liquidity,
+            PoolRiskError::InvalidLiquidityBounds
+        );
+        require!(
+            self.default_max_slippage_bps <= absolute_max_slippage_bps,
+            PoolRiskError::InvalidSlippage
+        );
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum PoolRiskError {
+    #[msg("Pool min liquidity must be below its max liquidity")]
+    InvalidLiquidityBounds,
+    #[msg("Pool default slippage exceeds the global slippage ceiling")]
+    InvalidSlippage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_pool_risk_params() {
+        let mut params = PoolRiskParams {
+            whirlpool: Pubkey::new_unique(),
+            min_liquidity: 1_000,
+            max_liquidity: 1_000_000,
+            default_max_slippage_bps: 50,
+            bump: 255,
+        };
+        assert!(params.validate(100).is_ok());
+        assert_eq!(params.validate(49).unwrap_err(), error!(PoolRiskError::InvalidSlippage));
+
+        params.max_liquidity = params.min_liquidity;
+        assert_eq!(
+            params.validate(100).unwrap_err(),
+            error!(PoolRiskError::InvalidLiquidityBounds)
+        );
+    }
+}
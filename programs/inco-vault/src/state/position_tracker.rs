@@ -4,7 +4,32 @@
 //! - LP position reference (NFT mint)
 //! - Encrypted deposit amounts (token A and B via Inco handles)
 //! - Encrypted profit tracking (fees + 3 reward mints)
+//! - Reward mint binding per reward index
+//! - Cleartext profits collected but not yet encrypted
+//! - Optional keeper delegate for collect/rebalance
+//! - Active reward slot count
+//! - Lifetime liquidity compounded from fees and rewards
+//! - Pool sqrt price when the current position was opened
+//! - Pool token A/B mints, for validating token accounts without a pool read
+//! - Per-reward routing policy applied on collection
+//! - Optional display label (raw bytes; clients decode it as UTF-8)
+//! - Encrypted performance-fee high-water marks and the fee accrued above them
+//! - Owner freeze that locks out delegates and keepers
+//! - Collection sequence number for the realization ledger
+//! - Cleartext profit revealed at settlement
+//! - Admin-set performance fee rate override
+//! - Queued large withdrawal request
+//! - Attestation nonce
+//! - Owner-approved pool per reward for compound swaps
+//! - Whether a settled performance fee is still to be claimed
+//! - Rolling window of withdrawals made without a queued request
 //! - Position metadata (tick range, rebalance count)
+//! - Lifecycle status and cumulative withdrawals
+//!
+//! Migration note: fields added since the original layout are appended after
+//! `bump`, so trackers created with the original 243-byte layout must be
+//! migrated (`migrate_tracker`) before they deserialize against this struct.
+//! Migrated and newly created trackers carry `version == CURRENT_VERSION`.
 
 use anchor_lang::prelude::*;
 
@@ -62,6 +87,160 @@ pub struct PositionTracker {
     
     /// PDA bump seed
     pub bump: u8,
+    
+    // ========== LIFECYCLE ==========
+    /// Current lifecycle status
+    pub status: PositionStatus,
+    
+    /// Total liquidity withdrawn over the position's lifetime
+    pub total_withdrawn_liquidity: u128,
+    
+    // ========== REWARD BINDING ==========
+    /// Pool reward mints by index (default pubkey = uninitialized slot)
+    pub reward_mints: [Pubkey; 3],
+    
+    // ========== PENDING (COLLECTED, NOT YET ENCRYPTED) ==========
+    /// Token A fees awaiting encryption
+    pub pending_fee_a: u64,
+    
+    /// Token B fees awaiting encryption
+    pub pending_fee_b: u64,
+    
+    /// Rewards awaiting encryption, by reward index
+    pub pending_rewards: [u64; 3],
+    
+    // ========== LAYOUT ==========
+    /// Account layout version (see `migrate_tracker`)
+    pub version: u8,
+    
+    // ========== DELEGATION ==========
+    /// Keeper allowed to collect/rebalance for the owner (default = none)
+    pub delegate: Pubkey,
+    
+    /// Number of active pool reward slots (indexes `0..reward_count`)
+    pub reward_count: u8,
+    
+    // ========== COMPOUNDING ==========
+    /// Liquidity added back to the position by `collect_and_compound`
+    pub lifetime_compounded: u128,
+    
+    // ========== ENTRY PRICE ==========
+    /// Pool sqrt price (Q64.64) when the current position was opened
+    pub entry_sqrt_price: u128,
+    
+    // ========== POOL MINTS ==========
+    /// Whirlpool token A mint
+    pub token_mint_a: Pubkey,
+    
+    /// Whirlpool token B mint
+    pub token_mint_b: Pubkey,
+    
+    // ========== REWARD ROUTING ==========
+    /// What `collect_all_profits` does with each collected reward, by index
+    pub reward_routes: [RewardRoute; 3],
+    
+    // ========== DISPLAY ==========
+    /// Client display label, zero-padded (all zeros = no label)
+    pub label: [u8; 32],
+    
+    // ========== PERFORMANCE FEE HIGH-WATER MARK ==========
+    /// Realized profit A at the last fee settlement (0 = never settled)
+    pub encrypted_high_water_mark_a: u128,
+    
+    /// Realized profit B at the last fee settlement (0 = never settled)
+    pub encrypted_high_water_mark_b: u128,
+    
+    /// ebool handle `profit_a > high_water_mark_a` awaiting decryption (0 = none)
+    pub hwm_check_a: u128,
+    
+    /// ebool handle `profit_b > high_water_mark_b` awaiting decryption (0 = none)
+    pub hwm_check_b: u128,
+    
+    /// Performance fee A owed, scaled by 10_000 (Inco has no encrypted division)
+    pub encrypted_performance_fee_a: u128,
+    
+    /// Performance fee B owed, scaled by 10_000
+    pub encrypted_performance_fee_b: u128,
+    
+    // ========== OWNER FREEZE ==========
+    /// Set by the owner; only the owner may act on the position while true
+    pub frozen: bool,
+    
+    // ========== REALIZATION LEDGER ==========
+    /// Collections recorded so far; numbers each `ProfitRealized` event
+    pub collect_count: u32,
+    
+    // ========== SETTLEMENT ==========
+    /// Realized profit A revealed by `settle_position` (0 until settled)
+    pub settled_profit_a: u128,
+    /// Realized profit B revealed by `settle_position` (0 until settled)
+    pub settled_profit_b: u128,
+    
+    // ========== FEE TIER ==========
+    /// Admin-set performance fee rate for this position, replacing
+    /// `VaultConfig::performance_fee_bps` when set
+    pub fee_bps_override: Option<u16>,
+    
+    // ========== WITHDRAWAL QUEUE ==========
+    /// Liquidity of the pending large-withdrawal request (0 = none)
+    pub requested_withdrawal_liquidity: u128,
+    /// Earliest time the requested withdrawal may execute
+    pub withdrawal_ready_at: i64,
+    
+    // ========== ATTESTATION ==========
+    /// Attestations accepted by `collect_and_attest`; numbers each `ProfitAttested`
+    pub attest_nonce: u64,
+    
+    // ========== COMPOUND SWAPS ==========
+    /// Pool `collect_and_compound` may swap each reward through, set by the
+    /// owner (default = none; only the position's own pool is allowed)
+    pub reward_swap_pools: [Pubkey; 3],
+    
+    // ========== FEE CLAIM ==========
+    /// Settled performance fee not yet claimed (counted once in
+    /// `VaultPDA::unclaimed_fee_positions`)
+    pub performance_fee_owed: bool,
+    
+    // ========== UN-QUEUED WITHDRAWALS ==========
+    /// Start of the current window of withdrawals made without a request
+    pub unqueued_window_start: i64,
+    /// Liquidity withdrawn without a request since `unqueued_window_start`
+    pub unqueued_window_liquidity: u128,
+}
+
+/// Lifecycle status of a tracked position
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PositionStatus {
+    /// Position is open with its full deposit
+    Open,
+    /// Some liquidity has been withdrawn
+    PartiallyWithdrawn,
+    /// Position has been closed on Whirlpool
+    Closed,
+}
+
+/// Destination of a collected reward
+///
+/// Only `Track` rewards are encrypted into the tracker; the others leave its
+/// accounting as soon as they are collected.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RewardRoute {
+    /// Held in the vault and folded into the encrypted reward total
+    #[default]
+    Track,
+    /// Held in a vault-owned account for `collect_and_compound` to reinvest
+    Compound,
+    /// Collected straight into the owner's token account
+    ClaimToUser,
+    /// Collected straight into the fee recipient's token account
+    ToTreasury,
+}
+
+impl RewardRoute {
+    /// Whether the reward is collected into a vault-owned account
+    pub fn is_vault_held(self) -> bool {
+        matches!(self, RewardRoute::Track | RewardRoute::Compound)
+    }
 }
 
 impl PositionTracker {
@@ -82,10 +261,71 @@ impl PositionTracker {
         4 +     // tick_upper
         2 +     // rebalance_count
         8 +     // last_update
-        1;      // bump
-        // Total: 233 bytes
+        1 +     // bump
+        1 +     // status
+        16 +    // total_withdrawn_liquidity
+        96 +    // reward_mints
+        8 +     // pending_fee_a
+        8 +     // pending_fee_b
+        24 +    // pending_rewards
+        1 +     // version
+        32 +    // delegate
+        1 +     // reward_count
+        16 +    // lifetime_compounded
+        16 +    // entry_sqrt_price
+        32 +    // token_mint_a
+        32 +    // token_mint_b
+        3 +     // reward_routes
+        32 +    // label
+        16 +    // encrypted_high_water_mark_a
+        16 +    // encrypted_high_water_mark_b
+        16 +    // hwm_check_a
+        16 +    // hwm_check_b
+        16 +    // encrypted_performance_fee_a
+        16 +    // encrypted_performance_fee_b
+        1 +     // frozen
+        4 +     // collect_count
+        16 +    // settled_profit_a
+        16 +    // settled_profit_b
+        3 +     // fee_bps_override (Option<u16>)
+        16 +    // requested_withdrawal_liquidity
+        8 +     // withdrawal_ready_at
+        8 +     // attest_nonce
+        96 +    // reward_swap_pools
+        1 +     // performance_fee_owed
+        8 +     // unqueued_window_start
+        16;     // unqueued_window_liquidity
+        // Total: 850 bytes
+
+    /// Size of the original (pre-versioning) layout
+    pub const LEGACY_LEN: usize = 243;
+
+    /// Byte offset of `version` (fixed; later fields are appended after it)
+    pub const VERSION_OFFSET: usize = 396;
+
+    /// Current account layout version
+    /// - 1: lifecycle, reward mints, pending profits
+    /// - 2: delegate
+    /// - 3: reward_count
+    /// - 4: lifetime_compounded
+    /// - 5: entry_sqrt_price
+    /// - 6: token_mint_a/b (filled from the pool by `migrate_tracker`)
+    /// - 7: reward_routes
+    /// - 8: label
+    /// - 9: performance-fee high-water marks
+    /// - 10: frozen
+    /// - 11: collect_count
+    /// - 12: settled_profit_a/b
+    /// - 13: fee_bps_override
+    /// - 14: requested_withdrawal_liquidity, withdrawal_ready_at
+    /// - 15: attest_nonce
+    /// - 16: reward_swap_pools
+    /// - 17: performance_fee_owed
+    /// - 18: unqueued_window_start, unqueued_window_liquidity
+    pub const CURRENT_VERSION: u8 = 18;
 
     /// Initialize a new position tracker
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         user: Pubkey,
@@ -96,6 +336,10 @@ impl PositionTracker {
         tick_lower: i32,
         tick_upper: i32,
         bump: u8,
+        reward_mints: [Pubkey; 3],
+        reward_count: u8,
+        entry_sqrt_price: u128,
+        token_mints: (Pubkey, Pubkey),
     ) -> Result<()> {
         self.user = user;
         self.lp_position_mint = lp_position_mint;
@@ -113,21 +357,454 @@ impl PositionTracker {
         self.rebalance_count = 0;
         self.last_update = self.deposit_timestamp;
         self.bump = bump;
+        self.status = PositionStatus::Open;
+        self.total_withdrawn_liquidity = 0;
+        self.reward_mints = reward_mints;
+        self.clear_pending_profits();
+        self.version = Self::CURRENT_VERSION;
+        self.delegate = Pubkey::default();
+        self.reward_count = reward_count;
+        self.lifetime_compounded = 0;
+        self.entry_sqrt_price = entry_sqrt_price;
+        (self.token_mint_a, self.token_mint_b) = token_mints;
+        self.reward_routes = [RewardRoute::Track; 3];
+        self.label = [0; 32];
+        self.encrypted_high_water_mark_a = 0;
+        self.encrypted_high_water_mark_b = 0;
+        self.hwm_check_a = 0;
+        self.hwm_check_b = 0;
+        self.encrypted_performance_fee_a = 0;
+        self.encrypted_performance_fee_b = 0;
+        self.frozen = false;
+        self.collect_count = 0;
+        self.settled_profit_a = 0;
+        self.settled_profit_b = 0;
+        self.fee_bps_override = None;
+        self.requested_withdrawal_liquidity = 0;
+        self.withdrawal_ready_at = 0;
+        self.attest_nonce = 0;
+        self.reward_swap_pools = [Pubkey::default(); 3];
+        self.performance_fee_owed = false;
+        self.unqueued_window_start = 0;
+        self.unqueued_window_liquidity = 0;
         Ok(())
     }
 
     /// Update position after rebalance
+    ///
+    /// `rebalance_count` is informational and saturates at `u16::MAX`, so a
+    /// long-lived position can keep rebalancing after it tops out.
     pub fn update_after_rebalance(
         &mut self,
         new_lp_position_mint: Pubkey,
         new_tick_lower: i32,
         new_tick_upper: i32,
-    ) -> Result<()> {
+        entry_sqrt_price: u128,
+        now: i64,
+    ) {
         self.lp_position_mint = new_lp_position_mint;
+        self.entry_sqrt_price = entry_sqrt_price;
         self.tick_lower = new_tick_lower;
         self.tick_upper = new_tick_upper;
         self.rebalance_count = self.rebalance_count.saturating_add(1);
+        self.last_update = now;
+    }
+
+    /// Replace the encrypted deposit handles after a top-up
+    pub fn record_deposit_increase(&mut self, encrypted_deposit_a: u128, encrypted_deposit_b: u128) -> Result<()> {
+        self.encrypted_deposit_a = encrypted_deposit_a;
+        self.encrypted_deposit_b = encrypted_deposit_b;
         self.last_update = Clock::get()?.unix_timestamp;
         Ok(())
     }
+
+    /// Record a withdrawal and advance the lifecycle status
+    pub fn record_withdrawal(&mut self, liquidity: u128, closed: bool, now: i64) -> Result<()> {
+        self.total_withdrawn_liquidity = self.total_withdrawn_liquidity
+            .checked_add(liquidity)
+            .ok_or(TrackerError::CounterOverflow)?;
+        self.status = if closed {
+            PositionStatus::Closed
+        } else {
+            PositionStatus::PartiallyWithdrawn
+        };
+        self.last_update = now;
+        Ok(())
+    }
+
+    /// Accumulate collected profits that still need encrypting
+    pub fn add_pending_profits(&mut self, fee_a: u64, fee_b: u64, rewards: [u64; 3]) -> Result<()> {
+        self.pending_fee_a = self.pending_fee_a
+            .checked_add(fee_a)
+            .ok_or(TrackerError::PendingProfitOverflow)?;
+        self.pending_fee_b = self.pending_fee_b
+            .checked_add(fee_b)
+            .ok_or(TrackerError::PendingProfitOverflow)?;
+        for (pending, reward) in self.pending_rewards.iter_mut().zip(rewards) {
+            *pending = pending
+                .checked_add(reward)
+                .ok_or(TrackerError::PendingProfitOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Keep only the collected rewards routed to `Track`
+    pub fn tracked_rewards(&self, rewards: [u64; 3]) -> [u64; 3] {
+        let mut tracked = rewards;
+        for (amount, route) in tracked.iter_mut().zip(self.reward_routes) {
+            if route != RewardRoute::Track {
+                *amount = 0;
+            }
+        }
+        tracked
+    }
+
+    /// Whether any collected profit is awaiting encryption
+    pub fn has_pending_profits(&self) -> bool {
+        self.pending_fee_a > 0
+            || self.pending_fee_b > 0
+            || self.pending_rewards.iter().any(|&r| r > 0)
+    }
+
+    /// Whether any pending reward is large enough to be encrypted
+    ///
+    /// Rewards below `min_collect_threshold` are dust that stays pending
+    /// until it accumulates, and does not count as awaiting encryption.
+    pub fn has_encryptable_rewards(&self, min_collect_threshold: u64) -> bool {
+        self.pending_rewards
+            .iter()
+            .any(|&r| r > 0 && r >= min_collect_threshold)
+    }
+
+    /// Reset pending profits after they have been encrypted
+    pub fn clear_pending_profits(&mut self) {
+        self.pending_fee_a = 0;
+        self.pending_fee_b = 0;
+        self.pending_rewards = [0; 3];
+    }
+
+    /// Record liquidity compounded back into the position
+    pub fn record_compounded(&mut self, liquidity: u128) -> Result<()> {
+        self.lifetime_compounded = self.lifetime_compounded
+            .checked_add(liquidity)
+            .ok_or(TrackerError::CounterOverflow)?;
+        Ok(())
+    }
+
+    /// Number the next collection, returning its sequence and the position age
+    ///
+    /// Age is seconds since `deposit_timestamp` (floored at zero).
+    pub fn record_collection(&mut self, now: i64) -> Result<(u32, i64)> {
+        self.collect_count = self.collect_count
+            .checked_add(1)
+            .ok_or(TrackerError::CounterOverflow)?;
+        Ok((self.collect_count, now.saturating_sub(self.deposit_timestamp).max(0)))
+    }
+
+    /// Number the next accepted attestation
+    pub fn record_attestation(&mut self) -> Result<u64> {
+        self.attest_nonce = self.attest_nonce
+            .checked_add(1)
+            .ok_or(TrackerError::CounterOverflow)?;
+        Ok(self.attest_nonce)
+    }
+
+    /// Queue a large withdrawal of `liquidity`, executable from `ready_at`
+    pub fn request_withdrawal(&mut self, liquidity: u128, ready_at: i64) -> Result<()> {
+        require!(liquidity > 0, TrackerError::InvalidWithdrawalRequest);
+        require!(
+            self.requested_withdrawal_liquidity == 0,
+            TrackerError::WithdrawalAlreadyRequested
+        );
+        self.requested_withdrawal_liquidity = liquidity;
+        self.withdrawal_ready_at = ready_at;
+        Ok(())
+    }
+
+    /// Consume the pending request for a withdrawal of exactly `liquidity`
+    pub fn take_withdrawal_request(&mut self, liquidity: u128, now: i64) -> Result<()> {
+        require!(
+            self.requested_withdrawal_liquidity != 0
+                && self.requested_withdrawal_liquidity == liquidity,
+            TrackerError::WithdrawalNotRequested
+        );
+        require!(now >= self.withdrawal_ready_at, TrackerError::WithdrawalNotReady);
+        self.cancel_withdrawal_request()
+    }
+
+    /// Liquidity withdrawn without a request in the window of `window_secs`
+    /// that is open at `now` (0 once it has expired)
+    pub fn unqueued_window_total(&self, window_secs: i64, now: i64) -> u128 {
+        if now.saturating_sub(self.unqueued_window_start) >= window_secs {
+            0
+        } else {
+            self.unqueued_window_liquidity
+        }
+    }
+
+    /// Add a withdrawal made without a request to the current window,
+    /// starting a new one at `now` if it has expired
+    pub fn record_unqueued_withdrawal(&mut self, liquidity: u128, window_secs: i64, now: i64) -> Result<()> {
+        if now.saturating_sub(self.unqueued_window_start) >= window_secs {
+            self.unqueued_window_start = now;
+            self.unqueued_window_liquidity = 0;
+        }
+        self.unqueued_window_liquidity = self.unqueued_window_liquidity
+            .checked_add(liquidity)
+            .ok_or(TrackerError::CounterOverflow)?;
+        Ok(())
+    }
+
+    /// Drop the pending withdrawal request
+    pub fn cancel_withdrawal_request(&mut self) -> Result<()> {
+        require!(
+            self.requested_withdrawal_liquidity != 0,
+            TrackerError::WithdrawalNotRequested
+        );
+        self.requested_withdrawal_liquidity = 0;
+        self.withdrawal_ready_at = 0;
+        Ok(())
+    }
+
+    /// Whether `whirlpool` is the pool this position was opened in
+    ///
+    /// Every instruction that takes the pool next to the tracker checks this,
+    /// so a position can't be priced or moved through a different pool.
+    pub fn is_for_whirlpool(&self, whirlpool: &Pubkey) -> bool {
+        *whirlpool == self.whirlpool
+    }
+
+    /// Whether `key` is the owner or the configured delegate
+    pub fn is_owner_or_delegate(&self, key: &Pubkey) -> bool {
+        *key == self.user || (self.delegate != Pubkey::default() && *key == self.delegate)
+    }
+
+    /// Whether `key` is acting as the delegate rather than the owner
+    pub fn is_delegate_action(&self, key: &Pubkey) -> bool {
+        *key != self.user && *key == self.delegate
+    }
+
+    /// Check `key` may act on the position given its freeze state
+    ///
+    /// A frozen position accepts only its owner; delegates and keepers are
+    /// rejected until the owner unfreezes it.
+    pub fn require_not_frozen_for(&self, key: &Pubkey) -> Result<()> {
+        require!(!self.frozen || *key == self.user, TrackerError::PositionFrozen);
+        Ok(())
+    }
+
+    /// Check the position has not been closed
+    pub fn require_not_closed(&self) -> Result<()> {
+        require!(self.status != PositionStatus::Closed, TrackerError::PositionClosed);
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum TrackerError {
+    #[msg("Position is closed")]
+    PositionClosed,
+    #[msg("Pending profit overflow")]
+    PendingProfitOverflow,
+    #[msg("Lifetime counter overflow")]
+    CounterOverflow,
+    #[msg("Position is frozen by its owner")]
+    PositionFrozen,
+    #[msg("Withdrawal request must be for a positive amount of liquidity")]
+    InvalidWithdrawalRequest,
+    #[msg("A withdrawal request is already pending; cancel it first")]
+    WithdrawalAlreadyRequested,
+    #[msg("No pending withdrawal request for this amount")]
+    WithdrawalNotRequested,
+    #[msg("Requested withdrawal is not ready yet")]
+    WithdrawalNotReady,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_tracker() -> PositionTracker {
+        // `None` options serialize short, so trailing zeros are left unread
+        PositionTracker::deserialize(&mut &[0u8; PositionTracker::LEN - 8][..]).unwrap()
+    }
+
+    #[test]
+    fn test_lifetime_compounded_overflow() {
+        let mut tracker = empty_tracker();
+        tracker.record_compounded(10).unwrap();
+        assert_eq!(tracker.lifetime_compounded, 10);
+
+        tracker.lifetime_compounded = u128::MAX;
+        assert_eq!(
+            tracker.record_compounded(1).unwrap_err(),
+            error!(TrackerError::CounterOverflow)
+        );
+        assert_eq!(tracker.lifetime_compounded, u128::MAX);
+    }
+
+    #[test]
+    fn test_only_tracked_rewards_are_kept() {
+        let mut tracker = empty_tracker();
+        assert_eq!(tracker.reward_routes, [RewardRoute::Track; 3]);
+        assert_eq!(tracker.tracked_rewards([1, 2, 3]), [1, 2, 3]);
+
+        tracker.reward_routes = [RewardRoute::Compound, RewardRoute::Track, RewardRoute::ToTreasury];
+        assert_eq!(tracker.tracked_rewards([1, 2, 3]), [0, 2, 0]);
+    }
+
+    #[test]
+    fn test_rejects_whirlpool_other_than_tracked() {
+        let mut tracker = empty_tracker();
+        tracker.whirlpool = Pubkey::new_unique();
+        tracker.lp_position_mint = Pubkey::new_unique();
+        assert!(tracker.is_for_whirlpool(&tracker.whirlpool));
+
+        // Another pool, or a key from the tracker that isn't its pool
+        assert!(!tracker.is_for_whirlpool(&Pubkey::new_unique()));
+        assert!(!tracker.is_for_whirlpool(&tracker.lp_position_mint));
+        assert!(!tracker.is_for_whirlpool(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_reward_dust_is_not_encryptable() {
+        let mut tracker = empty_tracker();
+        assert!(!tracker.has_encryptable_rewards(0));
+
+        tracker.pending_rewards = [0, 99, 0];
+        assert!(!tracker.has_encryptable_rewards(100));
+        assert!(tracker.has_encryptable_rewards(99));
+        assert!(tracker.has_encryptable_rewards(0));
+
+        // Pending fees never block a reward claim
+        tracker.pending_rewards = [0; 3];
+        tracker.pending_fee_a = 1_000;
+        assert!(!tracker.has_encryptable_rewards(0));
+    }
+
+    #[test]
+    fn test_rebalance_count_saturates() {
+        let mut tracker = empty_tracker();
+        let mint = Pubkey::new_unique();
+        tracker.update_after_rebalance(mint, -64, 64, 1 << 64, 1_000);
+        assert_eq!(tracker.rebalance_count, 1);
+        assert_eq!(tracker.lp_position_mint, mint);
+        assert_eq!((tracker.tick_lower, tracker.tick_upper), (-64, 64));
+        assert_eq!(tracker.last_update, 1_000);
+
+        // Topping out never blocks a rebalance
+        tracker.rebalance_count = u16::MAX;
+        tracker.update_after_rebalance(mint, -128, 128, 1 << 64, 2_000);
+        assert_eq!(tracker.rebalance_count, u16::MAX);
+        assert_eq!(tracker.tick_lower, -128);
+    }
+
+    #[test]
+    fn test_total_withdrawn_liquidity_accumulates() {
+        let mut tracker = empty_tracker();
+        tracker.record_withdrawal(400, false, 1_000).unwrap();
+        assert_eq!(tracker.total_withdrawn_liquidity, 400);
+        assert_eq!(tracker.status, PositionStatus::PartiallyWithdrawn);
+
+        tracker.record_withdrawal(600, true, 2_000).unwrap();
+        assert_eq!(tracker.total_withdrawn_liquidity, 1_000);
+        assert_eq!(tracker.status, PositionStatus::Closed);
+        assert_eq!(tracker.last_update, 2_000);
+
+        tracker.total_withdrawn_liquidity = u128::MAX;
+        assert_eq!(
+            tracker.record_withdrawal(1, true, 3_000).unwrap_err(),
+            error!(TrackerError::CounterOverflow)
+        );
+        assert_eq!(tracker.total_withdrawn_liquidity, u128::MAX);
+    }
+
+    #[test]
+    fn test_frozen_position_accepts_only_owner() {
+        let mut tracker = empty_tracker();
+        tracker.user = Pubkey::new_unique();
+        tracker.delegate = Pubkey::new_unique();
+        assert!(tracker.require_not_frozen_for(&tracker.delegate).is_ok());
+
+        tracker.frozen = true;
+        assert!(tracker.require_not_frozen_for(&tracker.user).is_ok());
+        assert_eq!(
+            tracker.require_not_frozen_for(&tracker.delegate).unwrap_err(),
+            error!(TrackerError::PositionFrozen)
+        );
+        assert_eq!(
+            tracker.require_not_frozen_for(&Pubkey::new_unique()).unwrap_err(),
+            error!(TrackerError::PositionFrozen)
+        );
+    }
+
+    #[test]
+    fn test_record_collection_numbers_and_ages() {
+        let mut tracker = empty_tracker();
+        tracker.deposit_timestamp = 1_000;
+        assert_eq!(tracker.record_collection(1_500).unwrap(), (1, 500));
+        assert_eq!(tracker.record_collection(900).unwrap(), (2, 0));
+
+        tracker.collect_count = u32::MAX;
+        assert_eq!(
+            tracker.record_collection(2_000).unwrap_err(),
+            error!(TrackerError::CounterOverflow)
+        );
+    }
+
+    #[test]
+    fn test_record_attestation_increments_nonce() {
+        let mut tracker = empty_tracker();
+        assert_eq!(tracker.record_attestation().unwrap(), 1);
+        assert_eq!(tracker.record_attestation().unwrap(), 2);
+
+        tracker.attest_nonce = u64::MAX;
+        assert_eq!(
+            tracker.record_attestation().unwrap_err(),
+            error!(TrackerError::CounterOverflow)
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_request_lifecycle() {
+        let mut tracker = empty_tracker();
+        assert_eq!(
+            tracker.request_withdrawal(0, 100).unwrap_err(),
+            error!(TrackerError::InvalidWithdrawalRequest)
+        );
+        tracker.request_withdrawal(5_000, 100).unwrap();
+        assert_eq!(
+            tracker.request_withdrawal(1, 100).unwrap_err(),
+            error!(TrackerError::WithdrawalAlreadyRequested)
+        );
+
+        assert_eq!(
+            tracker.take_withdrawal_request(4_999, 100).unwrap_err(),
+            error!(TrackerError::WithdrawalNotRequested)
+        );
+        assert_eq!(
+            tracker.take_withdrawal_request(5_000, 99).unwrap_err(),
+            error!(TrackerError::WithdrawalNotReady)
+        );
+        tracker.take_withdrawal_request(5_000, 100).unwrap();
+        assert_eq!(tracker.requested_withdrawal_liquidity, 0);
+        assert_eq!(
+            tracker.cancel_withdrawal_request().unwrap_err(),
+            error!(TrackerError::WithdrawalNotRequested)
+        );
+    }
+
+    #[test]
+    fn test_unqueued_withdrawals_add_up_within_window() {
+        let mut tracker = empty_tracker();
+        tracker.record_unqueued_withdrawal(400, 3_600, 10_000).unwrap();
+        tracker.record_unqueued_withdrawal(500, 3_600, 13_599).unwrap();
+        assert_eq!(tracker.unqueued_window_total(3_600, 13_599), 900);
+
+        // The window runs from the first withdrawal, not the latest
+        assert_eq!(tracker.unqueued_window_total(3_600, 13_600), 0);
+        tracker.record_unqueued_withdrawal(100, 3_600, 13_600).unwrap();
+        assert_eq!(tracker.unqueued_window_start, 13_600);
+        assert_eq!(tracker.unqueued_window_total(3_600, 13_600), 100);
+    }
 }
@@ -4,9 +4,26 @@
 //! - Admin address with 2-step rotation
 //! - Emergency pause functionality
 //! - Protocol parameters
+//! - Performance fee and referrer split
+//! - Hard ceiling on per-call slippage
+//! - Tick range width guardrails
+//! - Dust threshold below which collected profits are not yet encrypted
+//! - Safe band for the pool's current tick when opening positions
+//! - Inactivity period after which the admin may force-close a position
+//! - Cap on open positions per vault
+//! - One-way wind-down switch that disables new deposits
+//! - Accepted Inco attestation message versions
+//! - Pending fee recipient for 2-step rotation
+//! - Cap on handles checked per `verify_decryption` call
+//! - High-water-mark mode for the performance fee
+//! - Delayed withdrawal queue for large exits
 
 use anchor_lang::prelude::*;
 
+use crate::instructions::verify_decryption::{message_versions, VerifyError};
+use crate::math::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+use crate::state::PoolRiskParams;
+
 /// Global vault configuration with emergency controls
 #[account]
 pub struct VaultConfig {
@@ -33,6 +50,66 @@ pub struct VaultConfig {
     
     /// PDA bump seed
     pub bump: u8,
+    
+    /// Maximum aggregate liquidity across all positions in one vault
+    pub max_total_liquidity_per_vault: u128,
+    
+    /// Protocol performance fee on collected fees, in basis points
+    pub performance_fee_bps: u16,
+    
+    /// Wallet that receives the protocol performance fee
+    pub fee_recipient: Pubkey,
+    
+    /// Share of the performance fee paid to a vault's referrer, in basis points
+    pub referrer_share_bps: u16,
+    
+    /// Upper bound on any per-call `max_slippage_bps`, in basis points
+    pub absolute_max_slippage_bps: u16,
+    
+    /// Narrowest allowed position range (`tick_upper - tick_lower`)
+    pub min_tick_width: i32,
+    
+    /// Widest allowed position range (`tick_upper - tick_lower`)
+    pub max_tick_width: i32,
+    
+    /// Collected amounts below this stay pending instead of being encrypted
+    pub min_collect_threshold: u64,
+    
+    /// Lowest pool `tick_current_index` at which positions may be opened
+    pub safe_tick_lower: i32,
+    
+    /// Highest pool `tick_current_index` at which positions may be opened
+    pub safe_tick_upper: i32,
+    
+    /// Seconds without tracker activity before `admin_force_close` is allowed
+    pub abandonment_period: i64,
+    
+    /// Most positions a single vault may hold open at once
+    pub max_positions_per_vault: u32,
+    
+    /// Set once by `disable_deposits_forever`; never cleared
+    pub deposits_permanently_disabled: bool,
+    
+    /// Bitmask of attestation message versions `verify_decryption` accepts
+    pub accepted_message_versions: u8,
+    
+    /// Pending fee recipient for 2-step rotation
+    pub pending_fee_recipient: Pubkey,
+    
+    /// Most handles a single `verify_decryption` call may check
+    pub max_handles_per_verify: u8,
+    
+    /// Charge the performance fee in ciphertext above each position's
+    /// high-water mark (`settle_performance_fee`, paid out by
+    /// `claim_performance_fee`) instead of on gross fees
+    pub high_water_mark_fees: bool,
+    
+    /// Withdrawals of more liquidity than this must be requested
+    /// `withdrawal_delay` seconds in advance
+    pub large_withdrawal_threshold: u128,
+    
+    /// Seconds between `request_withdrawal` and a large withdrawal
+    pub withdrawal_delay: i64,
 }
 
 impl VaultConfig {
@@ -45,8 +122,31 @@ impl VaultConfig {
         2 +     // default_max_slippage_bps
         16 +    // min_liquidity
         16 +    // max_liquidity
-        1;      // bump
-        // Total: 116 bytes
+        1 +     // bump
+        16 +    // max_total_liquidity_per_vault
+        2 +     // performance_fee_bps
+        32 +    // fee_recipient
+        2 +     // referrer_share_bps
+        2 +     // absolute_max_slippage_bps
+        4 +     // min_tick_width
+        4 +     // max_tick_width
+        8 +     // min_collect_threshold
+        4 +     // safe_tick_lower
+        4 +     // safe_tick_upper
+        8 +     // abandonment_period
+        4 +     // max_positions_per_vault
+        1 +     // deposits_permanently_disabled
+        1 +     // accepted_message_versions
+        32 +    // pending_fee_recipient
+        1 +     // max_handles_per_verify
+        1 +     // high_water_mark_fees
+        16 +    // large_withdrawal_threshold
+        8;      // withdrawal_delay
+        // Total: 266 bytes
+
+    /// Size of the original layout (admin through bump), before any field
+    /// was appended
+    pub const LEGACY_LEN: usize = 116;
 
     /// Default minimum liquidity (dust protection)
     pub const DEFAULT_MIN_LIQUIDITY: u128 = 1_000;
@@ -56,6 +156,42 @@ impl VaultConfig {
     
     /// Default max slippage (1%)
     pub const DEFAULT_MAX_SLIPPAGE_BPS: u16 = 100;
+    
+    /// Absolute cap on any performance fee rate (100%)
+    pub const MAX_FEE_BPS: u16 = 10_000;
+    
+    /// Default slippage ceiling (100%, i.e. no cap)
+    pub const DEFAULT_ABSOLUTE_MAX_SLIPPAGE_BPS: u16 = 10_000;
+    
+    /// Default narrowest range (any non-empty range)
+    pub const DEFAULT_MIN_TICK_WIDTH: i32 = 1;
+    
+    /// Default widest range (the full tick space)
+    pub const DEFAULT_MAX_TICK_WIDTH: i32 = MAX_TICK_INDEX - MIN_TICK_INDEX;
+    
+    /// Default abandonment period (365 days)
+    pub const DEFAULT_ABANDONMENT_PERIOD: i64 = 365 * 24 * 60 * 60;
+    
+    /// Shortest abandonment period the admin may set (7 days)
+    pub const MIN_ABANDONMENT_PERIOD: i64 = 7 * 24 * 60 * 60;
+    
+    /// Default per-vault position cap (uncapped)
+    pub const DEFAULT_MAX_POSITIONS_PER_VAULT: u32 = u32::MAX;
+    
+    /// Default accepted message versions (v0 only)
+    pub const DEFAULT_ACCEPTED_MESSAGE_VERSIONS: u8 = 1 << message_versions::V0;
+    
+    /// Default cap on handles per `verify_decryption` call
+    pub const DEFAULT_MAX_HANDLES_PER_VERIFY: u8 = 16;
+    
+    /// Default large-withdrawal threshold (no withdrawal is queued)
+    pub const DEFAULT_LARGE_WITHDRAWAL_THRESHOLD: u128 = u128::MAX;
+    
+    /// Default delay before a queued withdrawal may execute (1 day)
+    pub const DEFAULT_WITHDRAWAL_DELAY: i64 = 86_400;
+    
+    /// Default per-vault liquidity cap (uncapped)
+    pub const DEFAULT_MAX_TOTAL_LIQUIDITY_PER_VAULT: u128 = u128::MAX;
 
     /// Initialize vault config
     pub fn initialize(&mut self, admin: Pubkey, bump: u8) {
@@ -67,6 +203,33 @@ impl VaultConfig {
         self.min_liquidity = Self::DEFAULT_MIN_LIQUIDITY;
         self.max_liquidity = Self::DEFAULT_MAX_LIQUIDITY;
         self.bump = bump;
+        self.set_appended_defaults();
+    }
+
+    /// Default every field appended after the legacy layout
+    ///
+    /// Shared by `initialize` and `migrate_config`; reads `admin`, which the
+    /// legacy layout already holds.
+    pub fn set_appended_defaults(&mut self) {
+        self.max_total_liquidity_per_vault = Self::DEFAULT_MAX_TOTAL_LIQUIDITY_PER_VAULT;
+        self.performance_fee_bps = 0;
+        self.fee_recipient = self.admin;
+        self.referrer_share_bps = 0;
+        self.absolute_max_slippage_bps = Self::DEFAULT_ABSOLUTE_MAX_SLIPPAGE_BPS;
+        self.min_tick_width = Self::DEFAULT_MIN_TICK_WIDTH;
+        self.max_tick_width = Self::DEFAULT_MAX_TICK_WIDTH;
+        self.min_collect_threshold = 0;
+        self.safe_tick_lower = MIN_TICK_INDEX;
+        self.safe_tick_upper = MAX_TICK_INDEX;
+        self.abandonment_period = Self::DEFAULT_ABANDONMENT_PERIOD;
+        self.max_positions_per_vault = Self::DEFAULT_MAX_POSITIONS_PER_VAULT;
+        self.deposits_permanently_disabled = false;
+        self.accepted_message_versions = Self::DEFAULT_ACCEPTED_MESSAGE_VERSIONS;
+        self.pending_fee_recipient = Pubkey::default();
+        self.max_handles_per_verify = Self::DEFAULT_MAX_HANDLES_PER_VERIFY;
+        self.high_water_mark_fees = false;
+        self.large_withdrawal_threshold = Self::DEFAULT_LARGE_WITHDRAWAL_THRESHOLD;
+        self.withdrawal_delay = Self::DEFAULT_WITHDRAWAL_DELAY;
     }
 
     /// Pause the vault
@@ -82,6 +245,43 @@ impl VaultConfig {
         self.pause_timestamp = 0;
     }
 
+    /// Permanently disable new deposits (wind-down; there is no way back)
+    pub fn disable_deposits_forever(&mut self) {
+        self.deposits_permanently_disabled = true;
+    }
+
+    /// Check new deposits are still accepted
+    pub fn require_deposits_enabled(&self) -> Result<()> {
+        require!(
+            !self.deposits_permanently_disabled,
+            ConfigError::DepositsPermanentlyDisabled
+        );
+        Ok(())
+    }
+
+    /// Check an attestation message version is currently accepted
+    pub fn require_message_version(&self, message_version: u8) -> Result<()> {
+        require!(
+            message_version < 8 && self.accepted_message_versions & (1 << message_version) != 0,
+            VerifyError::UnsupportedMessageVersion
+        );
+        Ok(())
+    }
+
+    /// Whether withdrawing `liquidity` must go through the withdrawal queue
+    pub fn is_large_withdrawal(&self, liquidity: u128) -> bool {
+        liquidity > self.large_withdrawal_threshold
+    }
+
+    /// Check a `verify_decryption` call stays within the handle cap
+    pub fn require_handle_count(&self, num_handles: usize) -> Result<()> {
+        require!(
+            num_handles <= self.max_handles_per_verify as usize,
+            VerifyError::TooManyHandles
+        );
+        Ok(())
+    }
+
     /// Propose new admin (step 1 of rotation)
     pub fn propose_admin(&mut self, new_admin: Pubkey) {
         self.pending_admin = new_admin;
@@ -98,18 +298,127 @@ impl VaultConfig {
         Ok(())
     }
 
+    /// Propose new fee recipient (step 1 of rotation)
+    pub fn propose_fee_recipient(&mut self, new_recipient: Pubkey) {
+        self.pending_fee_recipient = new_recipient;
+    }
+
+    /// Accept the fee recipient role (step 2 of rotation)
+    pub fn accept_fee_recipient(&mut self, new_recipient: Pubkey) -> Result<()> {
+        require!(
+            self.pending_fee_recipient != Pubkey::default(),
+            ConfigError::NoPendingFeeRecipient
+        );
+        require!(
+            self.pending_fee_recipient == new_recipient,
+            ConfigError::NotPendingFeeRecipient
+        );
+        self.fee_recipient = new_recipient;
+        self.pending_fee_recipient = Pubkey::default();
+        Ok(())
+    }
+
+    /// Pause state as `(paused, pause_timestamp)`
+    pub fn pause_info(&self) -> (bool, i64) {
+        (self.paused, self.pause_timestamp)
+    }
+
     /// Check if vault is operational
+    ///
+    /// On rejection, logs when the vault was paused so clients can show
+    /// "paused since <time>"; the error code itself is unchanged.
     pub fn require_not_paused(&self) -> Result<()> {
-        require!(!self.paused, ConfigError::VaultPaused);
+        let (paused, pause_timestamp) = self.pause_info();
+        if paused {
+            msg!("Vault paused since {}", pause_timestamp);
+            return err!(ConfigError::VaultPaused);
+        }
+        Ok(())
+    }
+
+    /// Performance fee rate for a position: its override, else the vault rate
+    pub fn performance_fee_bps_for(&self, fee_bps_override: Option<u16>) -> u16 {
+        fee_bps_override.unwrap_or(self.performance_fee_bps)
+    }
+
+    /// Split the performance fee at `fee_bps` on `amount` into
+    /// (fee_recipient, referrer) shares
+    ///
+    /// Without a referrer the whole fee goes to `fee_recipient`. In
+    /// high-water-mark mode nothing is taken from gross fees; the fee accrues
+    /// per position in ciphertext instead.
+    pub fn performance_fee_split(&self, amount: u64, fee_bps: u16, has_referrer: bool) -> (u64, u64) {
+        if self.high_water_mark_fees {
+            return (0, 0);
+        }
+        // bps values are capped at 10_000, so neither product can overflow u128
+        let fee = (amount as u128 * fee_bps as u128 / 10_000) as u64;
+        let referrer_fee = if has_referrer {
+            (fee as u128 * self.referrer_share_bps as u128 / 10_000) as u64
+        } else {
+            0
+        };
+        (fee - referrer_fee, referrer_fee)
+    }
+
+    /// Resolve a per-call slippage, falling back to the pool's default if
+    /// `pool` is passed, else the global default
+    ///
+    /// Rejects values above `absolute_max_slippage_bps`.
+    pub fn resolve_slippage(
+        &self,
+        pool: Option<&PoolRiskParams>,
+        max_slippage_bps: Option<u16>,
+    ) -> Result<u16> {
+        let default = pool.map_or(self.default_max_slippage_bps, |p| p.default_max_slippage_bps);
+        let slippage = max_slippage_bps.unwrap_or(default);
+        require!(
+            slippage <= self.absolute_max_slippage_bps,
+            ConfigError::SlippageTooHigh
+        );
+        Ok(slippage)
+    }
+
+    /// Validate a position range's width against the configured bounds
+    ///
+    /// Ticks outside the Whirlpool tick space are rejected before the width
+    /// is computed, so pathological inputs never reach the subtraction.
+    pub fn validate_tick_width(&self, tick_lower: i32, tick_upper: i32) -> Result<()> {
+        let tick_space = MIN_TICK_INDEX..=MAX_TICK_INDEX;
+        require!(
+            tick_space.contains(&tick_lower) && tick_space.contains(&tick_upper),
+            ConfigError::TickOutOfBounds
+        );
+        let width = tick_upper
+            .checked_sub(tick_lower)
+            .ok_or(ConfigError::TickOverflow)?;
+        require!(width >= self.min_tick_width, ConfigError::TickRangeTooNarrow);
+        require!(width <= self.max_tick_width, ConfigError::TickRangeTooWide);
+        Ok(())
+    }
+
+    /// Reject pools whose current tick sits outside the safe band
+    pub fn validate_pool_tick(&self, tick_current_index: i32) -> Result<()> {
+        require!(
+            (self.safe_tick_lower..=self.safe_tick_upper).contains(&tick_current_index),
+            ConfigError::PoolTickOutOfSafeBand
+        );
         Ok(())
     }
 
-    /// Validate liquidity amount against bounds
-    pub fn validate_liquidity(&self, amount: u128) -> Result<()> {
-        require!(amount >= self.min_liquidity, ConfigError::LiquidityTooLow);
-        require!(amount <= self.max_liquidity, ConfigError::LiquidityTooHigh);
+    /// Validate liquidity amount against the pool's bounds if `pool` is
+    /// passed, else the global ones
+    pub fn validate_liquidity(&self, pool: Option<&PoolRiskParams>, amount: u128) -> Result<()> {
+        let max = pool.map_or(self.max_liquidity, |p| p.max_liquidity);
+        require!(amount >= self.min_liquidity_for(pool), ConfigError::LiquidityTooLow);
+        require!(amount <= max, ConfigError::LiquidityTooHigh);
         Ok(())
     }
+
+    /// Minimum position liquidity: the pool's if `pool` is passed, else the global one
+    pub fn min_liquidity_for(&self, pool: Option<&PoolRiskParams>) -> u128 {
+        pool.map_or(self.min_liquidity, |p| p.min_liquidity)
+    }
 }
 
 #[error_code]
@@ -122,4 +431,162 @@ pub enum ConfigError {
     LiquidityTooLow,
     #[msg("Liquidity amount too high")]
     LiquidityTooHigh,
+    #[msg("Slippage exceeds the configured ceiling")]
+    SlippageTooHigh,
+    #[msg("Tick range is narrower than the configured minimum")]
+    TickRangeTooNarrow,
+    #[msg("Tick range is wider than the configured maximum")]
+    TickRangeTooWide,
+    #[msg("Pool tick is outside the configured safe band")]
+    PoolTickOutOfSafeBand,
+    #[msg("Deposits are permanently disabled")]
+    DepositsPermanentlyDisabled,
+    #[msg("Tick index is outside the Whirlpool tick range")]
+    TickOutOfBounds,
+    #[msg("Tick width overflow")]
+    TickOverflow,
+    #[msg("No fee recipient rotation is pending")]
+    NoPendingFeeRecipient,
+    #[msg("Not the pending fee recipient")]
+    NotPendingFeeRecipient,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_config() -> VaultConfig {
+        let mut config = VaultConfig::try_from_slice(&[0u8; VaultConfig::LEN - 8]).unwrap();
+        config.initialize(Pubkey::default(), 255);
+        config
+    }
+
+    #[test]
+    fn test_migrated_legacy_config_matches_initialize() {
+        let initialized = default_config();
+
+        // Legacy bytes zero-extended, as `migrate_config` reallocs them
+        let mut data = vec![0u8; VaultConfig::LEN - 8];
+        data[..VaultConfig::LEGACY_LEN - 8]
+            .copy_from_slice(&initialized.try_to_vec().unwrap()[..VaultConfig::LEGACY_LEN - 8]);
+        let mut migrated = VaultConfig::try_from_slice(&data).unwrap();
+        migrated.set_appended_defaults();
+
+        assert_eq!(migrated.try_to_vec().unwrap(), initialized.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_tick_width_rejects_extreme_ticks() {
+        let config = default_config();
+        for (lower, upper) in [
+            (i32::MIN, i32::MAX),
+            (i32::MIN, 0),
+            (0, i32::MAX),
+            (MIN_TICK_INDEX - 1, 0),
+            (0, MAX_TICK_INDEX + 1),
+        ] {
+            assert_eq!(
+                config.validate_tick_width(lower, upper).unwrap_err(),
+                error!(ConfigError::TickOutOfBounds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_tick_width_full_range() {
+        let config = default_config();
+        assert!(config.validate_tick_width(MIN_TICK_INDEX, MAX_TICK_INDEX).is_ok());
+        assert_eq!(
+            config.validate_tick_width(MAX_TICK_INDEX, MIN_TICK_INDEX).unwrap_err(),
+            error!(ConfigError::TickRangeTooNarrow)
+        );
+    }
+
+    #[test]
+    fn test_fee_recipient_rotation() {
+        let mut config = default_config();
+        let recipient = Pubkey::new_unique();
+        assert_eq!(
+            config.accept_fee_recipient(Pubkey::default()).unwrap_err(),
+            error!(ConfigError::NoPendingFeeRecipient)
+        );
+
+        config.propose_fee_recipient(recipient);
+        assert_eq!(
+            config.accept_fee_recipient(Pubkey::new_unique()).unwrap_err(),
+            error!(ConfigError::NotPendingFeeRecipient)
+        );
+        assert_eq!(config.fee_recipient, Pubkey::default());
+
+        config.accept_fee_recipient(recipient).unwrap();
+        assert_eq!(config.fee_recipient, recipient);
+        assert_eq!(config.pending_fee_recipient, Pubkey::default());
+    }
+
+    #[test]
+    fn test_handle_count_cap() {
+        let config = default_config();
+        let max = VaultConfig::DEFAULT_MAX_HANDLES_PER_VERIFY as usize;
+        assert!(config.require_handle_count(max).is_ok());
+        assert_eq!(
+            config.require_handle_count(max + 1).unwrap_err(),
+            error!(VerifyError::TooManyHandles)
+        );
+    }
+
+    #[test]
+    fn test_high_water_mark_mode_skips_gross_fee() {
+        let mut config = default_config();
+        config.performance_fee_bps = 1_000;
+        assert_eq!(config.performance_fee_split(1_000, 1_000, false), (100, 0));
+
+        config.high_water_mark_fees = true;
+        assert_eq!(config.performance_fee_split(1_000, 1_000, false), (0, 0));
+    }
+
+    #[test]
+    fn test_fee_override_takes_precedence() {
+        let mut config = default_config();
+        config.performance_fee_bps = 1_000;
+        config.referrer_share_bps = 5_000;
+        assert_eq!(config.performance_fee_bps_for(None), 1_000);
+        assert_eq!(config.performance_fee_bps_for(Some(0)), 0);
+
+        let vip = config.performance_fee_bps_for(Some(200));
+        assert_eq!(vip, 200);
+        assert_eq!(config.performance_fee_split(1_000, vip, true), (10, 10));
+    }
+
+    #[test]
+    fn test_pool_risk_params_take_precedence() {
+        let mut config = default_config();
+        config.min_liquidity = 10;
+        config.max_liquidity = 100;
+        config.default_max_slippage_bps = 100;
+        config.absolute_max_slippage_bps = 500;
+        let pool = PoolRiskParams {
+            whirlpool: Pubkey::new_unique(),
+            min_liquidity: 1_000,
+            max_liquidity: 10_000,
+            default_max_slippage_bps: 20,
+            bump: 255,
+        };
+
+        assert!(config.validate_liquidity(None, 50).is_ok());
+        assert_eq!(
+            config.validate_liquidity(Some(&pool), 50).unwrap_err(),
+            error!(ConfigError::LiquidityTooLow)
+        );
+        assert!(config.validate_liquidity(Some(&pool), 5_000).is_ok());
+        assert_eq!(config.min_liquidity_for(None), 10);
+        assert_eq!(config.min_liquidity_for(Some(&pool)), 1_000);
+
+        assert_eq!(config.resolve_slippage(None, None).unwrap(), 100);
+        assert_eq!(config.resolve_slippage(Some(&pool), None).unwrap(), 20);
+        // The global ceiling still binds explicit values
+        assert_eq!(
+            config.resolve_slippage(Some(&pool), Some(600)).unwrap_err(),
+            error!(ConfigError::SlippageTooHigh)
+        );
+    }
 }
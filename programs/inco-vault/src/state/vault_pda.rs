@@ -4,7 +4,11 @@
 //! - Owns user token accounts for deposit/withdraw
 //! - Owns LP position token accounts (holds NFTs)
 //! - Signs CPI calls to Whirlpool program
-//! - Includes reentrancy guard
+//! - Includes reentrancy guard (with the slot it was taken, for stale-lock recovery)
+//! - Traces lock transitions as `VaultLockChanged` events in `lock-trace` builds
+//! - Accumulates lifetime fee totals for analytics
+//! - Records an optional referrer sharing the performance fee
+//! - Counts positions with a settled performance fee still to be claimed
 
 use anchor_lang::prelude::*;
 
@@ -22,6 +26,25 @@ pub struct VaultPDA {
     
     /// PDA bump seed
     pub bump: u8,
+    
+    /// Aggregate liquidity across all positions in this vault
+    pub total_liquidity: u128,
+    
+    /// Cleartext token A fees collected over the vault's lifetime
+    pub lifetime_fees_a: u128,
+    
+    /// Cleartext token B fees collected over the vault's lifetime
+    pub lifetime_fees_b: u128,
+    
+    /// Referrer that earns a share of the performance fee (set at init)
+    pub referrer: Option<Pubkey>,
+    
+    /// Slot the reentrancy lock was last taken (0 when unlocked)
+    pub locked_at_slot: u64,
+    
+    /// Positions with a settled performance fee not yet claimed; the claim
+    /// pays from the vault's idle balances, so nothing else may move them
+    pub unclaimed_fee_positions: u32,
 }
 
 impl VaultPDA {
@@ -30,42 +53,267 @@ impl VaultPDA {
         32 +    // owner
         1 +     // locked
         4 +     // position_count
-        1;      // bump
-        // Total: 46 bytes
+        1 +     // bump
+        16 +    // total_liquidity
+        16 +    // lifetime_fees_a
+        16 +    // lifetime_fees_b
+        33 +    // referrer (Option<Pubkey>)
+        8 +     // locked_at_slot
+        4;      // unclaimed_fee_positions
+        // Total: 139 bytes
+
+    /// Size of the original layout (owner through bump); `migrate_vault`
+    /// zero-extends it
+    pub const LEGACY_LEN: usize = 46;
+
+    /// Size before `unclaimed_fee_positions` was appended; also zero-extended
+    pub const PRE_FEE_CLAIM_LEN: usize = 135;
+
+    /// Slots after which a lock left behind by a prior transaction counts
+    /// as stale (~1 minute)
+    pub const STALE_LOCK_SLOTS: u64 = 150;
 
     /// Initialize a new vault
-    pub fn initialize(&mut self, owner: Pubkey, bump: u8) {
+    pub fn initialize(&mut self, owner: Pubkey, bump: u8, referrer: Option<Pubkey>) {
         self.owner = owner;
         self.locked = false;
         self.position_count = 0;
         self.bump = bump;
+        self.total_liquidity = 0;
+        self.lifetime_fees_a = 0;
+        self.lifetime_fees_b = 0;
+        self.referrer = referrer;
+        self.locked_at_slot = 0;
+        self.unclaimed_fee_positions = 0;
     }
 
     /// Lock the vault (reentrancy guard)
-    pub fn lock(&mut self) -> Result<()> {
-        require!(!self.locked, VaultError::VaultLocked);
+    pub fn lock(&mut self, _cx: LockContext) -> Result<()> {
+        if self.locked {
+            msg!("Vault locked since slot {}", self.locked_at_slot);
+            return err!(VaultError::VaultLocked);
+        }
         self.locked = true;
+        self.locked_at_slot = Clock::get()?.slot;
+        #[cfg(feature = "lock-trace")]
+        _cx.trace(true, self.locked_at_slot);
+        Ok(())
+    }
+
+    /// Require the lock to be held (by the calling instruction)
+    ///
+    /// Guards the `_locked` instruction bodies, which composite instructions
+    /// run after taking the lock once themselves.
+    pub fn require_locked(&self) -> Result<()> {
+        require!(self.locked, VaultError::VaultNotLocked);
         Ok(())
     }
 
+    /// Whether the lock was taken at least `STALE_LOCK_SLOTS` before `current_slot`
+    pub fn is_lock_stale(&self, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.locked_at_slot) >= Self::STALE_LOCK_SLOTS
+    }
+
     /// Unlock the vault
-    pub fn unlock(&mut self) {
+    ///
+    /// The trace reports the slot the released lock was taken (0 if the
+    /// vault was not locked), so it pairs with the matching lock event.
+    pub fn unlock(&mut self, _cx: LockContext) {
+        #[cfg(feature = "lock-trace")]
+        _cx.trace(false, self.locked_at_slot);
         self.locked = false;
+        self.locked_at_slot = 0;
+    }
+
+    /// Run `f` while holding the vault's reentrancy lock
+    ///
+    /// `vault` projects the VaultPDA out of the handler's accounts so `f`
+    /// keeps access to all of them; `instruction` is the calling
+    /// instruction's discriminator, reported in lock traces. The lock is
+    /// released once `f` succeeds; on error the whole transaction reverts,
+    /// lock included.
+    pub fn with_lock<'info, A, T>(
+        accounts: &mut A,
+        vault: fn(&mut A) -> &mut Account<'info, VaultPDA>,
+        instruction: &'static [u8],
+        f: impl FnOnce(&mut A) -> Result<T>,
+    ) -> Result<T> {
+        let vault_pda = vault(accounts);
+        let cx = LockContext { vault: vault_pda.key(), instruction };
+        vault_pda.lock(cx)?;
+        let result = f(accounts)?;
+        vault(accounts).unlock(cx);
+        Ok(result)
     }
 
     /// Increment position count
-    pub fn increment_position_count(&mut self) {
-        self.position_count = self.position_count.saturating_add(1);
+    pub fn increment_position_count(&mut self) -> Result<()> {
+        self.position_count = self.position_count
+            .checked_add(1)
+            .ok_or(VaultError::CounterOverflow)?;
+        Ok(())
     }
 
-    /// Decrement position count (when a position is closed)
+    /// Decrement position count (when a position is closed; floors at zero)
     pub fn decrement_position_count(&mut self) {
         self.position_count = self.position_count.saturating_sub(1);
     }
+
+    /// Add liquidity to the vault total, enforcing the per-vault cap
+    pub fn add_liquidity(&mut self, amount: u128, cap: u128) -> Result<()> {
+        let new_total = self.total_liquidity
+            .checked_add(amount)
+            .ok_or(VaultError::VaultCapExceeded)?;
+        require!(new_total <= cap, VaultError::VaultCapExceeded);
+        self.total_liquidity = new_total;
+        Ok(())
+    }
+
+    /// Record fees collected into the lifetime totals
+    pub fn record_fees(&mut self, fee_a: u64, fee_b: u64) -> Result<()> {
+        self.lifetime_fees_a = self.lifetime_fees_a
+            .checked_add(fee_a as u128)
+            .ok_or(VaultError::CounterOverflow)?;
+        self.lifetime_fees_b = self.lifetime_fees_b
+            .checked_add(fee_b as u128)
+            .ok_or(VaultError::CounterOverflow)?;
+        Ok(())
+    }
+
+    /// Remove liquidity from the vault total
+    pub fn remove_liquidity(&mut self, amount: u128) {
+        self.total_liquidity = self.total_liquidity.saturating_sub(amount);
+    }
+
+    /// Count a position whose settled performance fee is now owed
+    pub fn open_fee_claim(&mut self) -> Result<()> {
+        self.unclaimed_fee_positions = self.unclaimed_fee_positions
+            .checked_add(1)
+            .ok_or(VaultError::CounterOverflow)?;
+        Ok(())
+    }
+
+    /// Stop counting a position once its fee is claimed (floors at zero)
+    pub fn close_fee_claim(&mut self) {
+        self.unclaimed_fee_positions = self.unclaimed_fee_positions.saturating_sub(1);
+    }
+
+    /// Reject moving idle balances while a performance fee is owed from them
+    pub fn require_no_fee_owed(&self) -> Result<()> {
+        require!(self.unclaimed_fee_positions == 0, VaultError::PerformanceFeeOwed);
+        Ok(())
+    }
+}
+
+/// Which vault is locked or unlocked, and by which instruction
+#[derive(Clone, Copy)]
+pub struct LockContext {
+    pub vault: Pubkey,
+    /// Discriminator of the instruction taking or releasing the lock
+    pub instruction: &'static [u8],
+}
+
+impl LockContext {
+    #[cfg(feature = "lock-trace")]
+    fn trace(&self, locked: bool, slot: u64) {
+        emit!(VaultLockChanged {
+            vault: self.vault,
+            locked,
+            slot,
+            instruction_discriminant: self.instruction.to_vec(),
+        });
+    }
+}
+
+/// Lock transition, emitted only in `lock-trace` builds
+#[cfg(feature = "lock-trace")]
+#[event]
+pub struct VaultLockChanged {
+    pub vault: Pubkey,
+    pub locked: bool,
+    /// Slot the lock was taken (`locked_at_slot`)
+    pub slot: u64,
+    pub instruction_discriminant: Vec<u8>,
 }
 
 #[error_code]
 pub enum VaultError {
     #[msg("Vault is locked - operation in progress")]
     VaultLocked,
+    #[msg("Vault total liquidity cap exceeded")]
+    VaultCapExceeded,
+    #[msg("Lifetime counter overflow")]
+    CounterOverflow,
+    #[msg("Vault lock must already be held by the calling instruction")]
+    VaultNotLocked,
+    #[msg("A settled performance fee must be claimed before moving vault balances")]
+    PerformanceFeeOwed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_vault() -> VaultPDA {
+        // Zeroed bytes; `referrer: None` leaves trailing padding unread
+        VaultPDA::deserialize(&mut &[0u8; VaultPDA::LEN - 8][..]).unwrap()
+    }
+
+    #[test]
+    fn test_position_count_overflow() {
+        let mut vault = empty_vault();
+        vault.position_count = u32::MAX;
+        assert_eq!(
+            vault.increment_position_count().unwrap_err(),
+            error!(VaultError::CounterOverflow)
+        );
+        assert_eq!(vault.position_count, u32::MAX);
+    }
+
+    #[test]
+    fn test_position_count_decrement_floors_at_zero() {
+        let mut vault = empty_vault();
+        vault.decrement_position_count();
+        assert_eq!(vault.position_count, 0);
+    }
+
+    #[test]
+    fn test_fee_claims_block_idle_balances() {
+        let mut vault = empty_vault();
+        assert!(vault.require_no_fee_owed().is_ok());
+
+        vault.open_fee_claim().unwrap();
+        vault.open_fee_claim().unwrap();
+        assert_eq!(vault.require_no_fee_owed().unwrap_err(), error!(VaultError::PerformanceFeeOwed));
+        vault.close_fee_claim();
+        assert_eq!(vault.require_no_fee_owed().unwrap_err(), error!(VaultError::PerformanceFeeOwed));
+        vault.close_fee_claim();
+        vault.close_fee_claim();
+        assert!(vault.require_no_fee_owed().is_ok());
+    }
+
+    #[test]
+    fn test_require_locked() {
+        let mut vault = empty_vault();
+        assert_eq!(vault.require_locked().unwrap_err(), error!(VaultError::VaultNotLocked));
+
+        // Taking the lock directly (as `lock` does, minus the Clock sysvar)
+        vault.locked = true;
+        assert!(vault.require_locked().is_ok());
+        vault.unlock(LockContext { vault: Pubkey::default(), instruction: &[] });
+        assert_eq!(vault.require_locked().unwrap_err(), error!(VaultError::VaultNotLocked));
+    }
+
+    #[test]
+    fn test_lifetime_fees_overflow() {
+        let mut vault = empty_vault();
+        vault.record_fees(5, 7).unwrap();
+        assert_eq!((vault.lifetime_fees_a, vault.lifetime_fees_b), (5, 7));
+
+        vault.lifetime_fees_b = u128::MAX;
+        assert_eq!(
+            vault.record_fees(0, 1).unwrap_err(),
+            error!(VaultError::CounterOverflow)
+        );
+    }
 }
@@ -25,6 +25,33 @@ describe("inco-vault", () => {
         [Buffer.from("config")],
         program.programId
     );
+    const [programDataPda] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    it("Rejects config initialization by a non-deployer", async () => {
+        const imposter = Keypair.generate();
+        const sig = await provider.connection.requestAirdrop(imposter.publicKey, 1_000_000_000);
+        await provider.connection.confirmTransaction(sig);
+
+        try {
+            await program.methods
+                .initializeConfig()
+                .accounts({
+                    admin: imposter.publicKey,
+                    vaultConfig: configPda,
+                    program: program.programId,
+                    programData: programDataPda,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([imposter])
+                .rpc();
+            expect.fail("non-deployer initialized the config");
+        } catch (e) {
+            expect(e.toString()).to.include("UnauthorizedDeployer");
+        }
+    });
 
     it("Initializes the global vault config", async () => {
         const tx = await program.methods
@@ -32,6 +59,8 @@ describe("inco-vault", () => {
             .accounts({
                 admin: admin.publicKey,
                 vaultConfig: configPda,
+                program: program.programId,
+                programData: programDataPda,
                 systemProgram: SystemProgram.programId,
             })
             .rpc();
@@ -139,7 +168,7 @@ describe("inco-vault", () => {
 
             // 2. Add our program's verification instruction
             const verifyIx = await program.methods
-                .verifyDecryption(1, handles, plaintexts)
+                .verifyDecryption(1, handles, plaintexts, 1, null, 0)
                 .accounts({
                     authority: admin.publicKey,
                     instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -163,7 +192,7 @@ describe("inco-vault", () => {
         );
 
         await program.methods
-            .initializeVault()
+            .initializeVault(null)
             .accounts({
                 owner: admin.publicKey,
                 vault_pda: vaultPda,
@@ -176,4 +205,80 @@ describe("inco-vault", () => {
         expect(vault.locked).to.be.false;
         expect(vault.positionCount).to.equal(0);
     });
+
+    it("Lets the admin force-unlock a vault", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault"), admin.publicKey.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .forceUnlockVault()
+            .accounts({
+                admin: admin.publicKey,
+                vaultConfig: configPda,
+                vaultPda,
+            })
+            .rpc();
+
+        const vault = await program.account.vaultPda.fetch(vaultPda);
+        expect(vault.locked).to.be.false;
+    });
+
+    it("Reports vault stats", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault"), admin.publicKey.toBuffer()],
+            program.programId
+        );
+
+        const stats = await program.methods
+            .getVaultStats()
+            .accounts({ vaultPda })
+            .view();
+
+        expect(stats.positionCount).to.equal(0);
+        expect(stats.lifetimeFeesA.toString()).to.equal("0");
+        expect(stats.lifetimeFeesB.toString()).to.equal("0");
+        expect(stats.totalLiquidity.toString()).to.equal("0");
+    });
+
+    it("Reports the global config", async () => {
+        const config = await program.methods
+            .getConfig()
+            .accounts({ vaultConfig: configPda })
+            .view();
+
+        expect(config.programVersion).to.equal("0.1.0");
+        expect(config.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+        expect(config.paused).to.equal(false);
+        expect(config.minCollectThreshold.toString()).to.equal("0");
+        expect(config.safeTickLower).to.equal(-443636);
+        expect(config.safeTickUpper).to.equal(443636);
+        expect(config.maxPositionsPerVault).to.equal(4294967295);
+        expect(config.depositsPermanentlyDisabled).to.equal(false);
+        expect(config.acceptedMessageVersions).to.equal(1);
+        expect(config.pendingFeeRecipient.toBase58()).to.equal(PublicKey.default.toBase58());
+        expect(config.maxHandlesPerVerify).to.equal(16);
+        expect(config.highWaterMarkFees).to.equal(false);
+        expect(config.largeWithdrawalThreshold.toString()).to.equal("340282366920938463463374607431768211455");
+        expect(config.withdrawalDelay.toString()).to.equal("86400");
+    });
+
+    it("Closes an empty vault", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault"), admin.publicKey.toBuffer()],
+            program.programId
+        );
+
+        await program.methods
+            .closeVault()
+            .accounts({
+                owner: admin.publicKey,
+                vaultPda,
+            })
+            .rpc();
+
+        const info = await provider.connection.getAccountInfo(vaultPda);
+        expect(info).to.be.null;
+    });
 });
@@ -40,6 +40,7 @@ const VAULT_IDL: any = {
             "name": "createPositionWithLiquidity",
             "accounts": [
                 { "name": "authority", "isMut": true, "isSigner": true },
+                { "name": "funder", "isMut": true, "isSigner": true },
                 { "name": "vaultConfig", "isMut": false, "isSigner": false },
                 { "name": "vaultPda", "isMut": true, "isSigner": false },
                 { "name": "positionTracker", "isMut": true, "isSigner": false },
@@ -69,7 +70,8 @@ const VAULT_IDL: any = {
                 { "name": "liquidityAmount", "type": "u128" },
                 { "name": "tokenMaxA", "type": "u64" },
                 { "name": "tokenMaxB", "type": "u64" },
-                { "name": "maxSlippageBps", "type": { "option": "u16" } }
+                { "name": "maxSlippageBps", "type": { "option": "u16" } },
+                { "name": "label", "type": { "option": { "array": ["u8", 32] } } }
             ]
         },
         {
@@ -97,7 +99,9 @@ const VAULT_IDL: any = {
                 { "name": "liquidityAmount", "type": "u128" },
                 { "name": "minAmountA", "type": "u64" },
                 { "name": "minAmountB", "type": "u64" },
-                { "name": "closePosition", "type": "bool" }
+                { "name": "closePosition", "type": "bool" },
+                { "name": "requireAttestation", "type": "bool" },
+                { "name": "collectFeesFirst", "type": { "option": "bool" } }
             ]
         },
         {
@@ -112,6 +116,8 @@ const VAULT_IDL: any = {
                 { "name": "positionTokenAccount", "isMut": false, "isSigner": false },
                 { "name": "tokenVaultA", "isMut": true, "isSigner": false },
                 { "name": "tokenVaultB", "isMut": true, "isSigner": false },
+                { "name": "tokenMintA", "isMut": false, "isSigner": false },
+                { "name": "tokenMintB", "isMut": false, "isSigner": false },
                 { "name": "feeAccountA", "isMut": true, "isSigner": false },
                 { "name": "feeAccountB", "isMut": true, "isSigner": false },
                 { "name": "rewardAccount0", "isMut": true, "isOptional": true, "isSigner": false },
@@ -119,13 +125,28 @@ const VAULT_IDL: any = {
                 { "name": "rewardAccount2", "isMut": true, "isOptional": true, "isSigner": false },
                 { "name": "incoLightningProgram", "isMut": false, "isSigner": false },
                 { "name": "whirlpoolProgram", "isMut": false, "isSigner": false },
-                { "name": "tokenProgram", "isMut": false, "isSigner": false }
+                { "name": "tokenProgram", "isMut": false, "isSigner": false },
+                { "name": "associatedTokenProgram", "isMut": false, "isSigner": false },
+                { "name": "systemProgram", "isMut": false, "isSigner": false }
             ],
-            "args": []
+            "args": [
+                { "name": "encrypt", "type": "bool" },
+                { "name": "rewardMin", "type": { "option": { "array": ["u64", 3] } } }
+            ]
         }
     ]
 };
 
+// Pads a UTF-8 label to the program's fixed 32-byte field
+function encodeLabel(label?: string): number[] | null {
+    if (!label) return null;
+    const bytes = Buffer.from(label, 'utf8');
+    if (bytes.length > 32) throw new Error("Label must be at most 32 bytes");
+    const padded = Buffer.alloc(32);
+    bytes.copy(padded);
+    return Array.from(padded);
+}
+
 export async function createVaultPosition(req: Request, res: Response) {
     try {
         const {
@@ -139,7 +160,8 @@ export async function createVaultPosition(req: Request, res: Response) {
             encryptedAmountB, // base64 string
             amountA, // cleartext amount for liquidity calc
             amountType,
-            slippageBps
+            slippageBps,
+            label // optional display label, UTF-8, at most 32 bytes
         } = req.body;
 
         if (!wallet || !whirlpool || !encryptedAmountA || !encryptedAmountB || !amountA) {
@@ -326,10 +348,12 @@ export async function createVaultPosition(req: Request, res: Response) {
             new BN(liquidityAmount.toString()),
             new BN(tokenMaxA.toString()),
             new BN(tokenMaxB.toString()),
-            slippageBps !== undefined ? slippageBps : null // u16 option
+            slippageBps !== undefined ? slippageBps : null, // u16 option
+            encodeLabel(label)
         )
             .accounts({
                 authority: walletPubkey,
+                funder: walletPubkey,
                 vaultConfig,
                 vaultPda,
                 positionTracker,
@@ -380,7 +404,9 @@ export async function withdrawVaultPosition(req: Request, res: Response) {
             positionMint,
             liquidityAmount,
             closePosition,
-            slippageBps
+            slippageBps,
+            requireAttestation,
+            collectFeesFirst
         } = req.body;
 
         if (!wallet || !whirlpool || !positionMint || !liquidityAmount) {
@@ -470,7 +496,9 @@ export async function withdrawVaultPosition(req: Request, res: Response) {
             new BN(liquidityAmount),
             new BN(tokenMinA),
             new BN(tokenMinB),
-            closePosition || false
+            closePosition || false,
+            requireAttestation || false,
+            collectFeesFirst !== undefined ? collectFeesFirst : null // bool option
         )
             .accounts({
                 authority: walletPubkey,
@@ -517,7 +545,9 @@ export async function collectVaultProfits(req: Request, res: Response) {
             whirlpool,
             positionMint,
             tokenMintA,
-            tokenMintB
+            tokenMintB,
+            encrypt,
+            rewardMin
         } = req.body;
 
         if (!wallet || !whirlpool || !positionMint || !tokenMintA || !tokenMintB) {
@@ -547,11 +577,14 @@ export async function collectVaultProfits(req: Request, res: Response) {
         const [tokenVaultA] = PublicKey.findProgramAddressSync([Buffer.from("vault_a"), vaultPda.toBuffer(), new PublicKey(tokenMintA).toBuffer()], INCO_VAULT_PROGRAM_ID);
         const [tokenVaultB] = PublicKey.findProgramAddressSync([Buffer.from("vault_b"), vaultPda.toBuffer(), new PublicKey(tokenMintB).toBuffer()], INCO_VAULT_PROGRAM_ID);
 
-        // Fee stats accounts
-        const [feeAccountA] = PublicKey.findProgramAddressSync([Buffer.from("fees_a"), vaultPda.toBuffer(), new PublicKey(tokenMintA).toBuffer()], INCO_VAULT_PROGRAM_ID);
-        const [feeAccountB] = PublicKey.findProgramAddressSync([Buffer.from("fees_b"), vaultPda.toBuffer(), new PublicKey(tokenMintB).toBuffer()], INCO_VAULT_PROGRAM_ID);
+        // Fee accounts: the vault PDA's ATAs (created on first collect)
+        const feeAccountA = await getAssociatedTokenAddress(new PublicKey(tokenMintA), vaultPda, true);
+        const feeAccountB = await getAssociatedTokenAddress(new PublicKey(tokenMintB), vaultPda, true);
 
-        const tx = await program.methods.collectAllProfits()
+        const tx = await program.methods.collectAllProfits(
+            encrypt !== undefined ? encrypt : true,
+            rewardMin ? rewardMin.map((m: string | number) => new BN(m.toString())) : null // [u64; 3] option
+        )
             .accounts({
                 authority: walletPubkey,
                 vaultConfig,
@@ -562,6 +595,8 @@ export async function collectVaultProfits(req: Request, res: Response) {
                 positionTokenAccount,
                 tokenVaultA,
                 tokenVaultB,
+                tokenMintA: new PublicKey(tokenMintA),
+                tokenMintB: new PublicKey(tokenMintB),
                 feeAccountA,
                 feeAccountB,
                 rewardAccount0: null,
@@ -569,7 +604,9 @@ export async function collectVaultProfits(req: Request, res: Response) {
                 rewardAccount2: null,
                 incoLightningProgram: INCO_LIGHTNING_ID,
                 whirlpoolProgram: WHIRLPOOL_PROGRAM_ID,
-                tokenProgram: TOKEN_PROGRAM_ID
+                tokenProgram: TOKEN_PROGRAM_ID,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId
             })
             .transaction();
         const { blockhash } = await connection.getLatestBlockhash();
@@ -103,10 +103,12 @@ export async function createEncryptedPosition(
             1000000n,                               // liquidity (placeholder)
             1000000n,                               // maxA
             1000000n,                               // maxB
-            null                                    // maxSlippage
+            null,                                   // maxSlippage
+            null                                    // label
         )
         .accounts({
             authority: wallet.publicKey,
+            funder: wallet.publicKey,
             vaultConfig: PublicKey.findProgramAddressSync([Buffer.from('config')], program.programId)[0],
             vaultPda: vaultPda,
             positionTracker: trackerPda,
@@ -187,7 +189,10 @@ export async function decryptWithOnChainVerification(
         .verifyDecryption(
             result.handles.length,
             handleBuffers,
-            plaintextBuffers
+            plaintextBuffers,
+            result.ed25519Instructions.length,
+            null, // attestations start at instruction index 0
+            0     // v0 message layout: handle || plaintext (16 bytes each)
         )
         .accounts({
             authority: wallet.publicKey,